@@ -1,4 +1,5 @@
-use rocksdb::{ColumnFamilyDescriptor, Options, DB};
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
 
 use std::path::Path;
 use std::sync::Arc;
@@ -6,9 +7,27 @@ use std::sync::Arc;
 mod column_families {
 	pub const EXECUTED_BLOCKS: &str = "executed_blocks";
 	pub const SYNCED_HEIGHT: &str = "synced_height";
+	pub const WRITE_AHEAD_LOG: &str = "write_ahead_log";
 }
 use column_families::*;
 
+/// The progress of a block read from the DA, see [`DaWriteAheadLogEntry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalStatus {
+	/// The block was handed to the executor, it may or may not be in AptosDB.
+	Submitted,
+}
+
+/// An entry of the write-ahead log, recorded before a block read from the DA is executed.
+/// The entry is deleted once the DA DB records the block as executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaWriteAheadLogEntry {
+	/// The digest of the id of the blob.
+	pub blob_id: [u8; 32],
+	pub celestia_height: u64,
+	pub commit_status: WalStatus,
+}
+
 /// Simple data store for locally recorded DA events.
 ///
 /// An async access API is provided to avoid blocking async tasks.
@@ -26,26 +45,17 @@ impl DaDB {
 
 		let synced_height = ColumnFamilyDescriptor::new(SYNCED_HEIGHT, Options::default());
 		let executed_blocks = ColumnFamilyDescriptor::new(EXECUTED_BLOCKS, Options::default());
+		let write_ahead_log = ColumnFamilyDescriptor::new(WRITE_AHEAD_LOG, Options::default());
 
-		let db = DB::open_cf_descriptors(&options, path, vec![synced_height, executed_blocks])
-			.map_err(|e| anyhow::anyhow!("Failed to open DA DB: {:?}", e))?;
+		let db = DB::open_cf_descriptors(
+			&options,
+			path,
+			vec![synced_height, executed_blocks, write_ahead_log],
+		)
+		.map_err(|e| anyhow::anyhow!("Failed to open DA DB: {:?}", e))?;
 		Ok(Self { inner: Arc::new(db) })
 	}
 
-	pub async fn add_executed_block(&self, id: String) -> Result<(), anyhow::Error> {
-		let da_db = self.inner.clone();
-		tokio::task::spawn_blocking(move || {
-			let cf = da_db
-				.cf_handle(EXECUTED_BLOCKS)
-				.ok_or(anyhow::anyhow!("No executed_blocks column family"))?;
-			da_db
-				.put_cf(&cf, id.clone(), id)
-				.map_err(|e| anyhow::anyhow!("Failed to add executed block: {:?}", e))
-		})
-		.await??;
-		Ok(())
-	}
-
 	pub async fn has_executed_block(&self, id: String) -> Result<bool, anyhow::Error> {
 		let da_db = self.inner.clone();
 		let id = tokio::task::spawn_blocking(move || {
@@ -60,23 +70,6 @@ impl DaDB {
 		Ok(id.is_some())
 	}
 
-	pub async fn set_synced_height(&self, height: u64) -> Result<(), anyhow::Error> {
-		// This is heavy for this purpose, but progressively the contents of the DA DB will be used for more things
-		let da_db = self.inner.clone();
-		tokio::task::spawn_blocking(move || {
-			let cf = da_db
-				.cf_handle(SYNCED_HEIGHT)
-				.ok_or(anyhow::anyhow!("No synced_height column family"))?;
-			let height = serde_json::to_string(&height)
-				.map_err(|e| anyhow::anyhow!("Failed to serialize synced height: {:?}", e))?;
-			da_db
-				.put_cf(&cf, "synced_height", height)
-				.map_err(|e| anyhow::anyhow!("Failed to set synced height: {:?}", e))
-		})
-		.await??;
-		Ok(())
-	}
-
 	pub async fn get_synced_height(&self) -> Result<u64, anyhow::Error> {
 		// This is heavy for this purpose, but progressively the contents of the DA DB will be used for more things
 		let da_db = self.inner.clone();
@@ -97,4 +90,141 @@ impl DaDB {
 		.await??;
 		Ok(height)
 	}
+
+	/// Records in the write-ahead log that a block is about to be executed.
+	pub async fn record_submitted_block(
+		&self,
+		blob_id: [u8; 32],
+		celestia_height: u64,
+	) -> Result<(), anyhow::Error> {
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(WRITE_AHEAD_LOG)
+				.ok_or(anyhow::anyhow!("No write_ahead_log column family"))?;
+			let entry = DaWriteAheadLogEntry {
+				blob_id,
+				celestia_height,
+				commit_status: WalStatus::Submitted,
+			};
+			let entry = serde_json::to_vec(&entry)
+				.map_err(|e| anyhow::anyhow!("Failed to serialize WAL entry: {:?}", e))?;
+			da_db
+				.put_cf(&cf, blob_id, entry)
+				.map_err(|e| anyhow::anyhow!("Failed to record submitted block: {:?}", e))
+		})
+		.await??;
+		Ok(())
+	}
+
+	/// Deletes the write-ahead log entry of a blob whose block was not executed.
+	pub async fn discard_submitted_block(&self, blob_id: [u8; 32]) -> Result<(), anyhow::Error> {
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(WRITE_AHEAD_LOG)
+				.ok_or(anyhow::anyhow!("No write_ahead_log column family"))?;
+			da_db
+				.delete_cf(&cf, blob_id)
+				.map_err(|e| anyhow::anyhow!("Failed to discard submitted block: {:?}", e))
+		})
+		.await??;
+		Ok(())
+	}
+
+	/// Records a block as executed, marks the DA height as synced and deletes the write-ahead log
+	/// entry of the block, in a single atomic write.
+	pub async fn commit_executed_block(
+		&self,
+		id: String,
+		blob_id: [u8; 32],
+		synced_height: u64,
+	) -> Result<(), anyhow::Error> {
+		let da_db = self.inner.clone();
+		tokio::task::spawn_blocking(move || {
+			let executed_blocks = da_db
+				.cf_handle(EXECUTED_BLOCKS)
+				.ok_or(anyhow::anyhow!("No executed_blocks column family"))?;
+			let synced_height_cf = da_db
+				.cf_handle(SYNCED_HEIGHT)
+				.ok_or(anyhow::anyhow!("No synced_height column family"))?;
+			let write_ahead_log = da_db
+				.cf_handle(WRITE_AHEAD_LOG)
+				.ok_or(anyhow::anyhow!("No write_ahead_log column family"))?;
+			let synced_height = serde_json::to_string(&synced_height)
+				.map_err(|e| anyhow::anyhow!("Failed to serialize synced height: {:?}", e))?;
+
+			let mut batch = WriteBatch::default();
+			batch.put_cf(&executed_blocks, id.clone(), id);
+			batch.put_cf(&synced_height_cf, "synced_height", synced_height);
+			batch.delete_cf(&write_ahead_log, blob_id);
+			da_db
+				.write(batch)
+				.map_err(|e| anyhow::anyhow!("Failed to commit executed block: {:?}", e))
+		})
+		.await??;
+		Ok(())
+	}
+
+	/// Gets the write-ahead log entries of the blocks submitted for execution but not recorded as
+	/// executed, by increasing Celestia height.
+	pub async fn get_submitted_blocks(&self) -> Result<Vec<DaWriteAheadLogEntry>, anyhow::Error> {
+		let da_db = self.inner.clone();
+		let entries = tokio::task::spawn_blocking(move || {
+			let cf = da_db
+				.cf_handle(WRITE_AHEAD_LOG)
+				.ok_or(anyhow::anyhow!("No write_ahead_log column family"))?;
+			let mut entries = Vec::new();
+			for item in da_db.iterator_cf(&cf, IteratorMode::Start) {
+				let (_, value) =
+					item.map_err(|e| anyhow::anyhow!("Failed to read WAL entry: {:?}", e))?;
+				let entry: DaWriteAheadLogEntry = serde_json::from_slice(&value)
+					.map_err(|e| anyhow::anyhow!("Failed to deserialize WAL entry: {:?}", e))?;
+				entries.push(entry);
+			}
+			entries.sort_by_key(|entry| entry.celestia_height);
+			Ok::<Vec<DaWriteAheadLogEntry>, anyhow::Error>(entries)
+		})
+		.await??;
+		Ok(entries)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_write_ahead_log() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let da_db = DaDB::open(dir.path())?;
+
+		da_db.record_submitted_block([1; 32], 5).await?;
+		da_db.record_submitted_block([2; 32], 3).await?;
+		let submitted = da_db.get_submitted_blocks().await?;
+		let heights: Vec<u64> = submitted.iter().map(|entry| entry.celestia_height).collect();
+		assert_eq!(heights, vec![3, 5]);
+
+		da_db.commit_executed_block("block".to_string(), [2; 32], 2).await?;
+		let submitted = da_db.get_submitted_blocks().await?;
+		assert_eq!(submitted.len(), 1);
+		assert_eq!(submitted[0].blob_id, [1; 32]);
+		assert!(da_db.has_executed_block("block".to_string()).await?);
+		assert_eq!(da_db.get_synced_height().await?, 2);
+		// the entry of the committed block is deleted
+		let write_ahead_log = da_db.inner.cf_handle(WRITE_AHEAD_LOG).unwrap();
+		assert_eq!(da_db.inner.iterator_cf(&write_ahead_log, IteratorMode::Start).count(), 1);
+
+		// the log survives restarts
+		drop(da_db);
+		let da_db = DaDB::open(dir.path())?;
+		assert_eq!(da_db.get_submitted_blocks().await?.len(), 1);
+
+		// a blob whose block is not executed is dropped from the log alone
+		da_db.discard_submitted_block([1; 32]).await?;
+		assert!(da_db.get_submitted_blocks().await?.is_empty());
+		assert_eq!(da_db.get_synced_height().await?, 2);
+
+		Ok(())
+	}
 }
@@ -1,6 +1,6 @@
 mod da_db;
 pub mod manager;
-pub mod partial;
+pub mod rollup;
 mod tasks;
 
 #[cfg(test)]
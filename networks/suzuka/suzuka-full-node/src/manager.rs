@@ -1,4 +1,4 @@
-use super::partial::SuzukaPartialNode;
+use super::rollup::RollupNode;
 use anyhow::Context;
 use godfig::{backend::config_file::ConfigFile, Godfig};
 use suzuka_config::Config;
@@ -43,11 +43,9 @@ impl Manager {
 
 		let config = self.godfig.try_wait_for_ready().await?;
 
-		let node = SuzukaPartialNode::try_from_config(config)
-			.await
-			.context("Failed to create the executor")?;
+		let node = RollupNode::new(config).await?;
 
-		let join_handle = tokio::spawn(node.run());
+		let join_handle = tokio::spawn(async move { node.run().await });
 
 		// Use tokio::select! to wait for either the handle or a cancellation signal
 		tokio::select! {
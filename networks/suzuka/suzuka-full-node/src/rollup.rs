@@ -0,0 +1,361 @@
+use crate::{da_db::DaDB, tasks};
+use m1_da_light_node_client::{
+	blob_response, LightNodeServiceClient, StreamReadFromHeightRequest,
+	StreamReadFromHeightResponse,
+};
+use maptos_dof_execution::{
+	v1::Executor, ApplyResult, BlockRetry, DaIngestionConfig, DynOptFinExecutor,
+	MakeOptFinServices, VerificationMode, Verifier,
+};
+use mcr_settlement_client::McrSettlementClient;
+use mcr_settlement_manager::{
+	CommitmentEventStream, McrSettlementManager, McrSettlementManagerOperations,
+};
+use movement_rest::MovementRest;
+use movement_types::block::{BlockCommitment, BlockCommitmentEvent};
+use suzuka_config::Config;
+
+use anyhow::Context;
+use futures::{future::Either, stream};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::{select, try_join};
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+/// Accepts the blobs streamed by the DA light node, which only streams the blobs it verified
+/// in its own verification mode, with their envelope stripped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LightNodeVerifier;
+
+#[tonic::async_trait]
+impl Verifier for LightNodeVerifier {
+	async fn verifiy_validator_in(
+		&self,
+		_verification_mode: VerificationMode,
+		_blob: &[u8],
+		_height: u64,
+	) -> Result<bool, anyhow::Error> {
+		Ok(true)
+	}
+
+	async fn verify_m_of_n(
+		&self,
+		_verification_mode: VerificationMode,
+		_blob: &[u8],
+		_height: u64,
+	) -> Result<bool, anyhow::Error> {
+		Ok(true)
+	}
+}
+
+/// The runnable rollup node, composing the executor, the DA verifier, the DA light node client
+/// and the settlement of the blocks.
+pub struct RollupNode {
+	executor: Executor,
+	verifier: Arc<dyn Verifier + Send + Sync>,
+	light_node_client: LightNodeServiceClient<tonic::transport::Channel>,
+	settlement_manager: McrSettlementManager,
+	// Stream receiving commitment events, conditionally enabled
+	commitment_events:
+		Either<CommitmentEventStream, stream::Pending<<CommitmentEventStream as Stream>::Item>>,
+	movement_rest: MovementRest,
+	da_db: DaDB,
+	config: Config,
+	// The blobs the write-ahead log records as submitted for execution but not committed,
+	// when the node was started.
+	submitted_blobs: HashSet<[u8; 32]>,
+	// The commitment of the block head when the node was started, if blobs were submitted
+	// for execution but not recorded as executed before a crash.
+	committed_before_restart: Option<BlockCommitment>,
+}
+
+impl RollupNode {
+	/// Connects to the DA light node and initializes the executor, the verifier, the
+	/// settlement manager and the DA DB.
+	pub async fn new(config: Config) -> Result<Self, anyhow::Error> {
+		// todo: extract into getter
+		let light_node_connection_hostname = config
+			.m1_da_light_node
+			.m1_da_light_node_config
+			.m1_da_light_node_connection_hostname();
+
+		// todo: extract into getter
+		let light_node_connection_port = config
+			.m1_da_light_node
+			.m1_da_light_node_config
+			.m1_da_light_node_connection_port();
+		// todo: extract into getter
+		debug!(
+			"Connecting to light node at {}:{}",
+			light_node_connection_hostname, light_node_connection_port
+		);
+		let light_node_client = m1_da_light_node_client::connect(format!(
+			"http://{}:{}",
+			light_node_connection_hostname, light_node_connection_port
+		))
+		.await
+		.context("Failed to connect to light node")?;
+
+		debug!("Creating the executor");
+		let executor = Executor::try_from_config(&config.execution_config.maptos_config)
+			.context("Failed to create the inner executor")?;
+
+		debug!("Creating the settlement client");
+		let settlement_client = McrSettlementClient::build_with_config(&config.mcr)
+			.await
+			.context("Failed to build MCR settlement client with config")?;
+		let (settlement_manager, commitment_events) =
+			McrSettlementManager::new(settlement_client, &config.mcr);
+		let commitment_events = if config.mcr.should_settle() {
+			Either::Left(commitment_events)
+		} else {
+			Either::Right(stream::pending())
+		};
+
+		debug!("Creating the movement rest service");
+		let movement_rest =
+			MovementRest::try_from_env().context("Failed to create MovementRest")?;
+
+		debug!("Creating the DA DB");
+		let da_db =
+			DaDB::open(&config.da_db.da_db_path).context("Failed to create or get DA DB")?;
+
+		Ok(Self {
+			executor,
+			verifier: Arc::new(LightNodeVerifier),
+			light_node_client,
+			settlement_manager,
+			commitment_events,
+			movement_rest,
+			da_db,
+			config,
+			submitted_blobs: HashSet::new(),
+			committed_before_restart: None,
+		})
+	}
+
+	fn settlement_enabled(&self) -> bool {
+		matches!(&self.commitment_events, Either::Left(_))
+	}
+
+	// ! Currently this only implements opt.
+	/// Runs the node until crash or shutdown: the blobs streamed by the DA light node are
+	/// verified in order, and their blocks are executed and settled.
+	pub async fn run(mut self) -> Result<(), anyhow::Error> {
+		let (transaction_sender, transaction_receiver) = mpsc::channel(16);
+		let (context, exec_background) = self
+			.executor
+			.background(transaction_sender, &self.config.execution_config.maptos_config)?;
+		let services = context.services();
+		self.movement_rest.set_context(services.opt_api_context());
+		let transaction_ingress_task = tasks::transaction_ingress::Task::new(
+			transaction_receiver,
+			self.light_node_client.clone(),
+			// FIXME: why are the struct member names so tautological?
+			self.config.m1_da_light_node.m1_da_light_node_config.clone(),
+		);
+
+		let (
+			execution_and_settlement_result,
+			transaction_ingress_result,
+			background_task_result,
+			services_result,
+		) = try_join!(
+			tokio::spawn(async move { self.run_da_loop().await }),
+			tokio::spawn(async move { transaction_ingress_task.run().await }),
+			tokio::spawn(exec_background),
+			tokio::spawn(services.run()),
+			// tokio::spawn(async move { movement_rest.run_service().await }),
+		)?;
+		execution_and_settlement_result
+			.and(transaction_ingress_result)
+			.and(background_task_result)
+			.and(services_result)
+	}
+
+	async fn run_da_loop(mut self) -> anyhow::Result<()> {
+		self.executor.set_da_ingestion(DaIngestionConfig {
+			verifier: self.verifier.clone(),
+			blob_format: self
+				.config
+				.m1_da_light_node
+				.m1_da_light_node_config
+				.m1_da_light_node_blob_format(),
+			// this has to be deterministic, otherwise nodes will not be able to agree on the
+			// block commitment
+			retry: BlockRetry {
+				count: self.config.execution_extension.block_retry_count,
+				increment_microseconds: self
+					.config
+					.execution_extension
+					.block_retry_increment_microseconds,
+			},
+		})?;
+
+		// blobs submitted for execution before a crash are read again from their DA height
+		let mut height = self.da_db.get_synced_height().await?;
+		for entry in self.da_db.get_submitted_blocks().await? {
+			info!(
+				celestia_height = entry.celestia_height,
+				"Replaying block submitted before restart"
+			);
+			height = height.min(entry.celestia_height);
+			self.submitted_blobs.insert(entry.blob_id);
+		}
+		// blocks are executed one at a time, so only the block head may have been committed
+		// without being recorded as executed; it must be found before the genesis rollover
+		if !self.submitted_blobs.is_empty() {
+			self.committed_before_restart = Some(self.executor.get_block_head_commitment()?);
+		}
+
+		// TODO: this is a temporary solution to rollover the genesis block, really this
+		// (a) needs to be read from the DA and
+		// (b) requires modifications to Aptos Core.
+		self.executor.rollover_genesis_block().await?;
+
+		let mut blobs_from_da = self
+			.light_node_client
+			.stream_read_from_height(StreamReadFromHeightRequest { height })
+			.await?
+			.into_inner();
+
+		loop {
+			select! {
+				Some(res) = blobs_from_da.next() => {
+					let response = res.context("failed to get next blob from DA")?;
+					self.process_blob_from_da(response).await?;
+				}
+				Some(res) = self.commitment_events.next() => {
+					let event = res.context("failed to get commitment event")?;
+					self.process_commitment_event(event).await?;
+				}
+				else => break,
+			}
+		}
+		Ok(())
+	}
+
+	async fn process_blob_from_da(
+		&mut self,
+		response: StreamReadFromHeightResponse,
+	) -> anyhow::Result<()> {
+		let blob = match response
+			.blob
+			.ok_or(anyhow::anyhow!("No blob in response"))?
+			.blob_type
+			.ok_or(anyhow::anyhow!("No blob type in response"))?
+		{
+			blob_response::BlobType::SequencedBlobBlock(blob) => blob,
+			_ => {
+				anyhow::bail!("Invalid blob type in response")
+			}
+		};
+		let blob_id = blob.blob_id;
+
+		// check if the block has already been executed
+		if self.da_db.has_executed_block(blob_id.clone()).await? {
+			warn!("Block already executed: {:#?}. It will be skipped", blob_id);
+			return Ok(());
+		}
+
+		// the da height must be greater than 1
+		if blob.height < 2 {
+			anyhow::bail!("Invalid DA height: {:?}", blob.height);
+		}
+
+		let wal_id: [u8; 32] = Sha256::digest(blob_id.as_bytes()).into();
+		let submitted_before_restart = self.submitted_blobs.remove(&wal_id);
+		self.da_db.record_submitted_block(wal_id, blob.height).await?;
+
+		// the light node only serves the blobs it verified
+		let span = info_span!(target: "movement_timing", "execute_block", id = %blob_id);
+		let result = self
+			.executor
+			.apply_da_blob_opt(&blob.data, blob.height, blob.timestamp, VerificationMode::Cowboy)
+			.instrument(span)
+			.await?;
+
+		// mark the da_height - 1 as synced
+		// we can't mark this height as synced because we must allow for the possibility of multiple blocks at the same height according to the m1 da specifications (which currently is built on celestia which itself allows more than one block at the same height)
+		// the block is set as executed and its write-ahead log entry deleted in the same write
+		match result {
+			ApplyResult::Committed { tx_count, commitment, .. } => {
+				// decrement the number of transactions in flight on the executor
+				self.executor.decrement_transactions_in_flight(tx_count.into());
+				self.da_db
+					.commit_executed_block(blob_id.clone(), wal_id, blob.height - 1)
+					.await?;
+				info!("Executed block: {}", blob_id);
+				self.settle(&blob_id, commitment).await;
+			}
+			ApplyResult::AlreadyApplied => {
+				self.da_db
+					.commit_executed_block(blob_id.clone(), wal_id, blob.height - 1)
+					.await?;
+				// a block committed before a restart was not settled
+				match self.committed_before_restart.take() {
+					Some(commitment) if submitted_before_restart => {
+						warn!(blob_id = %blob_id, "Block was committed before restart, skipping its execution");
+						self.settle(&blob_id, commitment).await;
+					}
+					commitment => {
+						self.committed_before_restart = commitment;
+						warn!(blob_id = %blob_id, "Block already applied, it is not settled again");
+					}
+				}
+			}
+			ApplyResult::Skipped(reason) => {
+				// the blob is read again after a restart, and skipped alike
+				warn!(blob_id = %blob_id, %reason, "Skipping blob, its block is not executed");
+				self.da_db.discard_submitted_block(wal_id).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn settle(&self, blob_id: &str, commitment: BlockCommitment) {
+		// todo: this needs defaults
+		if self.settlement_enabled() {
+			info!("Posting block commitment via settlement manager");
+			match self.settlement_manager.post_block_commitment(commitment).await {
+				Ok(_) => {}
+				Err(e) => {
+					error!("Failed to post block commitment: {:?}", e);
+				}
+			}
+		} else {
+			info!(blob_id = %blob_id, "Skipping settlement");
+		}
+	}
+
+	async fn process_commitment_event(
+		&mut self,
+		event: BlockCommitmentEvent,
+	) -> anyhow::Result<()> {
+		match event {
+			BlockCommitmentEvent::Accepted(commitment) => {
+				debug!("Commitment accepted: {:?}", commitment);
+				self.executor
+					.set_finalized_block_height(commitment.height())
+					.context("failed to set finalized block height")
+			}
+			BlockCommitmentEvent::Rejected { height, reason } => {
+				debug!("Commitment rejected: {:?} {:?}", height, reason);
+				let current_head_height = self.executor.get_block_head_height()?;
+				if height > current_head_height {
+					// Nothing to revert
+					Ok(())
+				} else {
+					self.executor
+						.revert_block_head_to(height - 1)
+						.await
+						.context(format!("failed to revert to block height {}", height - 1))
+				}
+			}
+		}
+	}
+}
@@ -1,4 +1,3 @@
 //! Modules to separate full node processing into actor-like tasks.
 
-pub mod execute_settle;
 pub mod transaction_ingress;
@@ -3,3 +3,29 @@ pub mod test;
 
 pub use m1_da_light_node_grpc::light_node_service_client::LightNodeServiceClient;
 pub use m1_da_light_node_grpc::*;
+
+use tonic::transport::Channel;
+use tonic_health::pb::{
+	health_check_response::ServingStatus, health_client::HealthClient, HealthCheckRequest,
+};
+
+/// Checks that the light node reports its service as serving over the gRPC health protocol.
+pub async fn check_health(channel: Channel) -> Result<(), anyhow::Error> {
+	let response = HealthClient::new(channel)
+		.check(HealthCheckRequest { service: LIGHT_NODE_SERVICE_NAME.to_string() })
+		.await
+		.map_err(|e| anyhow::anyhow!("Failed to check light node health: {}", e))?;
+	match response.into_inner().status() {
+		ServingStatus::Serving => Ok(()),
+		status => anyhow::bail!("Light node is not serving: {:?}", status),
+	}
+}
+
+/// Connects to the light node at the given endpoint, checking its health before use.
+pub async fn connect(
+	endpoint: impl Into<String>,
+) -> Result<LightNodeServiceClient<Channel>, anyhow::Error> {
+	let channel = Channel::from_shared(endpoint.into())?.connect().await?;
+	check_health(channel.clone()).await?;
+	Ok(LightNodeServiceClient::new(channel))
+}
@@ -1,3 +1,7 @@
 tonic::include_proto!("movementlabs.protocol_units.da.m1.light_node.v1beta1"); // The string specified here
+/// The fully qualified name of the light node gRPC service.
+pub const LIGHT_NODE_SERVICE_NAME: &str =
+	"movementlabs.protocol_units.da.m1.light_node.v1beta1.LightNodeService";
+
 pub const FILE_DESCRIPTOR_SET: &[u8] =
 	tonic::include_file_descriptor_set!("m1-da-light-node-grpc-descriptor");
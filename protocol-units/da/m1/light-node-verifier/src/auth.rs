@@ -0,0 +1,140 @@
+//! Authentication of the blobs submitted to the DA.
+//!
+//! When an [`HmacKey`] is configured, every submitted blob is prefixed with an HMAC-SHA256
+//! over the rest of the blob, so that blobs written to the namespace by anyone without the
+//! key are rejected even in cowboy verification mode.
+
+use crate::payload::VerificationError;
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Debug, Formatter};
+
+/// The length of the MAC prefixed to authenticated blobs.
+pub const MAC_LEN: usize = 32;
+
+const SHA256_BLOCK_LEN: usize = 64;
+
+/// The key shared by the light nodes to authenticate blobs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HmacKey(pub [u8; 32]);
+
+impl Debug for HmacKey {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("HmacKey(<redacted>)")
+	}
+}
+
+/// Computes the HMAC-SHA256 of a message, as specified in RFC 2104.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; MAC_LEN] {
+	let mut block_key = [0u8; SHA256_BLOCK_LEN];
+	if key.len() > SHA256_BLOCK_LEN {
+		block_key[..MAC_LEN].copy_from_slice(&Sha256::digest(key));
+	} else {
+		block_key[..key.len()].copy_from_slice(key);
+	}
+
+	let mut inner = Sha256::new();
+	inner.update(block_key.map(|byte| byte ^ 0x36));
+	inner.update(message);
+	let mut outer = Sha256::new();
+	outer.update(block_key.map(|byte| byte ^ 0x5c));
+	outer.update(inner.finalize());
+	outer.finalize().into()
+}
+
+/// Prefixes the blob with its MAC when a key is set, and returns it unchanged otherwise.
+pub fn encode_authenticated(key: Option<&HmacKey>, blob: Vec<u8>) -> Vec<u8> {
+	let Some(key) = key else {
+		return blob;
+	};
+	let mut authenticated = Vec::with_capacity(MAC_LEN + blob.len());
+	authenticated.extend_from_slice(&hmac_sha256(&key.0, &blob));
+	authenticated.extend(blob);
+	authenticated
+}
+
+/// Checks and strips the MAC prefix of a blob when a key is set.
+/// When no key is set, all blobs are accepted as they are.
+pub fn decode_authenticated<'a>(
+	key: Option<&HmacKey>,
+	blob: &'a [u8],
+) -> Result<&'a [u8], VerificationError> {
+	let Some(key) = key else {
+		return Ok(blob);
+	};
+	if blob.len() < MAC_LEN {
+		return Err(VerificationError::MissingMac);
+	}
+	let (mac, payload) = blob.split_at(MAC_LEN);
+	let expected = hmac_sha256(&key.0, payload);
+	// compare in constant time so the MAC cannot be guessed byte by byte
+	let difference = mac.iter().zip(expected.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+	if difference != 0 {
+		return Err(VerificationError::InvalidMac);
+	}
+	Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_hmac_sha256_rfc_4231_vectors() {
+		// test case 2
+		let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+		assert_eq!(
+			hex::encode(mac),
+			"5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+
+		// test case 6, with a key longer than the block size
+		let mac =
+			hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First");
+		assert_eq!(
+			hex::encode(mac),
+			"60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+		);
+	}
+
+	#[test]
+	fn test_round_trip() -> Result<(), anyhow::Error> {
+		let key = HmacKey([7; 32]);
+		let blob = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+		let authenticated = encode_authenticated(Some(&key), blob.clone());
+		assert_eq!(authenticated.len(), MAC_LEN + blob.len());
+		assert_eq!(decode_authenticated(Some(&key), &authenticated)?, blob.as_slice());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rejects_forged_blobs() {
+		let key = HmacKey([7; 32]);
+		let mut authenticated = encode_authenticated(Some(&key), vec![0, 1, 2, 3]);
+
+		let other_key = HmacKey([8; 32]);
+		assert_eq!(
+			decode_authenticated(Some(&other_key), &authenticated),
+			Err(VerificationError::InvalidMac)
+		);
+
+		*authenticated.last_mut().unwrap() ^= 1;
+		assert_eq!(
+			decode_authenticated(Some(&key), &authenticated),
+			Err(VerificationError::InvalidMac)
+		);
+		assert_eq!(
+			decode_authenticated(Some(&key), &authenticated[..MAC_LEN - 1]),
+			Err(VerificationError::MissingMac)
+		);
+	}
+
+	#[test]
+	fn test_accepts_all_blobs_without_key() -> Result<(), anyhow::Error> {
+		let blob = vec![0, 1, 2, 3];
+		assert_eq!(encode_authenticated(None, blob.clone()), blob);
+		assert_eq!(decode_authenticated(None, &blob)?, blob.as_slice());
+		Ok(())
+	}
+}
@@ -1,3 +1,6 @@
+pub mod auth;
+pub mod payload;
+pub mod proofs;
 pub mod v1;
 
 pub use m1_da_light_node_grpc::*;
@@ -19,6 +22,12 @@ pub trait Verifier {
 		}
 	}
 
+	/// Gets the block bytes carried by a blob which was verified, stripping any envelope
+	/// added when it was posted.
+	fn decode_blob<'a>(&self, blob: &'a [u8]) -> Result<&'a [u8], anyhow::Error> {
+		Ok(blob)
+	}
+
 	async fn verify_cowboy(
 		&self,
 		_verification_mode: VerificationMode,
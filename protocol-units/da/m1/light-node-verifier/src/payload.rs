@@ -0,0 +1,184 @@
+//! Versioning of the payloads submitted to the DA.
+//!
+//! Every submitted blob is prefixed with a 4-byte little-endian [`DaPayloadVersion`],
+//! so that verifiers can detect encodings they do not understand instead of misreading them.
+//! From version 2, the header is followed by the SHA-256 hash of the content,
+//! so that corrupted blobs are detected when they are fetched.
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::warn;
+
+/// The version of the encoding of a DA payload.
+pub type DaPayloadVersion = u32;
+
+/// The version written to newly submitted payloads.
+pub const CURRENT_DA_PAYLOAD_VERSION: DaPayloadVersion = 2;
+
+/// The version of payloads submitted before content hashes were introduced.
+pub const UNHASHED_DA_PAYLOAD_VERSION: DaPayloadVersion = 1;
+
+/// The versions this verifier is able to decode.
+pub const SUPPORTED_DA_PAYLOAD_VERSIONS: &[DaPayloadVersion] =
+	&[UNHASHED_DA_PAYLOAD_VERSION, CURRENT_DA_PAYLOAD_VERSION];
+
+/// The pseudo-version of payloads submitted before versioning was introduced.
+pub const LEGACY_DA_PAYLOAD_VERSION: DaPayloadVersion = 0;
+
+const VERSION_HEADER_LEN: usize = std::mem::size_of::<DaPayloadVersion>();
+
+const CONTENT_HASH_LEN: usize = 32;
+
+/// Errors thrown when verifying a blob.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerificationError {
+	#[error("Unsupported DA payload version {got}, supported versions are {supported:?}")]
+	UnsupportedVersion { got: DaPayloadVersion, supported: Vec<DaPayloadVersion> },
+	#[error("Blob is too short to carry a MAC")]
+	MissingMac,
+	#[error("Blob MAC does not match its contents")]
+	InvalidMac,
+	#[error("Payload is too short to carry a content hash")]
+	MissingContentHash,
+	#[error(
+		"Payload content hash mismatch, expected {} but got {}",
+		hex::encode(expected),
+		hex::encode(got)
+	)]
+	ContentHashMismatch { expected: [u8; CONTENT_HASH_LEN], got: [u8; CONTENT_HASH_LEN] },
+}
+
+/// Prefixes the payload with the current version header and its content hash.
+pub fn encode_payload(payload: &[u8]) -> Vec<u8> {
+	let mut blob = Vec::with_capacity(VERSION_HEADER_LEN + CONTENT_HASH_LEN + payload.len());
+	blob.extend_from_slice(&CURRENT_DA_PAYLOAD_VERSION.to_le_bytes());
+	blob.extend_from_slice(&Sha256::digest(payload));
+	blob.extend_from_slice(payload);
+	blob
+}
+
+/// Strips the version header from a blob, returning the version and the payload.
+/// The content hash is checked when the version carries one; payloads of older versions
+/// are returned unchecked.
+///
+/// When `accept_legacy` is set, blobs without a supported header are taken to be
+/// unversioned legacy payloads and returned whole as [`LEGACY_DA_PAYLOAD_VERSION`].
+/// This is only meant for the migration window, as legacy payloads cannot be told apart
+/// from payloads of unknown versions.
+pub fn decode_payload(
+	blob: &[u8],
+	accept_legacy: bool,
+) -> Result<(DaPayloadVersion, &[u8]), VerificationError> {
+	let version = blob
+		.get(..VERSION_HEADER_LEN)
+		.map(|header| {
+			let mut bytes = [0; VERSION_HEADER_LEN];
+			bytes.copy_from_slice(header);
+			DaPayloadVersion::from_le_bytes(bytes)
+		})
+		.unwrap_or(LEGACY_DA_PAYLOAD_VERSION);
+
+	if version == CURRENT_DA_PAYLOAD_VERSION {
+		let hashed = &blob[VERSION_HEADER_LEN..];
+		if hashed.len() < CONTENT_HASH_LEN {
+			return Err(VerificationError::MissingContentHash);
+		}
+		let (expected, payload) = hashed.split_at(CONTENT_HASH_LEN);
+		let got: [u8; CONTENT_HASH_LEN] = Sha256::digest(payload).into();
+		if expected != got {
+			let mut expected_hash = [0; CONTENT_HASH_LEN];
+			expected_hash.copy_from_slice(expected);
+			return Err(VerificationError::ContentHashMismatch { expected: expected_hash, got });
+		}
+		return Ok((version, payload));
+	}
+
+	if SUPPORTED_DA_PAYLOAD_VERSIONS.contains(&version) {
+		warn!(version, "DA payload carries no content hash, skipping the integrity check");
+		return Ok((version, &blob[VERSION_HEADER_LEN..]));
+	}
+
+	if accept_legacy {
+		warn!("Legacy DA payload carries no content hash, skipping the integrity check");
+		return Ok((LEGACY_DA_PAYLOAD_VERSION, blob));
+	}
+
+	Err(VerificationError::UnsupportedVersion {
+		got: version,
+		supported: SUPPORTED_DA_PAYLOAD_VERSIONS.to_vec(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_round_trip() -> Result<(), anyhow::Error> {
+		let payload = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+		let blob = encode_payload(&payload);
+		assert_eq!(&blob[..VERSION_HEADER_LEN], &CURRENT_DA_PAYLOAD_VERSION.to_le_bytes());
+
+		let (version, decoded) = decode_payload(&blob, false)?;
+		assert_eq!(version, CURRENT_DA_PAYLOAD_VERSION);
+		assert_eq!(decoded, payload.as_slice());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_corrupted_payload_is_rejected() {
+		let mut blob = encode_payload(&[0, 1, 2, 3]);
+		*blob.last_mut().unwrap() ^= 1;
+
+		let got: [u8; CONTENT_HASH_LEN] = Sha256::digest([0, 1, 2, 2]).into();
+		let expected: [u8; CONTENT_HASH_LEN] = Sha256::digest([0, 1, 2, 3]).into();
+		assert_eq!(
+			decode_payload(&blob, false),
+			Err(VerificationError::ContentHashMismatch { expected, got })
+		);
+		assert_eq!(
+			decode_payload(&blob[..VERSION_HEADER_LEN + CONTENT_HASH_LEN - 1], false),
+			Err(VerificationError::MissingContentHash)
+		);
+	}
+
+	#[test]
+	fn test_unhashed_payload_is_accepted() -> Result<(), anyhow::Error> {
+		let mut blob = UNHASHED_DA_PAYLOAD_VERSION.to_le_bytes().to_vec();
+		blob.extend_from_slice(&[1, 2, 3]);
+
+		let (version, decoded) = decode_payload(&blob, false)?;
+		assert_eq!(version, UNHASHED_DA_PAYLOAD_VERSION);
+		assert_eq!(decoded, &[1, 2, 3]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_unknown_version_is_rejected() {
+		let mut blob = 42u32.to_le_bytes().to_vec();
+		blob.extend_from_slice(&[1, 2, 3]);
+
+		assert_eq!(
+			decode_payload(&blob, false),
+			Err(VerificationError::UnsupportedVersion {
+				got: 42,
+				supported: SUPPORTED_DA_PAYLOAD_VERSIONS.to_vec()
+			})
+		);
+	}
+
+	#[test]
+	fn test_legacy_payload_is_accepted_in_compatibility_mode() -> Result<(), anyhow::Error> {
+		// zstd frames start with a magic number which is not a supported version
+		let blob = vec![0x28, 0xb5, 0x2f, 0xfd, 1, 2, 3];
+
+		assert!(decode_payload(&blob, false).is_err());
+		let (version, decoded) = decode_payload(&blob, true)?;
+		assert_eq!(version, LEGACY_DA_PAYLOAD_VERSION);
+		assert_eq!(decoded, blob.as_slice());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,60 @@
+//! Verification of Merkle proofs without a live verifier.
+//!
+//! Keys and values of the sparse Merkle tree are identified by their SHA3-256 hashes here.
+//! Aptos state proofs hash state keys and values with their own hashers and should be checked
+//! with the executor instead.
+
+use aptos_crypto::HashValue;
+use aptos_types::proof::{SparseMerkleProof, TransactionAccumulatorProof};
+
+/// Verifies that `key` maps to `value` in the sparse Merkle tree with the given root,
+/// or that it is absent if `value` is `None`.
+///
+/// ```
+/// use aptos_crypto::{hash::CryptoHash, HashValue};
+/// use aptos_types::proof::{SparseMerkleLeafNode, SparseMerkleProof};
+/// use m1_da_light_node_verifier::proofs::verify_sparse_merkle_inclusion;
+///
+/// let leaf =
+/// 	SparseMerkleLeafNode::new(HashValue::sha3_256_of(b"key"), HashValue::sha3_256_of(b"value"));
+/// let root = leaf.hash();
+/// let proof = SparseMerkleProof::new(Some(leaf), vec![]);
+///
+/// assert!(verify_sparse_merkle_inclusion(root, b"key", Some(b"value"), &proof));
+/// assert!(!verify_sparse_merkle_inclusion(root, b"key", Some(b"other value"), &proof));
+/// assert!(!verify_sparse_merkle_inclusion(root, b"key", None, &proof));
+/// ```
+pub fn verify_sparse_merkle_inclusion(
+	root: HashValue,
+	key: &[u8],
+	value: Option<&[u8]>,
+	proof: &SparseMerkleProof,
+) -> bool {
+	proof
+		.verify_by_hash(root, HashValue::sha3_256_of(key), value.map(HashValue::sha3_256_of))
+		.is_ok()
+}
+
+/// Verifies that the element with the given hash is at `element_index` in the transaction
+/// accumulator with the given root.
+///
+/// ```
+/// use aptos_crypto::HashValue;
+/// use aptos_types::proof::TransactionAccumulatorProof;
+/// use m1_da_light_node_verifier::proofs::verify_accumulator_inclusion;
+///
+/// // the root of an accumulator with a single element is that element's hash
+/// let element_hash = HashValue::sha3_256_of(b"transaction info");
+/// let proof = TransactionAccumulatorProof::new(vec![]);
+///
+/// assert!(verify_accumulator_inclusion(element_hash, element_hash, 0, &proof));
+/// assert!(!verify_accumulator_inclusion(HashValue::zero(), element_hash, 0, &proof));
+/// ```
+pub fn verify_accumulator_inclusion(
+	root: HashValue,
+	element_hash: HashValue,
+	element_index: u64,
+	proof: &TransactionAccumulatorProof,
+) -> bool {
+	proof.verify(root, element_hash, element_index).is_ok()
+}
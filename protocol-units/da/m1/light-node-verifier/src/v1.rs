@@ -1,24 +1,45 @@
-use crate::Verifier;
+use crate::{
+	auth::{self, HmacKey},
+	payload, Verifier,
+};
 use celestia_rpc::{BlobClient, Client, HeaderClient};
 use celestia_types::{nmt::Namespace, Blob};
 use m1_da_light_node_grpc::VerificationMode;
 use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct V1Verifier {
 	pub client: Arc<Client>,
 	pub namespace: Namespace,
+	/// Whether to accept unversioned payloads submitted before payload versioning.
+	pub accept_legacy_payloads: bool,
+	/// The key blobs are authenticated with, all blobs are accepted in cowboy mode if unset.
+	pub hmac_key: Option<HmacKey>,
 }
 
 #[tonic::async_trait]
 impl Verifier for V1Verifier {
-	/// All verification is the same for now
+	/// All verification is the same for now.
+	/// The cowboy authenticity check applies in every mode, as the other modes build on it.
+	/// Blobs which cannot be decoded are rejected, errors are only returned when inclusion
+	/// cannot be checked.
 	async fn verify(
 		&self,
-		_verification_mode: VerificationMode,
+		verification_mode: VerificationMode,
 		blob: &[u8],
 		height: u64,
 	) -> Result<bool, anyhow::Error> {
+		if !self.verify_cowboy(verification_mode, blob, height).await? {
+			return Ok(false);
+		}
+
+		// reject payloads which cannot be decoded before checking inclusion
+		if let Err(e) = self.decode_blob(blob) {
+			warn!("Rejecting undecodable blob: {}", e);
+			return Ok(false);
+		}
+
 		let celestia_blob = Blob::new(self.namespace.clone(), blob.to_vec())?;
 
 		celestia_blob.validate()?;
@@ -49,13 +70,21 @@ impl Verifier for V1Verifier {
 		Ok(true)
 	}
 
+	/// Strips the MAC and the version header.
+	fn decode_blob<'a>(&self, blob: &'a [u8]) -> Result<&'a [u8], anyhow::Error> {
+		let payload = auth::decode_authenticated(self.hmac_key.as_ref(), blob)?;
+		let (_, data) = payload::decode_payload(payload, self.accept_legacy_payloads)?;
+		Ok(data)
+	}
+
+	/// Accepts blobs carrying a valid MAC, or all blobs if no key is set.
 	async fn verify_cowboy(
 		&self,
 		_verification_mode: VerificationMode,
-		_blob: &[u8],
+		blob: &[u8],
 		_height: u64,
 	) -> Result<bool, anyhow::Error> {
-		unimplemented!()
+		Ok(auth::decode_authenticated(self.hmac_key.as_ref(), blob).is_ok())
 	}
 
 	async fn verify_m_of_n(
@@ -89,7 +118,7 @@ mod tests {
 		let config = dot_movement
 			.try_get_config_from_json::<m1_da_light_node_util::M1DaLightNodeConfig>()?;
 
-		let data = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+		let data = payload::encode_payload(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 		let blob = Blob::new(celestia_namespace.clone(), data.clone())?;
 
 		let height = client.blob_submit(&[blob], GasPrice::default()).await?;
@@ -109,9 +138,14 @@ mod tests {
 		let client = Arc::new(config.connect_celestia().await?);
 		let celestia_namespace = config.celestia_namespace();
 
-		let verifier = V1Verifier { client: client.clone(), namespace: celestia_namespace.clone() };
+		let verifier = V1Verifier {
+			client: client.clone(),
+			namespace: celestia_namespace.clone(),
+			accept_legacy_payloads: false,
+			hmac_key: None,
+		};
 
-		let data = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+		let data = payload::encode_payload(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 		let blob = Blob::new(celestia_namespace.clone(), data.clone())?;
 
 		let height = client.blob_submit(&[blob], GasPrice::default()).await?;
@@ -120,7 +154,7 @@ mod tests {
 
 		assert!(included);
 
-		let absent_data = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 7];
+		let absent_data = payload::encode_payload(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 7]);
 
 		let absent_included = verifier.verify(VerificationMode::Cowboy, &absent_data, height).await;
 
@@ -142,9 +176,14 @@ mod tests {
 		let client = Arc::new(config.connect_celestia().await?);
 		let celestia_namespace = config.celestia_namespace();
 
-		let verifier = V1Verifier { client: client.clone(), namespace: celestia_namespace.clone() };
+		let verifier = V1Verifier {
+			client: client.clone(),
+			namespace: celestia_namespace.clone(),
+			accept_legacy_payloads: false,
+			hmac_key: None,
+		};
 
-		let data = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+		let data = payload::encode_payload(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 		let blob = Blob::new(celestia_namespace.clone(), data.clone())?;
 
 		let height = client.blob_submit(&[blob], GasPrice::default()).await?;
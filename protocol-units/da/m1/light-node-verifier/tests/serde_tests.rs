@@ -0,0 +1,68 @@
+//! Round-trip tests for the encodings of the types the verifier exchanges.
+//!
+//! `VerificationMode` and `Blob` are generated by prost, so they round-trip through the protobuf
+//! wire format and the enum's string names rather than serde.
+
+use m1_da_light_node_verifier::{
+	payload::{decode_payload, encode_payload, CURRENT_DA_PAYLOAD_VERSION},
+	Blob, VerificationMode,
+};
+use proptest::prelude::*;
+use prost::Message;
+
+/// The largest blob accepted by Celestia.
+const MAX_BLOB_LEN: usize = 1_973_786;
+
+const ALL_VERIFICATION_MODES: [VerificationMode; 3] =
+	[VerificationMode::Cowboy, VerificationMode::ValidatorIn, VerificationMode::MOfN];
+
+fn verification_mode() -> impl Strategy<Value = VerificationMode> {
+	prop::sample::select(ALL_VERIFICATION_MODES.to_vec())
+}
+
+fn blob() -> impl Strategy<Value = Blob> {
+	(any::<String>(), prop::collection::vec(any::<u8>(), 0..4096), any::<u64>(), any::<u64>())
+		.prop_map(|(blob_id, data, height, timestamp)| Blob { blob_id, data, height, timestamp })
+}
+
+proptest! {
+	#[test]
+	fn verification_mode_round_trips(mode in verification_mode()) {
+		prop_assert_eq!(VerificationMode::try_from(mode as i32).ok(), Some(mode));
+		prop_assert_eq!(VerificationMode::from_str_name(mode.as_str_name()), Some(mode));
+	}
+
+	#[test]
+	fn blob_round_trips(blob in blob()) {
+		prop_assert_eq!(Blob::decode(blob.encode_to_vec().as_slice())?, blob);
+	}
+
+	#[test]
+	fn payload_round_trips(data in prop::collection::vec(any::<u8>(), 0..4096)) {
+		let (version, decoded) = decode_payload(&encode_payload(&data), false)?;
+		prop_assert_eq!(version, CURRENT_DA_PAYLOAD_VERSION);
+		prop_assert_eq!(decoded, data.as_slice());
+	}
+}
+
+#[test]
+fn test_all_verification_modes_round_trip() {
+	for mode in ALL_VERIFICATION_MODES {
+		assert_eq!(VerificationMode::try_from(mode as i32).ok(), Some(mode));
+		assert_eq!(VerificationMode::from_str_name(mode.as_str_name()), Some(mode));
+	}
+	assert_eq!(VerificationMode::from_str_name("UNKNOWN"), None);
+}
+
+#[test]
+fn test_edge_case_blobs_round_trip() -> Result<(), anyhow::Error> {
+	for data in [vec![], vec![0xff; MAX_BLOB_LEN]] {
+		let blob =
+			Blob { blob_id: String::new(), data: data.clone(), height: u64::MAX, timestamp: 0 };
+		assert_eq!(Blob::decode(blob.encode_to_vec().as_slice())?, blob);
+
+		let (_, decoded) = decode_payload(&encode_payload(&data), false)?;
+		assert_eq!(decoded, data.as_slice());
+	}
+	Ok(())
+}
@@ -0,0 +1,248 @@
+use lru::LruCache;
+use m1_da_light_node_grpc::VerificationMode;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The name of the file the cache is persisted to within its data directory.
+pub const DA_BLOB_CACHE_FILE: &str = "da_cache.bin";
+
+/// The verified blob payloads found at each cached DA height.
+pub type CachedBlobs = Arc<Vec<Vec<u8>>>;
+
+/// The persisted form of the cache.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Snapshot {
+	// The verification mode the cached blobs were verified with.
+	verification_mode: i32,
+	// The verified watermark of the cache.
+	checkpoint: Option<u64>,
+	// The cached heights, from the least to the most recently used.
+	entries: Vec<(u64, Vec<Vec<u8>>)>,
+}
+
+#[derive(Debug)]
+struct Inner {
+	// None if the capacity is zero, in which case nothing is cached.
+	blobs: Option<LruCache<u64, CachedBlobs>>,
+	verification_mode: VerificationMode,
+	// The verified watermark: every height from the first cached one up to it was completely
+	// verified.
+	checkpoint: Option<u64>,
+	// The heights completely verified past the watermark, which is advanced once the gap
+	// below them is verified.
+	verified_past_checkpoint: BTreeSet<u64>,
+}
+
+impl Inner {
+	fn new(capacity: usize, verification_mode: VerificationMode) -> Self {
+		Self {
+			blobs: NonZeroUsize::new(capacity).map(LruCache::new),
+			verification_mode,
+			checkpoint: None,
+			verified_past_checkpoint: BTreeSet::new(),
+		}
+	}
+
+	fn advance_checkpoint(&mut self, height: u64) {
+		match self.checkpoint {
+			None => self.checkpoint = Some(height),
+			Some(checkpoint) if height <= checkpoint => return,
+			Some(checkpoint) if height > checkpoint + 1 => {
+				self.verified_past_checkpoint.insert(height);
+				return;
+			}
+			Some(_) => self.checkpoint = Some(height),
+		}
+		while let Some(checkpoint) = self.checkpoint {
+			if !self.verified_past_checkpoint.remove(&(checkpoint + 1)) {
+				break;
+			}
+			self.checkpoint = Some(checkpoint + 1);
+		}
+	}
+}
+
+/// Caches the blobs verified at each DA height, so that restarts do not re-fetch and
+/// re-verify them.
+///
+/// The cache is kept in memory and, if a data directory is configured, persisted on shutdown.
+/// Only the heights up to the verified watermark are reloaded, and only if they were verified
+/// with the same verification mode.
+#[derive(Debug, Clone)]
+pub struct DaBlobCache {
+	inner: Arc<Mutex<Inner>>,
+	path: Option<PathBuf>,
+}
+
+impl DaBlobCache {
+	/// Creates a cache, loading the blobs persisted in the data directory if there are any.
+	pub fn try_new(
+		capacity: usize,
+		data_dir: Option<&Path>,
+		verification_mode: VerificationMode,
+	) -> Result<Self, anyhow::Error> {
+		let path = data_dir.map(|data_dir| data_dir.join(DA_BLOB_CACHE_FILE));
+		let mut inner = Inner::new(capacity, verification_mode);
+
+		if let Some(path) = path.as_ref().filter(|path| path.exists()) {
+			let snapshot: Snapshot = bcs::from_bytes(&std::fs::read(path)?)?;
+			let mut loaded = 0;
+			// blobs verified with another mode must be verified again
+			if snapshot.verification_mode == verification_mode as i32 {
+				inner.checkpoint = snapshot.checkpoint;
+				for (height, blobs) in snapshot.entries {
+					// heights past the checkpoint were not completely verified
+					if snapshot.checkpoint.is_some_and(|checkpoint| height <= checkpoint) {
+						if let Some(cache) = inner.blobs.as_mut() {
+							cache.put(height, Arc::new(blobs));
+							loaded += 1;
+						}
+					}
+				}
+			}
+			info!(checkpoint = snapshot.checkpoint, loaded, "loaded_da_blob_cache");
+		}
+
+		Ok(Self { inner: Arc::new(Mutex::new(inner)), path })
+	}
+
+	fn lock(&self) -> Result<std::sync::MutexGuard<'_, Inner>, anyhow::Error> {
+		self.inner.lock().map_err(|_| anyhow::anyhow!("DA blob cache poisoned"))
+	}
+
+	pub fn get(&self, height: u64) -> Result<Option<CachedBlobs>, anyhow::Error> {
+		let mut inner = self.lock()?;
+		Ok(inner.blobs.as_mut().and_then(|cache| cache.get(&height).cloned()))
+	}
+
+	/// Caches the blobs at a height whose blobs were all fetched and verified.
+	pub fn insert(&self, height: u64, blobs: Vec<Vec<u8>>) -> Result<(), anyhow::Error> {
+		let mut inner = self.lock()?;
+		if let Some(cache) = inner.blobs.as_mut() {
+			cache.put(height, Arc::new(blobs));
+		}
+		inner.advance_checkpoint(height);
+		Ok(())
+	}
+
+	/// Sets the verification mode of the blobs cached from now on.
+	/// The blobs verified with another mode are dropped.
+	pub fn set_verification_mode(
+		&self,
+		verification_mode: VerificationMode,
+	) -> Result<(), anyhow::Error> {
+		let mut inner = self.lock()?;
+		if inner.verification_mode != verification_mode {
+			let capacity = inner.blobs.as_ref().map_or(0, |cache| cache.cap().get());
+			*inner = Inner::new(capacity, verification_mode);
+		}
+		Ok(())
+	}
+
+	/// Writes the cache to its data directory, if it has one.
+	pub fn persist(&self) -> Result<(), anyhow::Error> {
+		let Some(path) = &self.path else {
+			return Ok(());
+		};
+
+		let snapshot = {
+			let inner = self.lock()?;
+			let entries = inner
+				.blobs
+				.iter()
+				.flat_map(|cache| cache.iter().rev())
+				.map(|(height, blobs)| (*height, blobs.as_ref().clone()))
+				.collect();
+			Snapshot {
+				verification_mode: inner.verification_mode as i32,
+				checkpoint: inner.checkpoint,
+				entries,
+			}
+		};
+
+		// write to a temporary file first so that a crash does not leave a truncated cache
+		let temp_path = path.with_extension("bin.tmp");
+		std::fs::write(&temp_path, bcs::to_bytes(&snapshot)?)?;
+		std::fs::rename(&temp_path, path)?;
+		info!(
+			checkpoint = snapshot.checkpoint,
+			entries = snapshot.entries.len(),
+			"persisted_da_blob_cache"
+		);
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_persists_across_restarts() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		{
+			let cache = DaBlobCache::try_new(2, Some(dir.path()), VerificationMode::MOfN)?;
+			cache.insert(1, vec![vec![1]])?;
+			cache.insert(2, vec![vec![2], vec![2, 2]])?;
+			cache.insert(3, vec![])?;
+			cache.persist()?;
+		}
+
+		let cache = DaBlobCache::try_new(2, Some(dir.path()), VerificationMode::MOfN)?;
+		assert_eq!(cache.get(1)?, None);
+		assert_eq!(cache.get(2)?, Some(Arc::new(vec![vec![2], vec![2, 2]])));
+		assert_eq!(cache.get(3)?, Some(Arc::new(vec![])));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_drops_heights_past_the_verified_watermark() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		{
+			let cache = DaBlobCache::try_new(8, Some(dir.path()), VerificationMode::MOfN)?;
+			cache.insert(1, vec![vec![1]])?;
+			// height 2 was not completely verified
+			cache.insert(3, vec![vec![3]])?;
+			assert_eq!(cache.get(3)?, Some(Arc::new(vec![vec![3]])));
+			cache.persist()?;
+		}
+
+		let cache = DaBlobCache::try_new(8, Some(dir.path()), VerificationMode::MOfN)?;
+		assert_eq!(cache.get(1)?, Some(Arc::new(vec![vec![1]])));
+		assert_eq!(cache.get(3)?, None);
+
+		// the watermark moves past heights verified out of order once the gap is verified
+		cache.insert(3, vec![vec![3]])?;
+		cache.insert(2, vec![vec![2]])?;
+		assert_eq!(cache.lock()?.checkpoint, Some(3));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_drops_blobs_verified_with_another_mode() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		{
+			let cache = DaBlobCache::try_new(8, Some(dir.path()), VerificationMode::MOfN)?;
+			cache.insert(1, vec![vec![1]])?;
+			cache.set_verification_mode(VerificationMode::Cowboy)?;
+			assert_eq!(cache.get(1)?, None);
+			cache.insert(2, vec![vec![2]])?;
+			cache.persist()?;
+		}
+
+		let cache = DaBlobCache::try_new(8, Some(dir.path()), VerificationMode::MOfN)?;
+		assert_eq!(cache.get(2)?, None);
+		let cache = DaBlobCache::try_new(8, Some(dir.path()), VerificationMode::Cowboy)?;
+		assert_eq!(cache.get(2)?, Some(Arc::new(vec![vec![2]])));
+
+		Ok(())
+	}
+}
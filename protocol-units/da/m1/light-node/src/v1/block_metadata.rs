@@ -0,0 +1,157 @@
+use anyhow::Context;
+use std::collections::HashSet;
+
+// Layout of a Celestia share, as specified by celestia-app.
+const NAMESPACE_SIZE: usize = 29;
+const INFO_BYTE_SIZE: usize = 1;
+const SEQUENCE_LEN_SIZE: usize = 4;
+const SEQUENCE_START_FLAG: u8 = 1;
+
+/// The size and utilization of a Celestia block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaBlockMetadata {
+	pub height: u64,
+	/// The root of the data availability header, committing to all the shares of the block.
+	pub data_root: [u8; 32],
+	/// The width of the original data square, in shares.
+	pub square_size: u64,
+	/// The number of distinct namespaces with blobs in the block.
+	pub namespace_count: u64,
+	/// The total size of the blobs in the block, without the share overhead.
+	pub total_blob_bytes: u64,
+}
+
+impl DaBlockMetadata {
+	/// Summarizes a block from its extended data square, in row-major order.
+	/// Only the original data square, the top left quadrant, is read.
+	pub fn try_from_extended_square(
+		height: u64,
+		data_root: [u8; 32],
+		extended_square: &[Vec<u8>],
+	) -> Result<Self, anyhow::Error> {
+		let extended_width = (extended_square.len() as f64).sqrt() as usize;
+		if extended_width * extended_width != extended_square.len() || extended_width % 2 != 0 {
+			anyhow::bail!("{} shares do not form an extended data square", extended_square.len());
+		}
+		let square_size = extended_width / 2;
+
+		let mut namespaces = HashSet::new();
+		let mut total_blob_bytes = 0;
+		for row in 0..square_size {
+			let original_row = &extended_square[row * extended_width..][..square_size];
+			for share in original_row {
+				let namespace = share
+					.get(..NAMESPACE_SIZE)
+					.with_context(|| format!("Share of {} bytes has no namespace", share.len()))?;
+				if !is_blob_namespace(namespace) {
+					continue;
+				}
+				namespaces.insert(namespace);
+
+				let info_byte = share
+					.get(NAMESPACE_SIZE)
+					.with_context(|| format!("Share of {} bytes has no info byte", share.len()))?;
+				// the first share of each blob carries the length of the whole blob
+				if info_byte & SEQUENCE_START_FLAG != 0 {
+					let start = NAMESPACE_SIZE + INFO_BYTE_SIZE;
+					let sequence_len: [u8; SEQUENCE_LEN_SIZE] = share
+						.get(start..start + SEQUENCE_LEN_SIZE)
+						.and_then(|bytes| bytes.try_into().ok())
+						.context("Sequence start share has no sequence length")?;
+					total_blob_bytes += u32::from_be_bytes(sequence_len) as u64;
+				}
+			}
+		}
+
+		Ok(Self {
+			height,
+			data_root,
+			square_size: square_size as u64,
+			namespace_count: namespaces.len() as u64,
+			total_blob_bytes,
+		})
+	}
+}
+
+/// Whether a namespace holds blobs, rather than transactions or padding.
+/// The primary reserved namespaces are version 0 with an id of zeroes but for the last byte,
+/// the secondary reserved namespaces are version 255.
+fn is_blob_namespace(namespace: &[u8]) -> bool {
+	let (version, id) = (namespace[0], &namespace[1..NAMESPACE_SIZE - 1]);
+	version == 0 && id.iter().any(|byte| *byte != 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SHARE_SIZE: usize = 512;
+
+	fn share(namespace_id: u8, sequence_len: Option<u32>) -> Vec<u8> {
+		let mut share = vec![0; SHARE_SIZE];
+		// a user namespace id starts with 18 zero bytes
+		share[NAMESPACE_SIZE - 1] = namespace_id;
+		share[NAMESPACE_SIZE - 2] = 1;
+		if let Some(sequence_len) = sequence_len {
+			share[NAMESPACE_SIZE] = SEQUENCE_START_FLAG;
+			share[NAMESPACE_SIZE + 1..NAMESPACE_SIZE + 5]
+				.copy_from_slice(&sequence_len.to_be_bytes());
+		}
+		share
+	}
+
+	fn reserved_share(version: u8, last_byte: u8) -> Vec<u8> {
+		let mut share = vec![0; SHARE_SIZE];
+		share[0] = version;
+		share[NAMESPACE_SIZE - 1] = last_byte;
+		share[NAMESPACE_SIZE] = SEQUENCE_START_FLAG;
+		share
+	}
+
+	#[test]
+	fn test_summarize_extended_square() -> Result<(), anyhow::Error> {
+		// a 2x2 original square: a transaction, a blob spanning two shares, and another blob
+		let parity = reserved_share(255, 0);
+		let extended_square = [
+			reserved_share(0, 1),
+			share(7, Some(600)),
+			parity.clone(),
+			parity.clone(),
+			share(7, None),
+			share(8, Some(10)),
+			parity.clone(),
+			parity.clone(),
+			// the parity rows are not read
+			share(9, Some(1_000)),
+			parity.clone(),
+			parity.clone(),
+			parity.clone(),
+			parity.clone(),
+			parity.clone(),
+			parity.clone(),
+			parity,
+		];
+
+		let metadata = DaBlockMetadata::try_from_extended_square(5, [3; 32], &extended_square)?;
+		assert_eq!(
+			metadata,
+			DaBlockMetadata {
+				height: 5,
+				data_root: [3; 32],
+				square_size: 2,
+				namespace_count: 2,
+				total_blob_bytes: 610,
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rejects_malformed_squares() {
+		let not_square = [share(7, None), share(7, None), share(7, None)];
+		assert!(DaBlockMetadata::try_from_extended_square(1, [0; 32], &not_square).is_err());
+		let truncated_shares = [vec![0; 4], vec![0; 4], vec![0; 4], vec![0; 4]];
+		assert!(DaBlockMetadata::try_from_extended_square(1, [0; 32], &truncated_shares).is_err());
+	}
+}
@@ -0,0 +1,178 @@
+use lru::LruCache;
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, DB};
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+mod column_families {
+	pub const SUBMITTED_BLOBS: &str = "submitted_blobs";
+}
+use column_families::*;
+
+/// The blake3 hash identifying a submitted blob.
+pub type BlobHash = [u8; 32];
+
+/// The result of a previous submission of the same blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExistingCommitment {
+	/// The Celestia height at which the blob was included.
+	pub height: u64,
+}
+
+/// Remembers the blobs submitted to the DA so that retried submissions
+/// do not include the same blob twice.
+///
+/// The set is bounded to `max_cache_entries` and persisted to RocksDB, so it survives restarts.
+/// Recency is not persisted, entries reloaded on startup are evicted in arbitrary order.
+#[derive(Debug, Clone)]
+pub struct BlobDeduplicator {
+	db: Arc<DB>,
+	// The height at which each remembered blob was included.
+	cache: Arc<Mutex<LruCache<BlobHash, u64>>>,
+}
+
+impl BlobDeduplicator {
+	pub fn open(path: impl AsRef<Path>, max_cache_entries: usize) -> Result<Self, anyhow::Error> {
+		let max_cache_entries = NonZeroUsize::new(max_cache_entries)
+			.ok_or(anyhow::anyhow!("Blob deduplication requires a non-zero cache size"))?;
+
+		let mut options = Options::default();
+		options.create_if_missing(true);
+		options.create_missing_column_families(true);
+
+		let submitted_blobs = ColumnFamilyDescriptor::new(SUBMITTED_BLOBS, Options::default());
+		let db = DB::open_cf_descriptors(&options, path, vec![submitted_blobs])
+			.map_err(|e| anyhow::anyhow!("Failed to open blob deduplication DB: {:?}", e))?;
+
+		let mut cache = LruCache::new(max_cache_entries);
+		{
+			let cf = db
+				.cf_handle(SUBMITTED_BLOBS)
+				.ok_or(anyhow::anyhow!("No submitted_blobs column family"))?;
+			for entry in db.iterator_cf(&cf, IteratorMode::Start) {
+				let (key, value) =
+					entry.map_err(|e| anyhow::anyhow!("Failed to read submitted blob: {:?}", e))?;
+				let hash: BlobHash = key
+					.as_ref()
+					.try_into()
+					.map_err(|_| anyhow::anyhow!("Invalid submitted blob hash length"))?;
+				let height = u64::from_be_bytes(
+					value
+						.as_ref()
+						.try_into()
+						.map_err(|_| anyhow::anyhow!("Invalid submitted blob height"))?,
+				);
+				// the stored hashes are unique, so an entry is only returned when evicted
+				if let Some((evicted, _)) = cache.push(hash, height) {
+					db.delete_cf(&cf, evicted)
+						.map_err(|e| anyhow::anyhow!("Failed to evict submitted blob: {:?}", e))?;
+				}
+			}
+		}
+
+		Ok(Self { db: Arc::new(db), cache: Arc::new(Mutex::new(cache)) })
+	}
+
+	pub fn hash(blob: &[u8]) -> BlobHash {
+		*blake3::hash(blob).as_bytes()
+	}
+
+	/// Returns the previous commitment if the blob has already been submitted.
+	pub fn check(&self, blob: &[u8]) -> Result<Option<ExistingCommitment>, anyhow::Error> {
+		let mut cache = self
+			.cache
+			.lock()
+			.map_err(|_| anyhow::anyhow!("Blob deduplication cache poisoned"))?;
+		Ok(cache.get(&Self::hash(blob)).map(|&height| ExistingCommitment { height }))
+	}
+
+	/// Records the blob as submitted at the given height.
+	pub fn record(&self, blob: &[u8], height: u64) -> Result<(), anyhow::Error> {
+		let hash = Self::hash(blob);
+		let cf = self
+			.db
+			.cf_handle(SUBMITTED_BLOBS)
+			.ok_or(anyhow::anyhow!("No submitted_blobs column family"))?;
+		let mut cache = self
+			.cache
+			.lock()
+			.map_err(|_| anyhow::anyhow!("Blob deduplication cache poisoned"))?;
+		self.db
+			.put_cf(&cf, hash, height.to_be_bytes())
+			.map_err(|e| anyhow::anyhow!("Failed to record submitted blob: {:?}", e))?;
+		// a re-recorded blob replaces its own entry, which must not be deleted
+		if let Some((evicted, _)) = cache.push(hash, height).filter(|(key, _)| *key != hash) {
+			self.db
+				.delete_cf(&cf, evicted)
+				.map_err(|e| anyhow::anyhow!("Failed to evict submitted blob: {:?}", e))?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_detects_duplicates() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let dedup = BlobDeduplicator::open(dir.path(), 16)?;
+
+		assert_eq!(dedup.check(b"blob")?, None);
+		dedup.record(b"blob", 7)?;
+		assert_eq!(dedup.check(b"blob")?, Some(ExistingCommitment { height: 7 }));
+		assert_eq!(dedup.check(b"other blob")?, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_evicts_least_recently_used() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let dedup = BlobDeduplicator::open(dir.path(), 2)?;
+
+		dedup.record(b"a", 1)?;
+		dedup.record(b"b", 2)?;
+		// touch a so that b becomes the least recently used
+		assert!(dedup.check(b"a")?.is_some());
+		dedup.record(b"c", 3)?;
+
+		assert!(dedup.check(b"a")?.is_some());
+		assert_eq!(dedup.check(b"b")?, None);
+		assert!(dedup.check(b"c")?.is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rerecording_keeps_the_blob() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		{
+			let dedup = BlobDeduplicator::open(dir.path(), 2)?;
+			dedup.record(b"blob", 7)?;
+			dedup.record(b"blob", 8)?;
+			assert_eq!(dedup.check(b"blob")?, Some(ExistingCommitment { height: 8 }));
+		}
+
+		let dedup = BlobDeduplicator::open(dir.path(), 2)?;
+		assert_eq!(dedup.check(b"blob")?, Some(ExistingCommitment { height: 8 }));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_survives_restart() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		{
+			let dedup = BlobDeduplicator::open(dir.path(), 16)?;
+			dedup.record(b"blob", 7)?;
+		}
+
+		let dedup = BlobDeduplicator::open(dir.path(), 16)?;
+		assert_eq!(dedup.check(b"blob")?, Some(ExistingCommitment { height: 7 }));
+
+		Ok(())
+	}
+}
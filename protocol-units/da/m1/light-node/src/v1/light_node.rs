@@ -1,48 +1,89 @@
 use m1_da_light_node_grpc::light_node_service_server::{LightNodeService, LightNodeServiceServer};
+use m1_da_light_node_grpc::LIGHT_NODE_SERVICE_NAME;
 use m1_da_light_node_util::config::Config;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::watch;
 use tonic::transport::Server;
+use tonic_health::ServingStatus;
 use tracing::info;
 
+/// Waits for SIGINT or SIGTERM.
+pub async fn shutdown_signal() -> Result<(), anyhow::Error> {
+	let mut sigterm = signal(SignalKind::terminate())?;
+	tokio::select! {
+		result = tokio::signal::ctrl_c() => result?,
+		_ = sigterm.recv() => (),
+	}
+	Ok(())
+}
+
+/// Waits until the shutdown of the light node is signalled on the channel.
+pub async fn wait_for_shutdown(shutdown: &mut watch::Receiver<bool>) {
+	// a dropped sender means the light node is going away as well
+	let _ = shutdown.wait_for(|stopped| *stopped).await;
+}
+
 pub trait LightNodeV1Operations: LightNodeService + Send + Sync + Sized + Clone {
 	/// Initializes from environment variables.
 	async fn try_from_config(config: Config) -> Result<Self, anyhow::Error>;
 
-	/// Runs the background tasks.
-	async fn run_background_tasks(&self) -> Result<(), anyhow::Error>;
+	/// Runs the background tasks, until the shutdown is signalled.
+	async fn run_background_tasks(
+		&self,
+		shutdown: watch::Receiver<bool>,
+	) -> Result<(), anyhow::Error>;
 
 	/// Tries to get the service address
 	fn try_service_address(&self) -> Result<String, anyhow::Error>;
 
-	/// Runs the server
-	async fn run_server(&self) -> Result<(), anyhow::Error> {
+	/// Runs the server, until the shutdown is signalled.
+	async fn run_server(&self, mut shutdown: watch::Receiver<bool>) -> Result<(), anyhow::Error> {
 		let reflection = tonic_reflection::server::Builder::configure()
 			.register_encoded_file_descriptor_set(m1_da_light_node_grpc::FILE_DESCRIPTOR_SET)
+			.register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
 			.build()?;
 
+		// initialization is complete once the services are built
+		let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+		health_reporter
+			.set_service_status(LIGHT_NODE_SERVICE_NAME, ServingStatus::Serving)
+			.await;
+
 		let address = self.try_service_address()?;
 		info!("Server listening on: {}", address);
 		Server::builder()
 			.accept_http1(true)
+			.add_service(health_service)
 			.add_service(LightNodeServiceServer::new(self.clone()))
 			.add_service(reflection)
-			.serve(address.parse()?)
+			.serve_with_shutdown(address.parse()?, async move {
+				wait_for_shutdown(&mut shutdown).await;
+				info!("Shutting down the server.");
+				health_reporter
+					.set_service_status(LIGHT_NODE_SERVICE_NAME, ServingStatus::NotServing)
+					.await;
+			})
 			.await?;
 
 		Ok(())
 	}
 
-	/// Runs the server and the background tasks.
+	/// Runs the server and the background tasks, until SIGINT or SIGTERM is received.
 	async fn run(self) -> Result<(), anyhow::Error> {
-		let background_handle = self.run_background_tasks();
-
-		let background_tasks = async move {
-			background_handle.await?;
+		let (shutdown_sender, shutdown) = watch::channel(false);
+		let signal_shutdown = async move {
+			shutdown_signal().await?;
+			info!("Received the shutdown signal.");
+			shutdown_sender.send_replace(true);
 			Ok::<_, anyhow::Error>(())
 		};
-		let server = self.run_server();
 
 		info!("Running server and background tasks.");
-		tokio::try_join!(server, background_tasks)?;
+		tokio::try_join!(
+			signal_shutdown,
+			self.run_server(shutdown.clone()),
+			self.run_background_tasks(shutdown)
+		)?;
 
 		Ok(())
 	}
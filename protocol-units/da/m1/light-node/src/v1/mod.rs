@@ -1,4 +1,8 @@
+pub mod blob_cache;
+pub mod block_metadata;
+pub mod dedup;
 pub mod passthrough;
+pub mod pipeline;
 #[cfg(feature = "sequencer")]
 pub mod sequencer;
 
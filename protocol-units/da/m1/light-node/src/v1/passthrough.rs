@@ -1,21 +1,46 @@
 use anyhow::Context;
 use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tokio::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::{watch, RwLock};
 use tokio_stream::{Stream, StreamExt};
-use tracing::debug;
+use tracing::{debug, warn};
 
-use celestia_rpc::{BlobClient, Client, HeaderClient};
+use celestia_rpc::{BlobClient, Client, HeaderClient, ShareClient};
 use celestia_types::{blob::GasPrice, nmt::Namespace, Blob as CelestiaBlob};
 
 // FIXME: glob imports are bad style
 use m1_da_light_node_grpc::light_node_service_server::LightNodeService;
 use m1_da_light_node_grpc::*;
 use m1_da_light_node_util::config::Config;
-use m1_da_light_node_verifier::{v1::V1Verifier, Verifier};
-
-use crate::v1::LightNodeV1Operations;
+use m1_da_light_node_verifier::{
+	auth::{self, HmacKey},
+	payload,
+	v1::V1Verifier,
+	Verifier,
+};
+
+use crate::v1::{
+	blob_cache::DaBlobCache, block_metadata::DaBlockMetadata, dedup::BlobDeduplicator,
+	light_node::wait_for_shutdown, LightNodeV1Operations,
+};
+
+// Interval between polls of Celestia for a submitted blob.
+const SUBMISSION_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+// Time after which a submitted blob which cannot be read back is reported as timed out.
+const SUBMISSION_STATUS_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Errors thrown while following the blobs posted to the DA.
+#[derive(Debug, Error)]
+pub enum DaError {
+	#[error("Failed to get the Celestia network head: {0}")]
+	NetworkHead(String),
+	#[error("Failed to get the blobs at height {height}: {message}")]
+	BlobsAtHeight { height: u64, message: String },
+}
 
 #[derive(Clone)]
 pub struct LightNodeV1 {
@@ -24,6 +49,9 @@ pub struct LightNodeV1 {
 	pub default_client: Arc<Client>,
 	pub verification_mode: Arc<RwLock<VerificationMode>>,
 	pub verifier: Arc<Box<dyn Verifier + Send + Sync>>,
+	pub deduplicator: Option<BlobDeduplicator>,
+	pub blob_cache: DaBlobCache,
+	pub hmac_key: Option<HmacKey>,
 }
 
 impl Debug for LightNodeV1 {
@@ -38,19 +66,34 @@ impl LightNodeV1Operations for LightNodeV1 {
 	/// Tries to create a new LightNodeV1 instance from the toml config file.
 	async fn try_from_config(config: Config) -> Result<Self, anyhow::Error> {
 		let client = Arc::new(config.connect_celestia().await?);
+		let deduplicator = config
+			.m1_da_light_node_dedup_db_path()
+			.map(|path| BlobDeduplicator::open(path, config.max_cache_entries()))
+			.transpose()?;
+		let verification_mode = VerificationMode::from_str_name("M_OF_N")
+			.context("Failed to parse verification mode")?;
+		let blob_cache_dir = config.m1_da_light_node_blob_cache_dir().map(PathBuf::from);
+		let blob_cache = DaBlobCache::try_new(
+			config.blob_cache_capacity(),
+			blob_cache_dir.as_deref(),
+			verification_mode,
+		)?;
+		let hmac_key = config.try_m1_da_light_node_hmac_key()?.map(HmacKey);
 
 		Ok(Self {
 			config: config.clone(),
 			celestia_namespace: config.celestia_namespace(),
 			default_client: client.clone(),
-			verification_mode: Arc::new(RwLock::new(
-				VerificationMode::from_str_name("M_OF_N")
-					.context("Failed to parse verification mode")?,
-			)),
+			verification_mode: Arc::new(RwLock::new(verification_mode)),
 			verifier: Arc::new(Box::new(V1Verifier {
 				client,
 				namespace: config.celestia_namespace(),
+				accept_legacy_payloads: config.m1_da_light_node_accept_legacy_payloads(),
+				hmac_key,
 			})),
+			deduplicator,
+			blob_cache,
+			hmac_key,
 		})
 	}
 
@@ -59,33 +102,79 @@ impl LightNodeV1Operations for LightNodeV1 {
 	}
 
 	/// Runs background tasks for the LightNodeV1 instance.
-	async fn run_background_tasks(&self) -> Result<(), anyhow::Error> {
+	async fn run_background_tasks(
+		&self,
+		mut shutdown: watch::Receiver<bool>,
+	) -> Result<(), anyhow::Error> {
+		// persist the blob cache when the light node shuts down
+		wait_for_shutdown(&mut shutdown).await;
+		self.blob_cache.persist()?;
 		Ok(())
 	}
 }
 
 impl LightNodeV1 {
-	/// Creates a new blob instance with the provided data.
+	/// Creates a new blob instance with the provided data, prefixed with the payload version
+	/// and, if blob authentication is enabled, the MAC.
 	pub fn create_new_celestia_blob(&self, data: Vec<u8>) -> Result<CelestiaBlob, anyhow::Error> {
+		let blob_data =
+			auth::encode_authenticated(self.hmac_key.as_ref(), payload::encode_payload(&data));
+		CelestiaBlob::new(self.celestia_namespace, blob_data)
+			.map_err(|e| anyhow::anyhow!("Failed to create a blob: {}", e))
+	}
+
+	/// Recreates a blob from a payload which already carries its version header.
+	fn create_celestia_blob_from_payload(
+		&self,
+		data: Vec<u8>,
+	) -> Result<CelestiaBlob, anyhow::Error> {
 		CelestiaBlob::new(self.celestia_namespace, data)
 			.map_err(|e| anyhow::anyhow!("Failed to create a blob: {}", e))
 	}
 
 	/// Submits a CelestiaNlob to the Celestia node.
 	pub async fn submit_celestia_blob(&self, blob: CelestiaBlob) -> Result<u64, anyhow::Error> {
-		let height = self
-			.default_client
-			.blob_submit(&[blob], GasPrice::default())
-			.await
-			.map_err(|e| anyhow::anyhow!("Failed submitting the blob: {}", e))?;
-
-		Ok(height)
+		self.submit_celestia_blobs(&[blob]).await
 	}
 
 	/// Submits Celestia blobs to the Celestia node.
+	///
+	/// Blobs which have already been submitted are skipped when deduplication is enabled.
+	/// If all of the blobs are duplicates, the height of the latest previous submission is returned.
 	pub async fn submit_celestia_blobs(
 		&self,
 		blobs: &[CelestiaBlob],
+	) -> Result<u64, anyhow::Error> {
+		let Some(deduplicator) = &self.deduplicator else {
+			return self.submit_celestia_blobs_unchecked(blobs).await;
+		};
+
+		let mut new_blobs = Vec::with_capacity(blobs.len());
+		let mut existing_height = None;
+		for blob in blobs {
+			match deduplicator.check(&blob.data)? {
+				Some(existing) => {
+					debug!(height = existing.height, "skipping duplicate blob submission");
+					existing_height = existing_height.max(Some(existing.height));
+				}
+				None => new_blobs.push(blob.clone()),
+			}
+		}
+		if new_blobs.is_empty() {
+			return existing_height.context("No blobs to submit");
+		}
+
+		let height = self.submit_celestia_blobs_unchecked(&new_blobs).await?;
+		for blob in &new_blobs {
+			deduplicator.record(&blob.data, height)?;
+		}
+
+		Ok(height)
+	}
+
+	async fn submit_celestia_blobs_unchecked(
+		&self,
+		blobs: &[CelestiaBlob],
 	) -> Result<u64, anyhow::Error> {
 		let height = self
 			.default_client
@@ -108,12 +197,22 @@ impl LightNodeV1 {
 		&self,
 		height: u64,
 	) -> Result<Vec<CelestiaBlob>, anyhow::Error> {
+		if let Some(cached_blobs) = self.blob_cache.get(height)? {
+			debug!(height, "Using cached verified blobs");
+			return cached_blobs
+				.iter()
+				.map(|data| self.create_celestia_blob_from_payload(data.clone()))
+				.collect();
+		}
+
 		let blobs = self.default_client.blob_get_all(height, &[self.celestia_namespace]).await;
 
 		if let Err(e) = &blobs {
 			debug!("Error getting blobs: {:?}", e);
 		}
 
+		// only cache heights which were successfully fetched and verified
+		let mut completely_verified = blobs.is_ok();
 		let blobs = blobs.unwrap_or_default();
 
 		let mut verified_blobs = Vec::new();
@@ -130,6 +229,7 @@ impl LightNodeV1 {
 
 			if let Err(e) = &verified {
 				debug!("Error verifying blob: {:?}", e);
+				completely_verified = false;
 			}
 
 			// FIXME: check the implications of treating errors as verification success.
@@ -143,6 +243,11 @@ impl LightNodeV1 {
 			}
 		}
 
+		if completely_verified {
+			self.blob_cache
+				.insert(height, verified_blobs.iter().map(|blob| blob.data.clone()).collect())?;
+		}
+
 		Ok(verified_blobs)
 	}
 
@@ -150,9 +255,23 @@ impl LightNodeV1 {
 	async fn get_blobs_at_height(&self, height: u64) -> Result<Vec<Blob>, anyhow::Error> {
 		let celestia_blobs = self.get_celestia_blobs_at_height(height).await?;
 		let mut blobs = Vec::new();
+		let accept_legacy_payloads = self.config.m1_da_light_node_accept_legacy_payloads();
 		for celestia_blob in celestia_blobs {
-			let blob = Self::celestia_blob_to_blob(celestia_blob, height)?;
-			debug!(blob_id = %blob.blob_id, "got blob");
+			let mut blob = Self::celestia_blob_to_blob(celestia_blob, height)?;
+			// strip the MAC and version header, blobs accepted despite a verifier error may
+			// not be decodable and are skipped, as they must not fail the whole height
+			let decoded = auth::decode_authenticated(self.hmac_key.as_ref(), &blob.data).and_then(
+				|authenticated| payload::decode_payload(authenticated, accept_legacy_payloads),
+			);
+			let (version, data) = match decoded {
+				Ok(decoded) => decoded,
+				Err(e) => {
+					warn!(blob_id = %blob.blob_id, height, "Skipping undecodable blob: {}", e);
+					continue;
+				}
+			};
+			debug!(blob_id = %blob.blob_id, version, "got blob");
+			blob.data = data.to_vec();
 			blobs.push(blob);
 		}
 		Ok(blobs)
@@ -189,6 +308,60 @@ impl LightNodeV1 {
 			as std::pin::Pin<Box<dyn Stream<Item = Result<Blob, anyhow::Error>> + Send>>)
 	}
 
+	/// Follows the blobs posted to the namespace from `start_height` on, yielding the payload
+	/// of each verified blob with its height, in order.
+	/// Celestia's network head is polled at the configured interval, and every height up to it
+	/// is fetched, so no height is skipped even if the node falls behind.
+	pub fn subscribe_blobs(
+		&self,
+		start_height: u64,
+	) -> impl Stream<Item = Result<(u64, Vec<u8>), DaError>> + '_ {
+		let poll_interval =
+			Duration::from_millis(self.config.m1_da_light_node_subscribe_poll_interval_ms());
+		async_stream::try_stream! {
+			let mut height = start_height;
+			loop {
+				let head: u64 = self
+					.default_client
+					.header_network_head()
+					.await
+					.map_err(|e| DaError::NetworkHead(e.to_string()))?
+					.height()
+					.into();
+				while height <= head {
+					let blobs = self.get_blobs_at_height(height).await.map_err(|e| {
+						DaError::BlobsAtHeight { height, message: e.to_string() }
+					})?;
+					for blob in blobs {
+						yield (height, blob.data);
+					}
+					height += 1;
+				}
+				tokio::time::sleep(poll_interval).await;
+			}
+		}
+	}
+
+	/// Gets the size and utilization of the Celestia block at the given height.
+	/// The whole extended data square of the block is fetched to count its blobs.
+	pub async fn get_block_metadata(&self, height: u64) -> Result<DaBlockMetadata, anyhow::Error> {
+		let header =
+			self.default_client.header_get_by_height(height).await.map_err(|e| {
+				anyhow::anyhow!("Failed to get the header at height {}: {}", height, e)
+			})?;
+		let data_root: [u8; 32] = header
+			.header
+			.data_hash
+			.context("Header has no data root")?
+			.as_bytes()
+			.try_into()
+			.context("Data root is not 32 bytes")?;
+		let extended_square = self.default_client.share_get_eds(&header).await.map_err(|e| {
+			anyhow::anyhow!("Failed to get the data square at height {}: {}", height, e)
+		})?;
+		DaBlockMetadata::try_from_extended_square(height, data_root, &extended_square.data_square)
+	}
+
 	/// Streams the latest blobs that can subscribed to.
 	async fn stream_blobs_from_height_on(
 		&self,
@@ -239,6 +412,30 @@ impl LightNodeV1 {
 			as std::pin::Pin<Box<dyn Stream<Item = Result<Blob, anyhow::Error>> + Send>>)
 	}
 
+	/// Polls Celestia until the blob can be read back at the given height.
+	/// Returns `false` if the blob was not found before the timeout.
+	async fn wait_for_blob_at_height(
+		&self,
+		blob: &CelestiaBlob,
+		height: u64,
+	) -> Result<bool, anyhow::Error> {
+		let deadline = tokio::time::Instant::now() + SUBMISSION_STATUS_TIMEOUT;
+		loop {
+			match self.default_client.blob_get_all(height, &[self.celestia_namespace]).await {
+				Ok(blobs) => {
+					if blobs.iter().any(|b| b.commitment == blob.commitment) {
+						return Ok(true);
+					}
+				}
+				Err(e) => debug!(height, "Error polling for submitted blob: {:?}", e),
+			}
+			if tokio::time::Instant::now() + SUBMISSION_STATUS_POLL_INTERVAL > deadline {
+				return Ok(false);
+			}
+			tokio::time::sleep(SUBMISSION_STATUS_POLL_INTERVAL).await;
+		}
+	}
+
 	pub fn celestia_blob_to_blob(blob: CelestiaBlob, height: u64) -> Result<Blob, anyhow::Error> {
 		let timestamp = chrono::Utc::now().timestamp_micros() as u64;
 
@@ -388,6 +585,29 @@ impl LightNodeService for LightNodeV1 {
 			blobs: blob_responses,
 		}))
 	}
+	/// Get the raw blobs stored at a specified height, without verifying or decoding them.
+	async fn get_blob_at_height(
+		&self,
+		request: tonic::Request<GetBlobAtHeightRequest>,
+	) -> std::result::Result<tonic::Response<GetBlobAtHeightResponse>, tonic::Status> {
+		let height = request.into_inner().height;
+		let blobs = self
+			.default_client
+			.blob_get_all(height, &[self.celestia_namespace])
+			.await
+			.map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+		if blobs.is_empty() {
+			return Err(tonic::Status::not_found("No blobs found at the specified height"));
+		}
+
+		Ok(tonic::Response::new(GetBlobAtHeightResponse {
+			height,
+			blobs: blobs.into_iter().map(|blob| blob.data).collect(),
+			namespace: self.celestia_namespace.as_bytes().to_vec(),
+		}))
+	}
+
 	/// Batch read and write operations for efficiency.
 	async fn batch_read(
 		&self,
@@ -444,6 +664,46 @@ impl LightNodeService for LightNodeV1 {
 
 		Ok(tonic::Response::new(BatchWriteResponse { blobs: blob_responses }))
 	}
+	/// Server streaming response type for the SubmitBlobStream method.
+	type SubmitBlobStreamStream = std::pin::Pin<
+		Box<dyn Stream<Item = Result<BlobSubmissionStatus, tonic::Status>> + Send + 'static>,
+	>;
+	/// Submit a blob and stream its status until it is finalized.
+	async fn submit_blob_stream(
+		&self,
+		request: tonic::Request<BlobSubmission>,
+	) -> std::result::Result<tonic::Response<Self::SubmitBlobStreamStream>, tonic::Status> {
+		let data = request.into_inner().data;
+		let me = Arc::new(self.clone());
+
+		let output = async_stream::try_stream! {
+
+			let celestia_blob = me.create_new_celestia_blob(data).map_err(|e| tonic::Status::internal(e.to_string()))?;
+			yield BlobSubmissionStatus {
+				status: Some(blob_submission_status::Status::Pending(blob_submission_status::Pending {})),
+			};
+
+			let height = me.submit_celestia_blob(celestia_blob.clone()).await.map_err(|e| tonic::Status::internal(e.to_string()))?;
+			yield BlobSubmissionStatus {
+				status: Some(blob_submission_status::Status::IncludedInBlock(blob_submission_status::IncludedInBlock { height })),
+			};
+
+			let found = me.wait_for_blob_at_height(&celestia_blob, height).await.map_err(|e| tonic::Status::internal(e.to_string()))?;
+			if !found {
+				Err(tonic::Status::deadline_exceeded(format!("Blob was not found at height {} before the timeout", height)))?;
+			}
+			yield BlobSubmissionStatus {
+				status: Some(blob_submission_status::Status::Finalized(blob_submission_status::Finalized {
+					height,
+					commitment: celestia_blob.commitment.0.to_vec(),
+				})),
+			};
+
+		};
+
+		Ok(tonic::Response::new(Box::pin(output) as Self::SubmitBlobStreamStream))
+	}
+
 	/// Update and manage verification parameters.
 	async fn update_verification_parameters(
 		&self,
@@ -452,6 +712,10 @@ impl LightNodeService for LightNodeV1 {
 		let verification_mode = request.into_inner().mode();
 		let mut mode = self.verification_mode.write().await;
 		*mode = verification_mode;
+		// the cached blobs were verified with the previous mode
+		self.blob_cache
+			.set_verification_mode(verification_mode)
+			.map_err(|e| tonic::Status::internal(e.to_string()))?;
 
 		Ok(tonic::Response::new(UpdateVerificationParametersResponse {
 			mode: verification_mode.into(),
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+use tracing::{debug, warn};
+
+use celestia_rpc::{BlobClient, Client, HeaderClient};
+use celestia_types::nmt::Namespace;
+use jsonrpsee_core::client::Error as ClientError;
+
+use m1_da_light_node_grpc::VerificationMode;
+use m1_da_light_node_verifier::{
+	auth::{self, HmacKey},
+	payload::{self, VerificationError},
+	Verifier,
+};
+
+// Number of fetched blobs buffered ahead of verification.
+const FETCHED_BLOBS_CHANNEL_CAPACITY: usize = 64;
+// Interval between polls of the Celestia network head once the pipeline has caught up.
+const NETWORK_HEAD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Errors thrown by the stages of a [`DaVerificationPipeline`].
+#[derive(Debug, Error)]
+pub enum PipelineError {
+	#[error("Failed to get the Celestia network head: {0}")]
+	NetworkHead(String),
+	#[error("Failed to get the blobs at height {height}: {message}")]
+	Fetch { height: u64, message: String },
+	#[error("Failed to verify a blob at height {height}: {message}")]
+	Verification { height: u64, message: String },
+}
+
+/// A value which passed verification, it can only be created by a [`DaVerificationPipeline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verified<T>(T);
+
+impl<T> Verified<T> {
+	pub fn get(&self) -> &T {
+		&self.0
+	}
+
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+/// Strips the MAC and the version header of the blobs read from the DA.
+#[derive(Debug, Clone, Copy)]
+pub struct BlobDecoder {
+	pub hmac_key: Option<HmacKey>,
+	/// Whether to accept unversioned payloads submitted before payload versioning.
+	pub accept_legacy_payloads: bool,
+}
+
+impl BlobDecoder {
+	/// Decodes a blob into its payload.
+	pub fn decode(&self, blob: &[u8]) -> Result<Vec<u8>, VerificationError> {
+		let authenticated = auth::decode_authenticated(self.hmac_key.as_ref(), blob)?;
+		let (version, payload) =
+			payload::decode_payload(authenticated, self.accept_legacy_payloads)?;
+		debug!(version, "decoded blob");
+		Ok(payload.to_vec())
+	}
+}
+
+/// Follows the blobs posted to a namespace, verifying and decoding them in order.
+///
+/// Blobs are fetched by a background task into a bounded channel, so fetching pauses
+/// while verification falls behind, and stops once the stream is dropped.
+pub struct DaVerificationPipeline<V> {
+	client: Arc<Client>,
+	namespace: Namespace,
+	decoder: BlobDecoder,
+	verifier: V,
+	mode: VerificationMode,
+}
+
+impl<V> DaVerificationPipeline<V>
+where
+	V: Verifier + Send + Sync + 'static,
+{
+	pub fn new(
+		client: Arc<Client>,
+		namespace: Namespace,
+		decoder: BlobDecoder,
+		verifier: V,
+		mode: VerificationMode,
+	) -> Self {
+		Self { client, namespace, decoder, verifier, mode }
+	}
+
+	/// Runs the pipeline from `start_height` on, yielding the payload of each verified blob
+	/// with its height. Blobs which fail verification or decoding are skipped, as anyone can
+	/// post to the namespace. The stream ends at the first error.
+	///
+	/// Blobs are verified before they are decoded, as the verifiers check the blob as posted.
+	pub fn run(
+		self,
+		start_height: u64,
+	) -> impl Stream<Item = Result<(u64, Verified<Vec<u8>>), PipelineError>> {
+		let (sender, mut receiver) = mpsc::channel(FETCHED_BLOBS_CHANNEL_CAPACITY);
+		tokio::spawn(Self::fetch_blobs(self.client, self.namespace, start_height, sender));
+
+		let Self { decoder, verifier, mode, .. } = self;
+		async_stream::try_stream! {
+			while let Some(fetched) = receiver.recv().await {
+				let (height, blob): (u64, Vec<u8>) = fetched?;
+				let verified = verifier.verify(mode, &blob, height).await.map_err(|e| {
+					PipelineError::Verification { height, message: e.to_string() }
+				})?;
+				if !verified {
+					debug!(height, "skipping blob which failed verification");
+					continue;
+				}
+				let payload = match decoder.decode(&blob) {
+					Ok(payload) => payload,
+					Err(e) => {
+						warn!(height, error = %e, "skipping verified blob which failed decoding");
+						continue;
+					}
+				};
+				yield (height, Verified(payload));
+			}
+		}
+	}
+
+	/// Sends the blobs at every height from `start_height` up to the network head, then
+	/// polls for new heights. Returns once an error is sent or the receiver is dropped.
+	///
+	/// A height the node answers `blob_get_all` with an error for is treated as empty, as the
+	/// node errors on heights without blobs in the namespace. Only transport errors are sent.
+	async fn fetch_blobs(
+		client: Arc<Client>,
+		namespace: Namespace,
+		start_height: u64,
+		sender: mpsc::Sender<Result<(u64, Vec<u8>), PipelineError>>,
+	) {
+		let mut height = start_height;
+		loop {
+			let head: u64 = match client.header_network_head().await {
+				Ok(header) => header.height().into(),
+				Err(e) => {
+					let _ = sender.send(Err(PipelineError::NetworkHead(e.to_string()))).await;
+					return;
+				}
+			};
+			while height <= head {
+				let blobs = match client.blob_get_all(height, &[namespace]).await {
+					Ok(blobs) => blobs,
+					Err(ClientError::Call(e)) => {
+						debug!(height, error = %e, "no blobs at height");
+						Vec::new()
+					}
+					Err(e) => {
+						let error = PipelineError::Fetch { height, message: e.to_string() };
+						let _ = sender.send(Err(error)).await;
+						return;
+					}
+				};
+				for blob in blobs {
+					if sender.send(Ok((height, blob.data))).await.is_err() {
+						return;
+					}
+				}
+				height += 1;
+			}
+			tokio::time::sleep(NETWORK_HEAD_POLL_INTERVAL).await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode_authenticated_blob() -> Result<(), anyhow::Error> {
+		let decoder =
+			BlobDecoder { hmac_key: Some(HmacKey([7; 32])), accept_legacy_payloads: false };
+		let blob = auth::encode_authenticated(
+			decoder.hmac_key.as_ref(),
+			payload::encode_payload(b"block"),
+		);
+		assert_eq!(decoder.decode(&blob)?, b"block");
+
+		let other_decoder = BlobDecoder { hmac_key: Some(HmacKey([8; 32])), ..decoder };
+		assert_eq!(other_decoder.decode(&blob), Err(VerificationError::InvalidMac));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_decode_legacy_blob() -> Result<(), anyhow::Error> {
+		let decoder = BlobDecoder { hmac_key: None, accept_legacy_payloads: true };
+		assert_eq!(decoder.decode(b"legacy")?, b"legacy");
+		Ok(())
+	}
+}
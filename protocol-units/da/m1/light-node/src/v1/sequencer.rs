@@ -19,11 +19,17 @@ use movement_algs::grouping_heuristic::{
 use movement_types::block::Block;
 use std::boxed::Box;
 use tokio::{
-	sync::mpsc::{Receiver, Sender},
+	sync::{
+		mpsc::{Receiver, Sender},
+		watch,
+	},
 	time::timeout,
 };
 
-use crate::v1::{passthrough::LightNodeV1 as LightNodeV1PassThrough, LightNodeV1Operations};
+use crate::v1::{
+	light_node::wait_for_shutdown, passthrough::LightNodeV1 as LightNodeV1PassThrough,
+	LightNodeV1Operations,
+};
 
 const LOGGING_UID: AtomicU64 = AtomicU64::new(0);
 
@@ -64,8 +70,22 @@ impl LightNodeV1Operations for LightNodeV1 {
 		self.pass_through.try_service_address()
 	}
 
-	async fn run_background_tasks(&self) -> Result<(), anyhow::Error> {
-		self.run_block_proposer().await?;
+	async fn run_background_tasks(
+		&self,
+		mut shutdown: watch::Receiver<bool>,
+	) -> Result<(), anyhow::Error> {
+		let pass_through_shutdown = shutdown.clone();
+		// the block proposer runs until it is stopped
+		let block_proposer = async {
+			tokio::select! {
+				result = self.run_block_proposer() => result,
+				_ = wait_for_shutdown(&mut shutdown) => Ok(()),
+			}
+		};
+		tokio::try_join!(
+			block_proposer,
+			self.pass_through.run_background_tasks(pass_through_shutdown)
+		)?;
 
 		Ok(())
 	}
@@ -120,10 +140,14 @@ impl LightNodeV1 {
 		// wrap the blocks in a struct that can be split and compressed
 		// spawn blocking because the compression is blocking and could be slow
 		let namespace = self.pass_through.celestia_namespace.clone();
+		let hmac_key = self.pass_through.hmac_key;
+		let blob_format = self.pass_through.config.m1_da_light_node_blob_format();
 		let blocks = tokio::task::spawn_blocking(move || {
 			blocks
 				.into_iter()
-				.map(|block| block::WrappedBlock::try_new(block, namespace))
+				.map(|block| {
+					block::WrappedBlock::try_new(block, namespace, hmac_key.as_ref(), blob_format)
+				})
 				.collect::<Result<Vec<_>, anyhow::Error>>()
 		})
 		.await??;
@@ -358,6 +382,15 @@ impl LightNodeService for LightNodeV1 {
 	) -> std::result::Result<tonic::Response<ReadAtHeightResponse>, tonic::Status> {
 		self.pass_through.read_at_height(request).await
 	}
+
+	/// Get the raw blobs stored at a specified height.
+	async fn get_blob_at_height(
+		&self,
+		request: tonic::Request<GetBlobAtHeightRequest>,
+	) -> std::result::Result<tonic::Response<GetBlobAtHeightResponse>, tonic::Status> {
+		self.pass_through.get_blob_at_height(request).await
+	}
+
 	/// Batch read and write operations for efficiency.
 	async fn batch_read(
 		&self,
@@ -407,6 +440,18 @@ impl LightNodeService for LightNodeV1 {
 
 		Ok(tonic::Response::new(BatchWriteResponse { blobs: intents }))
 	}
+	/// Server streaming response type for the SubmitBlobStream method.
+	type SubmitBlobStreamStream = std::pin::Pin<
+		Box<dyn Stream<Item = Result<BlobSubmissionStatus, tonic::Status>> + Send + 'static>,
+	>;
+	/// Raw blobs would bypass the sequencer, so they are not accepted in sequencer mode.
+	async fn submit_blob_stream(
+		&self,
+		_request: tonic::Request<BlobSubmission>,
+	) -> std::result::Result<tonic::Response<Self::SubmitBlobStreamStream>, tonic::Status> {
+		Err(tonic::Status::unimplemented("submit_blob_stream is not available in sequencer mode"))
+	}
+
 	/// Update and manage verification parameters.
 	async fn update_verification_parameters(
 		&self,
@@ -419,6 +464,11 @@ impl LightNodeService for LightNodeV1 {
 mod block {
 
 	use celestia_types::{nmt::Namespace, Blob};
+	use m1_da_light_node_util::codec::BlobFormat;
+	use m1_da_light_node_verifier::{
+		auth::{self, HmacKey},
+		payload,
+	};
 	use movement_algs::grouping_heuristic::{binpacking::BinpackingWeighted, splitting::Splitable};
 	use movement_types::block::Block;
 
@@ -429,15 +479,19 @@ mod block {
 	}
 
 	impl WrappedBlock {
-		pub fn try_new(block: Block, namespace: Namespace) -> Result<Self, anyhow::Error> {
-			// first serialize the block
-			let block_bytes = bcs::to_bytes(&block)?;
-
-			// then compress the block bytes
-			let compressed_block_bytes = zstd::encode_all(block_bytes.as_slice(), 0)?;
-
-			// then create a blob from the compressed block bytes
-			let blob = Blob::new(namespace, compressed_block_bytes)?;
+		pub fn try_new(
+			block: Block,
+			namespace: Namespace,
+			hmac_key: Option<&HmacKey>,
+			blob_format: BlobFormat,
+		) -> Result<Self, anyhow::Error> {
+			// first serialize and compress the block
+			let block_bytes = blob_format.codec().encode(&block)?;
+
+			// then create a blob from the versioned, authenticated compressed block bytes
+			let blob_data =
+				auth::encode_authenticated(hmac_key, payload::encode_payload(&block_bytes));
+			let blob = Blob::new(namespace, blob_data)?;
 
 			Ok(Self { block, blob })
 		}
@@ -0,0 +1,154 @@
+//! Encodings of the blocks posted to the DA.
+//!
+//! All formats are compressed with zstd. The format must be the same for all the nodes of a
+//! chain, blobs are not tagged with the format they were encoded with.
+
+use movement_types::{
+	block::{Block, BlockMetadata, Id},
+	transaction::Transaction,
+};
+use serde::{Deserialize, Serialize};
+
+/// The encoding of the blocks posted to the DA.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlobFormat {
+	/// BCS encoding of the block, the format used before encodings were configurable.
+	#[default]
+	Bcs,
+	/// JSON encoding of the block serde representation.
+	Json,
+	/// Protobuf encoding of the block parent and transactions, the ids are recomputed on decoding.
+	Protobuf,
+}
+
+impl BlobFormat {
+	/// Gets the codec for the format.
+	pub fn codec(&self) -> &'static dyn BlobCodec {
+		match self {
+			BlobFormat::Bcs => &BcsCodec,
+			BlobFormat::Json => &JsonCodec,
+			BlobFormat::Protobuf => &ProtobufCodec,
+		}
+	}
+}
+
+/// Encodes blocks into DA blob payloads, and decodes them back.
+pub trait BlobCodec: Send + Sync {
+	fn encode(&self, block: &Block) -> Result<Vec<u8>, anyhow::Error>;
+
+	fn decode(&self, bytes: &[u8]) -> Result<Block, anyhow::Error>;
+}
+
+pub struct BcsCodec;
+
+impl BlobCodec for BcsCodec {
+	fn encode(&self, block: &Block) -> Result<Vec<u8>, anyhow::Error> {
+		compress(&bcs::to_bytes(block)?)
+	}
+
+	fn decode(&self, bytes: &[u8]) -> Result<Block, anyhow::Error> {
+		Ok(bcs::from_bytes(&zstd::decode_all(bytes)?)?)
+	}
+}
+
+pub struct JsonCodec;
+
+impl BlobCodec for JsonCodec {
+	fn encode(&self, block: &Block) -> Result<Vec<u8>, anyhow::Error> {
+		compress(&serde_json::to_vec(block)?)
+	}
+
+	fn decode(&self, bytes: &[u8]) -> Result<Block, anyhow::Error> {
+		Ok(serde_json::from_slice(&zstd::decode_all(bytes)?)?)
+	}
+}
+
+pub struct ProtobufCodec;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct BlockMessage {
+	#[prost(bytes = "vec", tag = "1")]
+	parent: Vec<u8>,
+	#[prost(message, repeated, tag = "2")]
+	transactions: Vec<TransactionMessage>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct TransactionMessage {
+	#[prost(bytes = "vec", tag = "1")]
+	data: Vec<u8>,
+	#[prost(uint64, tag = "2")]
+	sequence_number: u64,
+}
+
+impl BlobCodec for ProtobufCodec {
+	fn encode(&self, block: &Block) -> Result<Vec<u8>, anyhow::Error> {
+		let message = BlockMessage {
+			parent: block.parent().to_vec(),
+			transactions: block
+				.transactions()
+				.map(|transaction| TransactionMessage {
+					data: transaction.data().to_vec(),
+					sequence_number: transaction.sequence_number(),
+				})
+				.collect(),
+		};
+		compress(&prost::Message::encode_to_vec(&message))
+	}
+
+	fn decode(&self, bytes: &[u8]) -> Result<Block, anyhow::Error> {
+		let message: BlockMessage = prost::Message::decode(zstd::decode_all(bytes)?.as_slice())?;
+		let parent: [u8; 32] = message
+			.parent
+			.try_into()
+			.map_err(|parent: Vec<u8>| anyhow::anyhow!("Parent id of {} bytes", parent.len()))?;
+		let transactions = message
+			.transactions
+			.into_iter()
+			.map(|transaction| Transaction::new(transaction.data, transaction.sequence_number))
+			.collect();
+		Ok(Block::new(BlockMetadata::default(), Id::new(parent), transactions))
+	}
+}
+
+fn compress(bytes: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+	Ok(zstd::encode_all(bytes, 0)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	fn block() -> Block {
+		Block::new(
+			BlockMetadata::default(),
+			Id::new([1; 32]),
+			BTreeSet::from([Transaction::new(vec![1, 2, 3], 0), Transaction::new(vec![4], 1)]),
+		)
+	}
+
+	#[test]
+	fn test_round_trip_all_formats() -> Result<(), anyhow::Error> {
+		for format in [BlobFormat::Bcs, BlobFormat::Json, BlobFormat::Protobuf] {
+			let codec = format.codec();
+			let decoded = codec.decode(&codec.encode(&block())?)?;
+			assert_eq!(decoded, block(), "{:?} does not round trip", format);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn test_bcs_matches_previous_encoding() -> Result<(), anyhow::Error> {
+		let previous = zstd::encode_all(bcs::to_bytes(&block())?.as_slice(), 0)?;
+		assert_eq!(BlobFormat::Bcs.codec().encode(&block())?, previous);
+		Ok(())
+	}
+
+	#[test]
+	fn test_rejects_other_formats() -> Result<(), anyhow::Error> {
+		let json = BlobFormat::Json.codec().encode(&block())?;
+		assert!(BlobFormat::Bcs.codec().decode(&json).is_err());
+		Ok(())
+	}
+}
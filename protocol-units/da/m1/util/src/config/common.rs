@@ -1,5 +1,6 @@
 use celestia_types::nmt::Namespace;
 use godfig::env_default;
+use serde::{Deserialize, Deserializer};
 
 // The default hostname for the Celestia RPC
 env_default!(
@@ -77,17 +78,72 @@ env_default!(
 	30730
 );
 
-// The default Celestia Namespace
-pub fn default_celestia_namespace() -> Namespace {
-	match std::env::var("CELESTIA_NAMESPACE") {
-		Ok(val) => match serde_json::from_str(&val) {
-			Ok(namespace) => namespace,
-			// todo: get rid of this unwrap somehow, event though it should never fail
-			Err(_) => Namespace::new_v0(b"movement").unwrap(),
-		},
-		// todo: get rid of this unwrap somehow, event though it should never fail
-		Err(_) => Namespace::new_v0(b"movement").unwrap(),
+/// Derives the Celestia namespace of a rollup chain from its Aptos chain ID,
+/// so that chains sharing a Celestia instance do not read each other's blobs.
+pub fn derive_namespace(chain_id: u8) -> Namespace {
+	let mut preimage = b"movement_rollup".to_vec();
+	preimage.extend_from_slice(&chain_id.to_le_bytes());
+	let hash = keccak_hash::keccak(preimage);
+	// todo: get rid of this unwrap somehow, even though a 10 byte v0 namespace never fails
+	Namespace::new_v0(&hash.as_bytes()[..10]).unwrap()
+}
+
+/// Parses a Celestia namespace from the hex encoding of its 10 byte v0 ID.
+pub fn namespace_from_hex(hex_namespace: &str) -> Result<Namespace, anyhow::Error> {
+	let bytes = hex::decode(hex_namespace.trim_start_matches("0x"))?;
+	if bytes.len() != 10 {
+		anyhow::bail!("Namespace ID must be 10 bytes, got {}", bytes.len());
+	}
+	Ok(Namespace::new_v0(&bytes)?)
+}
+
+/// Deserializes a Celestia namespace either in its own encoding or as a hex string.
+pub fn deserialize_namespace<'de, D>(deserializer: D) -> Result<Namespace, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum NamespaceRepr {
+		Namespace(Namespace),
+		Hex(String),
 	}
+
+	match NamespaceRepr::deserialize(deserializer)? {
+		NamespaceRepr::Namespace(namespace) => Ok(namespace),
+		NamespaceRepr::Hex(hex_namespace) => {
+			namespace_from_hex(&hex_namespace).map_err(serde::de::Error::custom)
+		}
+	}
+}
+
+// The chain ID from which the default Celestia namespace is derived
+env_default!(default_celestia_namespace_chain_id, "MAPTOS_CHAIN_ID", u8, 27);
+
+/// The namespace blobs were posted to before namespaces were derived from the chain ID.
+pub fn legacy_celestia_namespace() -> Namespace {
+	// todo: get rid of this unwrap somehow, even though it should never fail
+	Namespace::new_v0(b"movement").unwrap()
+}
+
+// The Celestia namespace set by the environment, if any
+fn env_celestia_namespace() -> Option<Namespace> {
+	let val = std::env::var("CELESTIA_NAMESPACE").ok()?;
+	serde_json::from_str(&val).ok().or_else(|| namespace_from_hex(&val).ok())
+}
+
+// The default Celestia Namespace of new configs, derived from the chain ID
+pub fn default_celestia_namespace() -> Namespace {
+	env_celestia_namespace()
+		.unwrap_or_else(|| derive_namespace(default_celestia_namespace_chain_id()))
+}
+
+// The default Celestia Namespace of existing configs which do not set one.
+// Those were written before namespaces were derived from the chain ID, and keep the legacy
+// namespace so that they still read the blobs posted to it. Switching to the derived namespace
+// requires setting it explicitly, and starts the chain's DA history from scratch.
+pub fn default_existing_celestia_namespace() -> Namespace {
+	env_celestia_namespace().unwrap_or_else(legacy_celestia_namespace)
 }
 
 // The default Celestia chain id
@@ -107,6 +163,14 @@ pub fn default_celestia_appd_replace_args() -> Vec<String> {
 	}
 }
 
+// The interval between polls of Celestia for new blobs when subscribing, in milliseconds
+env_default!(
+	default_m1_da_light_node_subscribe_poll_interval_ms,
+	"M1_DA_LIGHT_NODE_SUBSCRIBE_POLL_INTERVAL_MS",
+	u64,
+	1000
+);
+
 // Whether to use replace args for Celestia bridge
 env_default!(
 	default_celestia_bridge_use_replace_args,
@@ -123,5 +187,77 @@ pub fn default_celestia_bridge_replace_args() -> Vec<String> {
 	}
 }
 
+// Whether to accept DA payloads submitted before payload versioning.
+// Off by default: blobs of unknown versions are accepted as legacy payloads while this is on,
+// so it is only meant to be opted into for the migration window.
+env_default!(
+	default_m1_da_light_node_accept_legacy_payloads,
+	"M1_DA_LIGHT_NODE_ACCEPT_LEGACY_PAYLOADS",
+	bool,
+	false
+);
+
+// The maximum number of submitted blob hashes remembered for deduplication
+env_default!(
+	default_m1_da_light_node_dedup_max_cache_entries,
+	"M1_DA_LIGHT_NODE_DEDUP_MAX_CACHE_ENTRIES",
+	usize,
+	4096
+);
+
+// The maximum number of DA heights whose verified blobs are cached
+env_default!(
+	default_m1_da_light_node_blob_cache_capacity,
+	"M1_DA_LIGHT_NODE_BLOB_CACHE_CAPACITY",
+	usize,
+	1024
+);
+
 // Whether to use replace args for Celestia bridge
 env_default!(default_m1_da_light_node_is_initial, "M1_DA_LIGHT_NODE_IS_INITIAL", bool, true);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_derive_namespace() -> Result<(), anyhow::Error> {
+		assert_eq!(derive_namespace(27), derive_namespace(27));
+		assert_ne!(derive_namespace(27), derive_namespace(126));
+
+		let hash = keccak_hash::keccak(b"movement_rollup\x1b");
+		assert_eq!(derive_namespace(27), Namespace::new_v0(&hash.as_bytes()[..10])?);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_deserialize_hex_namespace() -> Result<(), anyhow::Error> {
+		#[derive(Deserialize)]
+		struct Wrapper {
+			#[serde(deserialize_with = "deserialize_namespace")]
+			namespace: Namespace,
+		}
+
+		let wrapper: Wrapper =
+			serde_json::from_str(r#"{ "namespace": "0x000102030405060708ff" }"#)?;
+		assert_eq!(wrapper.namespace, Namespace::new_v0(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 0xff])?);
+
+		let namespace = derive_namespace(27);
+		let wrapper: Wrapper = serde_json::from_value(
+			serde_json::json!({ "namespace": serde_json::to_value(namespace)? }),
+		)?;
+		assert_eq!(wrapper.namespace, namespace);
+
+		assert!(namespace_from_hex("0001").is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_existing_config_keeps_legacy_namespace() -> Result<(), anyhow::Error> {
+		let config: crate::config::local::appd::Config = serde_json::from_str("{}")?;
+		assert_eq!(config.celestia_namespace, legacy_celestia_namespace());
+		Ok(())
+	}
+}
@@ -2,7 +2,8 @@ use crate::config::common::{
 	default_celestia_appd_replace_args, default_celestia_appd_use_replace_args,
 	default_celestia_chain_id, default_celestia_namespace, default_celestia_rpc_listen_hostname,
 	default_celestia_rpc_listen_port, default_celestia_websocket_connection_hostname,
-	default_celestia_websocket_connection_port,
+	default_celestia_websocket_connection_port, default_existing_celestia_namespace,
+	deserialize_namespace,
 };
 
 use celestia_types::nmt::Namespace;
@@ -34,8 +35,13 @@ pub struct Config {
 	#[serde(default = "default_celestia_chain_id")]
 	pub celestia_chain_id: String,
 
-	/// The namespace for the Celestia node
-	#[serde(default = "default_celestia_namespace")]
+	/// The namespace for the Celestia node, derived from the chain ID in new configs.
+	/// Existing configs which do not set it keep the legacy `movement` namespace.
+	/// It can be set to the hex encoding of a 10 byte namespace ID.
+	#[serde(
+		default = "default_existing_celestia_namespace",
+		deserialize_with = "deserialize_namespace"
+	)]
 	pub celestia_namespace: Namespace,
 
 	/// The celestia app path for when that is being orchestrated locally
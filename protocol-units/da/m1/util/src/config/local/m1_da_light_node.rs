@@ -1,8 +1,11 @@
+use crate::codec::BlobFormat;
 use crate::config::common::{
 	default_celestia_rpc_connection_hostname, default_celestia_rpc_connection_port,
 	default_celestia_websocket_connection_hostname, default_celestia_websocket_connection_port,
+	default_m1_da_light_node_accept_legacy_payloads, default_m1_da_light_node_blob_cache_capacity,
 	default_m1_da_light_node_connection_hostname, default_m1_da_light_node_connection_port,
-	default_m1_da_light_node_listen_hostname, default_m1_da_light_node_listen_port,
+	default_m1_da_light_node_dedup_max_cache_entries, default_m1_da_light_node_listen_hostname,
+	default_m1_da_light_node_listen_port, default_m1_da_light_node_subscribe_poll_interval_ms,
 };
 use serde::{Deserialize, Serialize};
 
@@ -40,6 +43,39 @@ pub struct Config {
 	/// The port for m1-da-light-node connection
 	#[serde(default = "default_m1_da_light_node_connection_port")]
 	pub m1_da_light_node_connection_port: u16,
+
+	/// Whether to accept unversioned DA payloads, an opt-in for the migration window only.
+	/// While it is set, payloads of unknown versions are accepted as legacy payloads.
+	#[serde(default = "default_m1_da_light_node_accept_legacy_payloads")]
+	pub m1_da_light_node_accept_legacy_payloads: bool,
+
+	/// The path of the database persisting submitted blob hashes, deduplication is off if unset
+	#[serde(default)]
+	pub m1_da_light_node_dedup_db_path: Option<String>,
+
+	/// The maximum number of submitted blob hashes remembered for deduplication
+	#[serde(default = "default_m1_da_light_node_dedup_max_cache_entries")]
+	pub max_cache_entries: usize,
+
+	/// The maximum number of DA heights whose verified blobs are cached
+	#[serde(default = "default_m1_da_light_node_blob_cache_capacity")]
+	pub blob_cache_capacity: usize,
+
+	/// The directory the blob cache is persisted to on shutdown, it is not persisted if unset
+	#[serde(default)]
+	pub m1_da_light_node_blob_cache_dir: Option<String>,
+
+	/// The hex-encoded 32-byte key authenticating submitted blobs, blobs are not authenticated if unset
+	#[serde(default)]
+	pub m1_da_light_node_hmac_key: Option<String>,
+
+	/// The interval between polls of Celestia for new blobs when subscribing, in milliseconds
+	#[serde(default = "default_m1_da_light_node_subscribe_poll_interval_ms")]
+	pub m1_da_light_node_subscribe_poll_interval_ms: u64,
+
+	/// The encoding of the blocks posted to the DA, which must be the same for all nodes of a chain
+	#[serde(default)]
+	pub m1_da_light_node_blob_format: BlobFormat,
 }
 
 impl Default for Config {
@@ -54,6 +90,16 @@ impl Default for Config {
 			m1_da_light_node_listen_port: default_m1_da_light_node_listen_port(),
 			m1_da_light_node_connection_hostname: default_m1_da_light_node_connection_hostname(),
 			m1_da_light_node_connection_port: default_m1_da_light_node_connection_port(),
+			m1_da_light_node_accept_legacy_payloads:
+				default_m1_da_light_node_accept_legacy_payloads(),
+			m1_da_light_node_dedup_db_path: None,
+			max_cache_entries: default_m1_da_light_node_dedup_max_cache_entries(),
+			blob_cache_capacity: default_m1_da_light_node_blob_cache_capacity(),
+			m1_da_light_node_blob_cache_dir: None,
+			m1_da_light_node_hmac_key: None,
+			m1_da_light_node_subscribe_poll_interval_ms:
+				default_m1_da_light_node_subscribe_poll_interval_ms(),
+			m1_da_light_node_blob_format: BlobFormat::default(),
 		}
 	}
 }
@@ -1,3 +1,4 @@
+use crate::codec::BlobFormat;
 use anyhow::Context;
 use celestia_rpc::Client;
 use celestia_types::nmt::Namespace;
@@ -161,6 +162,97 @@ impl Config {
 		}
 	}
 
+	/// Gets whether M1 DA Light Node accepts unversioned legacy payloads
+	pub fn m1_da_light_node_accept_legacy_payloads(&self) -> bool {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.m1_da_light_node_accept_legacy_payloads,
+			Config::Arabica(local) => {
+				local.m1_da_light_node.m1_da_light_node_accept_legacy_payloads
+			}
+			Config::Mocha(local) => local.m1_da_light_node.m1_da_light_node_accept_legacy_payloads,
+		}
+	}
+
+	/// Gets the M1 DA Light Node blob deduplication database path, if deduplication is enabled
+	pub fn m1_da_light_node_dedup_db_path(&self) -> Option<String> {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.m1_da_light_node_dedup_db_path.clone(),
+			Config::Arabica(local) => local.m1_da_light_node.m1_da_light_node_dedup_db_path.clone(),
+			Config::Mocha(local) => local.m1_da_light_node.m1_da_light_node_dedup_db_path.clone(),
+		}
+	}
+
+	/// Gets the maximum number of submitted blob hashes remembered for deduplication
+	pub fn max_cache_entries(&self) -> usize {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.max_cache_entries,
+			Config::Arabica(local) => local.m1_da_light_node.max_cache_entries,
+			Config::Mocha(local) => local.m1_da_light_node.max_cache_entries,
+		}
+	}
+
+	/// Gets the maximum number of DA heights whose verified blobs are cached
+	pub fn blob_cache_capacity(&self) -> usize {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.blob_cache_capacity,
+			Config::Arabica(local) => local.m1_da_light_node.blob_cache_capacity,
+			Config::Mocha(local) => local.m1_da_light_node.blob_cache_capacity,
+		}
+	}
+
+	/// Gets the directory the blob cache is persisted to, if any
+	pub fn m1_da_light_node_blob_cache_dir(&self) -> Option<String> {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.m1_da_light_node_blob_cache_dir.clone(),
+			Config::Arabica(local) => {
+				local.m1_da_light_node.m1_da_light_node_blob_cache_dir.clone()
+			}
+			Config::Mocha(local) => local.m1_da_light_node.m1_da_light_node_blob_cache_dir.clone(),
+		}
+	}
+
+	/// Gets the key authenticating submitted blobs, if blob authentication is enabled
+	pub fn try_m1_da_light_node_hmac_key(&self) -> Result<Option<[u8; 32]>, anyhow::Error> {
+		let hmac_key = match self {
+			Config::Local(local) => &local.m1_da_light_node.m1_da_light_node_hmac_key,
+			Config::Arabica(local) => &local.m1_da_light_node.m1_da_light_node_hmac_key,
+			Config::Mocha(local) => &local.m1_da_light_node.m1_da_light_node_hmac_key,
+		};
+		hmac_key
+			.as_ref()
+			.map(|hmac_key| {
+				let bytes = hex::decode(hmac_key.trim_start_matches("0x"))
+					.context("Failed to decode the m1-da-light-node HMAC key as hex")?;
+				<[u8; 32]>::try_from(bytes.as_slice())
+					.map_err(|_| anyhow::anyhow!("The m1-da-light-node HMAC key must be 32 bytes"))
+			})
+			.transpose()
+	}
+
+	/// Gets the interval between polls of Celestia for new blobs when subscribing, in milliseconds
+	pub fn m1_da_light_node_subscribe_poll_interval_ms(&self) -> u64 {
+		match self {
+			Config::Local(local) => {
+				local.m1_da_light_node.m1_da_light_node_subscribe_poll_interval_ms
+			}
+			Config::Arabica(local) => {
+				local.m1_da_light_node.m1_da_light_node_subscribe_poll_interval_ms
+			}
+			Config::Mocha(local) => {
+				local.m1_da_light_node.m1_da_light_node_subscribe_poll_interval_ms
+			}
+		}
+	}
+
+	/// Gets the encoding of the blocks posted to the DA
+	pub fn m1_da_light_node_blob_format(&self) -> BlobFormat {
+		match self {
+			Config::Local(local) => local.m1_da_light_node.m1_da_light_node_blob_format,
+			Config::Arabica(local) => local.m1_da_light_node.m1_da_light_node_blob_format,
+			Config::Mocha(local) => local.m1_da_light_node.m1_da_light_node_blob_format,
+		}
+	}
+
 	/// Gets the memseq path
 	pub fn try_memseq_path(&self) -> Result<String, anyhow::Error> {
 		match self {
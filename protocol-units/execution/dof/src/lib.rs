@@ -11,6 +11,9 @@ pub use aptos_types::{
 	transaction::signature_verified_transaction::SignatureVerifiedTransaction,
 	transaction::{SignedTransaction, Transaction},
 };
+pub use m1_da_light_node_verifier::{VerificationMode, Verifier};
+pub use maptos_opt_executor::executor::{ApplyResult, BlockRetry, DaIngestionConfig};
+
 use maptos_execution_util::config::Config;
 use movement_types::block::BlockCommitment;
 
@@ -45,6 +48,18 @@ pub trait DynOptFinExecutor {
 		block: ExecutableBlock,
 	) -> Result<BlockCommitment, anyhow::Error>;
 
+	/// Set how the blobs read from the DA are ingested
+	fn set_da_ingestion(&self, config: DaIngestionConfig) -> Result<(), anyhow::Error>;
+
+	/// Verifies a blob read from the DA and executes its block optimistically
+	async fn apply_da_blob_opt(
+		&self,
+		blob: &[u8],
+		da_height: u64,
+		timestamp: u64,
+		mode: VerificationMode,
+	) -> Result<ApplyResult, anyhow::Error>;
+
 	/// Update the height of the latest finalized block
 	fn set_finalized_block_height(&self, block_height: u64) -> Result<(), anyhow::Error>;
 
@@ -54,6 +69,9 @@ pub trait DynOptFinExecutor {
 	/// Get block head height.
 	fn get_block_head_height(&self) -> Result<u64, anyhow::Error>;
 
+	/// Get the commitment of the block head.
+	fn get_block_head_commitment(&self) -> Result<BlockCommitment, anyhow::Error>;
+
 	/// Build block metadata for a timestamp
 	fn build_block_metadata(
 		&self,
@@ -1,6 +1,6 @@
 use crate::{
-	BlockMetadata, DynOptFinExecutor, ExecutableBlock, HashValue, MakeOptFinServices, Services,
-	SignedTransaction,
+	ApplyResult, BlockMetadata, DaIngestionConfig, DynOptFinExecutor, ExecutableBlock, HashValue,
+	MakeOptFinServices, Services, SignedTransaction, VerificationMode,
 };
 use maptos_execution_util::config::Config;
 use maptos_fin_view::FinalityView;
@@ -90,6 +90,21 @@ impl DynOptFinExecutor for Executor {
 		self.executor.execute_block(block).await
 	}
 
+	fn set_da_ingestion(&self, config: DaIngestionConfig) -> Result<(), anyhow::Error> {
+		self.executor.set_da_ingestion(config)
+	}
+
+	async fn apply_da_blob_opt(
+		&self,
+		blob: &[u8],
+		da_height: u64,
+		timestamp: u64,
+		mode: VerificationMode,
+	) -> Result<ApplyResult, anyhow::Error> {
+		debug!("Applying DA blob at height {}", da_height);
+		self.executor.apply_da_blob(blob, da_height, timestamp, mode).await
+	}
+
 	fn set_finalized_block_height(&self, height: u64) -> Result<(), anyhow::Error> {
 		self.finality_view.set_finalized_block_height(height)
 	}
@@ -110,6 +125,11 @@ impl DynOptFinExecutor for Executor {
 		self.executor.get_block_head_height()
 	}
 
+	/// Get the commitment of the block head.
+	fn get_block_head_commitment(&self) -> Result<BlockCommitment, anyhow::Error> {
+		self.executor.get_block_head_commitment()
+	}
+
 	/// Build block metadata for a timestamp
 	fn build_block_metadata(
 		&self,
@@ -1,10 +1,14 @@
+use aptos_config::config::{
+	RocksdbConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+	DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
 use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_db::AptosDB;
 use aptos_executor::db_bootstrapper;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::{
 	chain_id::ChainId,
-	on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+	on_chain_config::{FeatureFlag, Features, OnChainConsensusConfig, OnChainExecutionConfig},
 	transaction::{ChangeSet, Transaction, WriteSetPayload},
 	validator_signer::ValidatorSigner,
 };
@@ -12,49 +16,101 @@ use aptos_vm::AptosVM;
 use aptos_vm_genesis::{
 	default_gas_schedule, encode_genesis_change_set, GenesisConfiguration, TestValidator, Validator,
 };
+use maptos_execution_util::config::rocksdb;
+
+use anyhow::Context as _;
 
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::str::FromStr;
+
+/// The duration of an epoch configured at genesis.
+// This number should not exceed u64::MAX / 1_000_000_000
+// to avoid overflowing calculations in aptos-vm-genesis.
+// This will last several centuries.
+pub(crate) const EPOCH_DURATION_SECS: u64 = 60 * 60 * 24 * 1024 * 128;
+
+/// Sets the parts of a [`GenesisConfiguration`] controlled by the operator.
+/// The configuration is defined in `aptos-vm-genesis`, so its builder methods are provided by
+/// this extension trait.
+pub trait GenesisConfigurationExt: Sized {
+	/// Sets the feature flags enabled at genesis from a TOML file mapping flag names,
+	/// such as `CODE_DEPENDENCY_CHECK`, to whether they are enabled.
+	/// Flags which are not listed keep their default state, unknown flags are rejected.
+	fn with_features_from_toml_file(self, path: &Path) -> Result<Self, anyhow::Error>;
+}
+
+impl GenesisConfigurationExt for GenesisConfiguration {
+	fn with_features_from_toml_file(mut self, path: &Path) -> Result<Self, anyhow::Error> {
+		self.initial_features_override = Some(features_from_toml_file(path)?);
+		Ok(self)
+	}
+}
+
+/// The genesis configuration of the chain, with the framework's default feature flags.
+pub fn genesis_configuration() -> GenesisConfiguration {
+	// todo: get this config from somewhere
+	GenesisConfiguration {
+		allow_new_validators: true,
+		epoch_duration_secs: EPOCH_DURATION_SECS,
+		is_test: true,
+		min_stake: 0,
+		min_voting_threshold: 0,
+		// 1M APTOS coins (with 8 decimals).
+		max_stake: 100_000_000_000_000,
+		recurring_lockup_duration_secs: EPOCH_DURATION_SECS * 2,
+		required_proposer_stake: 0,
+		rewards_apy_percentage: 0,
+		voting_duration_secs: EPOCH_DURATION_SECS,
+		voting_power_increase_limit: 50,
+		employee_vesting_start: 1663456089,
+		employee_vesting_period_duration: 5 * 60, // 5 minutes
+		initial_features_override: None,
+		randomness_config_override: None,
+		jwk_consensus_config_override: None,
+	}
+}
+
+// Reads the feature flags of a TOML file, see
+// [`GenesisConfigurationExt::with_features_from_toml_file`].
+fn features_from_toml_file(path: &Path) -> Result<Features, anyhow::Error> {
+	let contents = std::fs::read_to_string(path)
+		.with_context(|| format!("Failed to read the feature flags file {}", path.display()))?;
+	let flags: BTreeMap<String, bool> = toml::from_str(&contents)
+		.with_context(|| format!("Failed to parse the feature flags file {}", path.display()))?;
+
+	let mut features = Features::default();
+	let mut unknown_flags = Vec::new();
+	for (name, enabled) in flags {
+		match FeatureFlag::from_str(&name) {
+			Ok(flag) if enabled => features.enable(flag),
+			Ok(flag) => features.disable(flag),
+			Err(_) => unknown_flags.push(name),
+		}
+	}
+	if !unknown_flags.is_empty() {
+		anyhow::bail!("Unknown feature flags in {}: {}", path.display(), unknown_flags.join(", "));
+	}
+	Ok(features)
+}
 
 fn genesis_change_set_and_validators(
 	chain_id: ChainId,
 	count: Option<usize>,
 	public_key: &Ed25519PublicKey,
+	genesis_config: &GenesisConfiguration,
 ) -> (ChangeSet, Vec<TestValidator>) {
 	let framework = aptos_cached_packages::head_release_bundle();
 	let test_validators = TestValidator::new_test_set(count, Some(100_000_000));
 	let validators_: Vec<Validator> = test_validators.iter().map(|t| t.data.clone()).collect();
 	let validators = &validators_;
 
-	// This number should not exceed u64::MAX / 1_000_000_000
-	// to avoid overflowing calculations in aptos-vm-genesis.
-	// This will last several centuries.
-	const EPOCH_DURATION_SECS: u64 = 60 * 60 * 24 * 1024 * 128;
-
 	let genesis = encode_genesis_change_set(
 		&public_key,
 		validators,
 		framework,
 		chain_id,
-		// todo: get this config from somewhere
-		&GenesisConfiguration {
-			allow_new_validators: true,
-			epoch_duration_secs: EPOCH_DURATION_SECS,
-			is_test: true,
-			min_stake: 0,
-			min_voting_threshold: 0,
-			// 1M APTOS coins (with 8 decimals).
-			max_stake: 100_000_000_000_000,
-			recurring_lockup_duration_secs: EPOCH_DURATION_SECS * 2,
-			required_proposer_stake: 0,
-			rewards_apy_percentage: 0,
-			voting_duration_secs: EPOCH_DURATION_SECS,
-			voting_power_increase_limit: 50,
-			employee_vesting_start: 1663456089,
-			employee_vesting_period_duration: 5 * 60, // 5 minutes
-			initial_features_override: None,
-			randomness_config_override: None,
-			jwk_consensus_config_override: None,
-		},
+		genesis_config,
 		&OnChainConsensusConfig::default_for_genesis(),
 		&OnChainExecutionConfig::default_for_genesis(),
 		&default_gas_schedule(),
@@ -62,14 +118,52 @@ fn genesis_change_set_and_validators(
 	(genesis, test_validators)
 }
 
+/// Opens the database, applying the RocksDB tuning parameters unless configured for tests.
+fn open_db(
+	db_dir: impl AsRef<Path> + Clone,
+	rocksdb_config: &rocksdb::Config,
+) -> Result<AptosDB, anyhow::Error> {
+	if rocksdb_config.is_test {
+		return Ok(AptosDB::new_for_test(db_dir));
+	}
+
+	let tuned_config = RocksdbConfig {
+		max_open_files: rocksdb_config.max_open_files,
+		max_total_wal_size: rocksdb_config.max_total_wal_size_bytes,
+		block_cache_size: rocksdb_config.block_cache_bytes,
+		..RocksdbConfig::default()
+	};
+	let rocksdb_configs = RocksdbConfigs {
+		ledger_db_config: tuned_config,
+		state_merkle_db_config: tuned_config,
+		state_kv_db_config: tuned_config,
+		index_db_config: tuned_config,
+		..RocksdbConfigs::default()
+	};
+	AptosDB::open(
+		StorageDirPaths::from_path(db_dir),
+		false,
+		NO_OP_STORAGE_PRUNER_CONFIG,
+		rocksdb_configs,
+		false,
+		BUFFERED_STATE_TARGET_ITEMS,
+		DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+	)
+	.map_err(|e| anyhow::anyhow!("Failed to open the DB: {:?}", e))
+}
+
 /// Bootstrap a database with a genesis transaction if it is empty.
+/// The genesis transaction is built from `genesis_config`, see [`genesis_configuration`].
 pub fn maybe_bootstrap_empty_db(
 	db_dir: impl AsRef<Path> + Clone,
 	chain_id: ChainId,
 	public_key: &Ed25519PublicKey,
+	rocksdb_config: &rocksdb::Config,
+	genesis_config: &GenesisConfiguration,
 ) -> Result<(DbReaderWriter, ValidatorSigner), anyhow::Error> {
-	let db_rw = DbReaderWriter::new(AptosDB::new_for_test(db_dir));
-	let (genesis, validators) = genesis_change_set_and_validators(chain_id, Some(1), public_key);
+	let db_rw = DbReaderWriter::new(open_db(db_dir, rocksdb_config)?);
+	let (genesis, validators) =
+		genesis_change_set_and_validators(chain_id, Some(1), public_key, genesis_config);
 	let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis));
 	let validator_signer =
 		ValidatorSigner::new(validators[0].data.owner_address, validators[0].consensus_key.clone());
@@ -94,3 +188,29 @@ pub fn maybe_bootstrap_empty_db(
 
 	Ok((db_rw, validator_signer))
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_with_features_from_toml_file() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let path = dir.path().join("features.toml");
+		std::fs::write(
+			&path,
+			"CODE_DEPENDENCY_CHECK = false\nAPTOS_STD_CHAIN_ID_NATIVES = true\n",
+		)?;
+
+		let genesis_config = genesis_configuration().with_features_from_toml_file(&path)?;
+		let features = genesis_config.initial_features_override.expect("features are set");
+		assert!(!features.is_enabled(FeatureFlag::CODE_DEPENDENCY_CHECK));
+		assert!(features.is_enabled(FeatureFlag::APTOS_STD_CHAIN_ID_NATIVES));
+
+		std::fs::write(&path, "NOT_A_FEATURE = true\n")?;
+		let error = genesis_configuration().with_features_from_toml_file(&path).unwrap_err();
+		assert!(error.to_string().contains("NOT_A_FEATURE"));
+
+		Ok(())
+	}
+}
@@ -3,6 +3,8 @@ use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use maptos_execution_util::config::Config;
 
+use crate::ExecutorMetrics;
+
 use std::sync::Arc;
 
 /// Infrastructure shared by services using the storage and the mempool.
@@ -11,6 +13,7 @@ pub struct Context {
 	pub(crate) mempool_client_sender: MempoolClientSender,
 	pub(crate) maptos_config: Config,
 	pub(crate) node_config: NodeConfig,
+	pub(crate) metrics: Arc<ExecutorMetrics>,
 }
 
 impl Context {
@@ -19,8 +22,9 @@ impl Context {
 		mempool_client_sender: MempoolClientSender,
 		maptos_config: Config,
 		node_config: NodeConfig,
+		metrics: Arc<ExecutorMetrics>,
 	) -> Self {
-		Context { db, mempool_client_sender, maptos_config, node_config }
+		Context { db, mempool_client_sender, maptos_config, node_config, metrics }
 	}
 
 	/// Returns a reference on the data store reader.
@@ -40,4 +44,9 @@ impl Context {
 	pub fn node_config(&self) -> &NodeConfig {
 		&self.node_config
 	}
+
+	/// Returns a reference on the metrics of the executor.
+	pub fn metrics(&self) -> Arc<ExecutorMetrics> {
+		Arc::clone(&self.metrics)
+	}
 }
@@ -0,0 +1,142 @@
+//! WebSocket endpoint streaming the events of committed transactions.
+
+use aptos_storage_interface::DbReader;
+use futures::{SinkExt, StreamExt};
+use poem::{
+	handler,
+	web::{
+		websocket::{Message, WebSocket},
+		Data, Query,
+	},
+	IntoResponse,
+};
+use serde::Deserialize;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::{debug, warn};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The number of events buffered for a connection before events are dropped.
+pub const EVENT_STREAM_BUFFER_SIZE: usize = 1024;
+
+// How often the ledger is checked for newly committed transactions.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// The maximum number of transactions read from the ledger at once.
+const MAX_TRANSACTIONS_PER_READ: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct EventStreamParams {
+	/// A JSON object the streamed events must match, see [`matches_filter`].
+	filter: Option<String>,
+}
+
+/// Checks whether an event matches a filter.
+/// Objects match if all of the filter's fields match the event's, other values must be equal.
+pub fn matches_filter(event: &serde_json::Value, filter: &serde_json::Value) -> bool {
+	match (event, filter) {
+		(serde_json::Value::Object(event), serde_json::Value::Object(filter)) => filter
+			.iter()
+			.all(|(key, filter)| event.get(key).is_some_and(|event| matches_filter(event, filter))),
+		(event, filter) => event == filter,
+	}
+}
+
+/// Upgrades the connection to a WebSocket pushing the JSON serialized `ContractEvent`s
+/// of transactions committed from then on.
+#[handler]
+pub fn stream_events(
+	ws: WebSocket,
+	Query(params): Query<EventStreamParams>,
+	Data(db_reader): Data<&Arc<dyn DbReader>>,
+) -> poem::Result<impl IntoResponse> {
+	let filter = params
+		.filter
+		.map(|filter| serde_json::from_str::<serde_json::Value>(&filter))
+		.transpose()
+		.map_err(poem::error::BadRequest)?;
+	let db_reader = Arc::clone(db_reader);
+
+	Ok(ws.on_upgrade(move |socket| async move {
+		let (mut sink, _) = socket.split();
+		let (sender, mut receiver) = mpsc::channel(EVENT_STREAM_BUFFER_SIZE);
+
+		let forwarder = tokio::spawn(async move {
+			if let Err(e) = forward_committed_events(db_reader, filter, sender).await {
+				warn!("Event stream failed: {:?}", e);
+			}
+		});
+		while let Some(event) = receiver.recv().await {
+			if sink.send(Message::Text(event.to_string())).await.is_err() {
+				debug!("Event stream client disconnected");
+				break;
+			}
+		}
+		forwarder.abort();
+	}))
+}
+
+/// Sends the matching events of newly committed transactions until the receiver is dropped.
+/// Events are dropped if the receiver falls behind by more than its buffer.
+async fn forward_committed_events(
+	db_reader: Arc<dyn DbReader>,
+	filter: Option<serde_json::Value>,
+	sender: mpsc::Sender<serde_json::Value>,
+) -> Result<(), anyhow::Error> {
+	let mut next_version = db_reader.get_latest_ledger_info_version()? + 1;
+	loop {
+		tokio::time::sleep(POLL_INTERVAL).await;
+		if sender.is_closed() {
+			return Ok(());
+		}
+
+		let ledger_version = db_reader.get_latest_ledger_info_version()?;
+		while next_version <= ledger_version {
+			let limit = (ledger_version - next_version + 1).min(MAX_TRANSACTIONS_PER_READ);
+			let transactions =
+				db_reader.get_transactions(next_version, limit, ledger_version, true)?;
+			for event in transactions.events.into_iter().flatten().flatten() {
+				let event = serde_json::to_value(&event)?;
+				if filter.as_ref().is_some_and(|filter| !matches_filter(&event, filter)) {
+					continue;
+				}
+				match sender.try_send(event) {
+					Ok(()) => {}
+					Err(TrySendError::Full(_)) => {
+						warn!("Event stream client is lagging, dropping event");
+					}
+					Err(TrySendError::Closed(_)) => return Ok(()),
+				}
+			}
+			next_version += limit;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_matches_filter() {
+		let event = json!({
+			"V2": {
+				"type_tag": "0x1::transaction_fee::FeeStatement",
+				"event_data": [1, 2, 3],
+			}
+		});
+
+		assert!(matches_filter(&event, &json!({})));
+		assert!(matches_filter(
+			&event,
+			&json!({ "V2": { "type_tag": "0x1::transaction_fee::FeeStatement" } })
+		));
+		assert!(!matches_filter(
+			&event,
+			&json!({ "V2": { "type_tag": "0x1::coin::DepositEvent" } })
+		));
+		assert!(!matches_filter(&event, &json!({ "V1": {} })));
+	}
+}
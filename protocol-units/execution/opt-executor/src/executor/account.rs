@@ -0,0 +1,338 @@
+use super::Executor;
+#[cfg(any(test, feature = "testing"))]
+use aptos_cached_packages::aptos_stdlib;
+#[cfg(any(test, feature = "testing"))]
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue};
+use aptos_sdk::move_types::language_storage::{StructTag, TypeTag};
+#[cfg(any(test, feature = "testing"))]
+use aptos_sdk::{
+	transaction_builder::TransactionFactory,
+	types::{AccountKey, LocalAccount},
+};
+#[cfg(any(test, feature = "testing"))]
+use aptos_storage_interface::state_view::LatestDbStateCheckpointView as _;
+use aptos_storage_interface::Order;
+use aptos_types::{
+	access_path::AccessPath, account_address::AccountAddress, account_config::CoinStoreResource,
+	state_store::state_key::StateKey, transaction::Version,
+};
+#[cfg(any(test, feature = "testing"))]
+use aptos_types::{
+	account_config::aptos_test_root_address,
+	transaction::{ExecutionStatus, Transaction, TransactionPayload, TransactionStatus},
+};
+#[cfg(any(test, feature = "testing"))]
+use aptos_vm_validator::vm_validator;
+use serde::Deserialize;
+
+#[cfg(any(test, feature = "testing"))]
+use anyhow::Context as _;
+use std::str::FromStr;
+
+const APTOS_COIN_TAG: &str = "0x1::aptos_coin::AptosCoin";
+
+/// The coin store event handle an event was emitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinEventType {
+	Deposit,
+	Withdrawal,
+}
+
+/// A deposit to, or a withdrawal from, the coin store of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoinEventEntry {
+	pub event_type: CoinEventType,
+	pub amount: u64,
+	pub version: Version,
+	/// The sequence number of the event in its event handle.
+	pub sequence_number: u64,
+}
+
+/// The data of both `0x1::coin::DepositEvent` and `0x1::coin::WithdrawEvent`.
+#[derive(Debug, Deserialize)]
+struct CoinEvent {
+	amount: u64,
+}
+
+/// Helpers committing transactions of the root account, for tests only.
+#[cfg(any(test, feature = "testing"))]
+impl Executor {
+	/// Gets the root account, which holds the private key of the chain config.
+	/// Its sequence number starts at 0, callers must set it if the account already transacted.
+	pub fn root_account(&self) -> LocalAccount {
+		LocalAccount::new(
+			aptos_test_root_address(),
+			AccountKey::from_private_key(self.config.chain.maptos_private_key.clone()),
+			0,
+		)
+	}
+
+	/// Creates an account funded with `initial_balance_octa` from the root account,
+	/// in a block of its own.
+	/// Returns the hash of the committed transaction.
+	pub async fn create_account(
+		&self,
+		new_address: AccountAddress,
+		initial_balance_octa: u64,
+	) -> Result<HashValue, anyhow::Error> {
+		let root_account = self.root_account();
+		// `aptos_account::transfer` creates the recipient account if it does not exist yet
+		self.transfer_coins(&root_account, new_address, initial_balance_octa)
+			.await
+			.with_context(|| format!("Failed to create account {}", new_address))
+	}
+
+	/// Creates `count` accounts with freshly generated keys in one block, then funds each with
+	/// `balance_per_account_octa` from the root account in a second block.
+	/// Returns the address and private key of each account.
+	pub async fn create_and_fund_test_accounts(
+		&self,
+		count: u32,
+		balance_per_account_octa: u64,
+	) -> Result<Vec<(AccountAddress, Ed25519PrivateKey)>, anyhow::Error> {
+		let root_account = self.root_account();
+		let accounts: Vec<LocalAccount> =
+			(0..count).map(|_| LocalAccount::generate(&mut rand::thread_rng())).collect();
+
+		let create_payloads = accounts
+			.iter()
+			.map(|account| aptos_stdlib::aptos_account_create_account(account.address()))
+			.collect();
+		self.execute_root_payloads(&root_account, create_payloads)
+			.await
+			.context("Failed to create the test accounts")?;
+		let fund_payloads = accounts
+			.iter()
+			.map(|account| {
+				aptos_stdlib::aptos_account_transfer(account.address(), balance_per_account_octa)
+			})
+			.collect();
+		self.execute_root_payloads(&root_account, fund_payloads)
+			.await
+			.context("Failed to fund the test accounts")?;
+
+		Ok(accounts
+			.into_iter()
+			.map(|account| (account.address(), account.private_key().clone()))
+			.collect())
+	}
+
+	/// Executes the payloads as transactions of the root account, in a block of their own,
+	/// and checks they all succeeded.
+	async fn execute_root_payloads(
+		&self,
+		root_account: &LocalAccount,
+		payloads: Vec<TransactionPayload>,
+	) -> Result<(), anyhow::Error> {
+		let state_view = self.db().reader.latest_state_checkpoint_view()?;
+		let sequence_number =
+			vm_validator::get_account_sequence_number(&state_view, root_account.address())?;
+		let tx_factory = TransactionFactory::new(self.config.chain.maptos_chain_id.clone());
+		let transactions = payloads
+			.into_iter()
+			.zip(sequence_number..)
+			.map(|(payload, sequence_number)| {
+				Transaction::UserTransaction(
+					root_account.sign_transaction(
+						tx_factory
+							.payload(payload)
+							.sender(root_account.address())
+							.sequence_number(sequence_number)
+							.build(),
+					),
+				)
+			})
+			.collect();
+
+		let parent_id = self.block_executor.committed_block_id();
+		let timestamp_usecs = chrono::Utc::now().timestamp_micros() as u64;
+		let (_, outputs) = self.propose_block(parent_id, timestamp_usecs, transactions).await?;
+		// the block metadata transaction comes first
+		for (index, output) in outputs.iter().enumerate().skip(1) {
+			if output.status() != &TransactionStatus::Keep(ExecutionStatus::Success) {
+				anyhow::bail!("Transaction {} of the block failed: {:?}", index, output.status());
+			}
+		}
+		Ok(())
+	}
+
+	/// Transfers APT from `sender` to `receiver`, in a block of its own.
+	/// The sender's sequence number is read from the ledger, the one tracked by `sender` is ignored.
+	/// Returns the hash of the committed transaction.
+	pub async fn transfer_coins(
+		&self,
+		sender: &LocalAccount,
+		receiver: AccountAddress,
+		amount_octa: u64,
+	) -> Result<HashValue, anyhow::Error> {
+		let state_view = self.db().reader.latest_state_checkpoint_view()?;
+		let sequence_number =
+			vm_validator::get_account_sequence_number(&state_view, sender.address())?;
+		let transaction = sender.sign_transaction(
+			TransactionFactory::new(self.config.chain.maptos_chain_id.clone())
+				.payload(aptos_stdlib::aptos_account_transfer(receiver, amount_octa))
+				.sender(sender.address())
+				.sequence_number(sequence_number)
+				.build(),
+		);
+		let transaction_hash = transaction.committed_hash();
+
+		let parent_id = self.block_executor.committed_block_id();
+		let timestamp_usecs = chrono::Utc::now().timestamp_micros() as u64;
+		let (_, outputs) = self
+			.propose_block(
+				parent_id,
+				timestamp_usecs,
+				vec![Transaction::UserTransaction(transaction)],
+			)
+			.await?;
+		// the block metadata transaction comes first
+		match outputs.get(1).map(|output| output.status()) {
+			Some(TransactionStatus::Keep(ExecutionStatus::Success)) => Ok(transaction_hash),
+			status => anyhow::bail!("Transfer to {} failed: {:?}", receiver, status),
+		}
+	}
+}
+
+impl Executor {
+	/// Gets the deposit and withdrawal events of the `CoinStore` of `coin_type` of an account,
+	/// APT if `None`, ordered by version.
+	/// `start` and `limit` page both event handles by sequence number, so a page holds up to
+	/// `limit` deposits and `limit` withdrawals.
+	/// Returns no events if the account has no coin store for the coin type.
+	pub fn get_coin_events(
+		&self,
+		address: AccountAddress,
+		coin_type: Option<TypeTag>,
+		start: u64,
+		limit: u64,
+	) -> Result<Vec<CoinEventEntry>, anyhow::Error> {
+		let version = self.version_or_latest(None)?;
+		let coin_type = match coin_type {
+			Some(coin_type) => coin_type,
+			None => TypeTag::Struct(Box::new(StructTag::from_str(APTOS_COIN_TAG)?)),
+		};
+		let store_tag = StructTag::from_str(&format!("0x1::coin::CoinStore<{}>", coin_type))?;
+		let state_key =
+			StateKey::access_path(AccessPath::resource_access_path(address, store_tag)?);
+		// the layout of the coin store does not depend on the coin type
+		let coin_store: CoinStoreResource = match self.get_state_value(&state_key, version)? {
+			Some(value) => bcs::from_bytes(value.bytes())?,
+			None => return Ok(Vec::new()),
+		};
+
+		let mut entries = Vec::new();
+		for (event_type, handle) in [
+			(CoinEventType::Deposit, coin_store.deposit_events()),
+			(CoinEventType::Withdrawal, coin_store.withdraw_events()),
+		] {
+			let events = self.db().reader.get_events(
+				handle.key(),
+				start,
+				Order::Ascending,
+				limit,
+				version,
+			)?;
+			for event in events {
+				entries.push(CoinEventEntry {
+					event_type,
+					amount: bcs::from_bytes::<CoinEvent>(event.event.event_data())?.amount,
+					version: event.transaction_version,
+					sequence_number: event.event.v1()?.sequence_number(),
+				});
+			}
+		}
+		entries.sort_by_key(|entry| entry.version);
+		Ok(entries)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+
+	#[tokio::test]
+	async fn test_create_account() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let new_address = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(new_address, 1_000).await?;
+		assert_eq!(executor.get_account_balance(new_address, None)?, 1_000);
+
+		// the root sequence number is looked up again for the next account
+		let other_address = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(other_address, 0).await?;
+		assert_eq!(executor.get_account_balance(other_address, None)?, 0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_create_and_fund_test_accounts() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let block_height = executor.get_block_head_height()?;
+
+		let accounts = executor.create_and_fund_test_accounts(3, 1_000).await?;
+		assert_eq!(accounts.len(), 3);
+		for (address, private_key) in &accounts {
+			assert_eq!(executor.get_account_balance(*address, None)?, 1_000);
+			let account_key = AccountKey::from_private_key(private_key.clone());
+			assert_eq!(account_key.authentication_key().account_address(), *address);
+		}
+		// one block creates the accounts, another funds them
+		assert_eq!(executor.get_block_head_height()?, block_height + 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_transfer_coins() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let sender = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 1_000_000_000).await?;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+
+		// the receiver account is created by the first transfer
+		executor.transfer_coins(&sender, receiver, 100).await?;
+		executor.transfer_coins(&sender, receiver, 200).await?;
+		assert_eq!(executor.get_account_balance(receiver, None)?, 300);
+		assert!(executor.get_account_balance(sender.address(), None)? < 1_000_000_000 - 300);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_coin_events() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let sender = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 1_000_000_000).await?;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.transfer_coins(&sender, receiver, 100).await?;
+
+		let events = executor.get_coin_events(sender.address(), None, 0, 10)?;
+		let summary: Vec<_> = events
+			.iter()
+			.map(|event| (event.event_type, event.amount, event.sequence_number))
+			.collect();
+		assert_eq!(
+			summary,
+			vec![(CoinEventType::Deposit, 1_000_000_000, 0), (CoinEventType::Withdrawal, 100, 0)]
+		);
+		assert!(events[0].version < events[1].version);
+
+		// pages past the end of both handles are empty
+		assert!(executor.get_coin_events(sender.address(), None, 1, 10)?.is_empty());
+		// accounts without a coin store have no events
+		let missing = LocalAccount::generate(&mut rand::thread_rng()).address();
+		assert!(executor.get_coin_events(missing, None, 0, 10)?.is_empty());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,104 @@
+use super::{Executor, ExecutorError};
+use aptos_crypto::HashValue;
+use aptos_sdk::move_types::language_storage::TypeTag;
+use aptos_types::{
+	contract_event::ContractEvent,
+	transaction::{ChangeSet, Version},
+	write_set::WriteSetMut,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// A rollup level telemetry event, committed to the ledger by [`Executor::emit_custom_event`].
+///
+/// The event is emitted with the `vector<u8>` type, holding the BCS encoding of this struct,
+/// so the API and the indexer can render it without a Move struct layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomEvent {
+	pub event_type: String,
+	/// The JSON encoding of the payload, as BCS cannot encode arbitrary JSON values.
+	pub payload: String,
+}
+
+impl CustomEvent {
+	/// Decodes a custom event from the data of a ledger event.
+	pub fn from_event_data(event_data: &[u8]) -> Result<Self, anyhow::Error> {
+		let bytes: Vec<u8> = bcs::from_bytes(event_data)?;
+		Ok(bcs::from_bytes(&bytes)?)
+	}
+}
+
+impl Executor {
+	/// Commits a custom event to the ledger, in a write set transaction of its own which
+	/// changes no state, and returns the version of that transaction.
+	/// Unlike [`Executor::apply_write_set`], this is not audited, as no state is changed.
+	/// Fails with [`ExecutorError::CustomEventsDisabled`] unless the chain configuration enables
+	/// custom events.
+	pub async fn emit_custom_event(
+		&self,
+		event_type: String,
+		payload: serde_json::Value,
+	) -> Result<Version, anyhow::Error> {
+		if !self.config.chain.maptos_custom_events_enabled {
+			return Err(ExecutorError::CustomEventsDisabled.into());
+		}
+		self.ensure_not_emergency_stopped()?;
+		if event_type.trim().is_empty() {
+			anyhow::bail!("A custom event type must be provided");
+		}
+
+		let event = CustomEvent { event_type, payload: payload.to_string() };
+		let event_data = bcs::to_bytes(&bcs::to_bytes(&event)?)?;
+		let contract_event =
+			ContractEvent::new_v2(TypeTag::Vector(Box::new(TypeTag::U8)), event_data);
+		let change_set = ChangeSet::new(WriteSetMut::new(vec![]).freeze()?, vec![contract_event]);
+		let (version, _) = self.apply_change_set(HashValue::random(), change_set).await?;
+		info!(event_type = %event.event_type, version, "emitted_custom_event");
+		Ok(version)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use maptos_execution_util::config::Config;
+
+	#[tokio::test]
+	async fn test_emit_custom_event_disabled() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let error = test_executor
+			.executor
+			.emit_custom_event("sequencer_lag".to_string(), serde_json::json!({ "blocks": 3 }))
+			.await
+			.unwrap_err();
+		assert!(matches!(
+			error.downcast_ref::<ExecutorError>(),
+			Some(ExecutorError::CustomEventsDisabled)
+		));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_emit_custom_event() -> Result<(), anyhow::Error> {
+		let (mut config, _db_path) = Config::for_testing()?;
+		config.chain.maptos_custom_events_enabled = true;
+		let executor = Executor::try_from_config(&config)?;
+		executor.rollover_genesis_now().await?;
+
+		let payload = serde_json::json!({ "blocks": 3, "lag_secs": 1.5 });
+		let version =
+			executor.emit_custom_event("sequencer_lag".to_string(), payload.clone()).await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let transaction =
+			executor.db_reader().get_transaction_by_version(version, ledger_version, true)?;
+		let events = transaction.events.unwrap_or_default();
+		assert_eq!(events.len(), 1);
+		let event = CustomEvent::from_event_data(events[0].event_data())?;
+		assert_eq!(event.event_type, "sequencer_lag");
+		assert_eq!(serde_json::from_str::<serde_json::Value>(&event.payload)?, payload);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,297 @@
+use super::Executor;
+use anyhow::Context;
+use aptos_crypto::HashValue;
+use aptos_executor_types::BlockExecutorTrait;
+use aptos_types::{
+	block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+	block_metadata::BlockMetadata,
+	transaction::{
+		signature_verified_transaction::into_signature_verified_block, SignedTransaction,
+		Transaction,
+	},
+};
+use m1_da_light_node_util::codec::BlobFormat;
+use m1_da_light_node_verifier::{VerificationMode, Verifier};
+use movement_types::block::{Block, BlockCommitment};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// The outcome of applying a DA blob with [`Executor::apply_da_blob`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyResult {
+	/// The block of the blob was executed and committed.
+	Committed {
+		block_height: u64,
+		/// The number of transactions of the block which were executed, replayed and
+		/// undecodable transactions excluded.
+		tx_count: u32,
+		/// The transaction accumulator root of the committed block.
+		state_root: HashValue,
+		/// The commitment of the block, to be settled.
+		commitment: BlockCommitment,
+	},
+	/// The blob was not applied, for the given reason.
+	Skipped(String),
+	/// The block is the latest committed block, or was when the executor was opened.
+	AlreadyApplied,
+}
+
+/// How the execution of a block read from the DA is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRetry {
+	/// The number of times the block is executed before giving up.
+	pub count: u64,
+	/// The amount by which the block timestamp is incremented on each retry.
+	pub increment_microseconds: u64,
+}
+
+/// How the blobs read from the DA are verified, decoded and executed by
+/// [`Executor::apply_da_blob`].
+#[derive(Clone)]
+pub struct DaIngestionConfig {
+	pub verifier: Arc<dyn Verifier + Send + Sync>,
+	/// The encoding of the blocks carried by the blobs.
+	pub blob_format: BlobFormat,
+	pub retry: BlockRetry,
+}
+
+impl Executor {
+	/// Sets how the blobs applied with [`Executor::apply_da_blob`] are ingested.
+	pub fn set_da_ingestion(&self, config: DaIngestionConfig) -> Result<(), anyhow::Error> {
+		*self
+			.da_ingestion
+			.write()
+			.map_err(|e| anyhow::anyhow!("DA ingestion config poisoned: {e}"))? = Some(config);
+		Ok(())
+	}
+
+	/// Verifies a blob read from the DA at `da_height`, decodes the block it carries and
+	/// executes and commits it with the given timestamp, as configured with
+	/// [`Executor::set_da_ingestion`].
+	///
+	/// Blobs which fail verification or cannot be decoded are skipped, as all nodes reject
+	/// them alike. Errors of the verifier itself are returned, so that a block is not skipped
+	/// by a node which could not reach the DA.
+	///
+	/// The transactions of the block which are already in the ledger or cannot be decoded are
+	/// not executed, a block only made of those is committed with its block metadata alone.
+	/// A block failing to execute is retried with later timestamps, as blocks timestamped too
+	/// closely to their parent fail to execute; nodes must retry alike to agree on the block
+	/// commitment.
+	pub async fn apply_da_blob(
+		&self,
+		blob: &[u8],
+		da_height: u64,
+		timestamp_usecs: u64,
+		mode: VerificationMode,
+	) -> Result<ApplyResult, anyhow::Error> {
+		let DaIngestionConfig { verifier, blob_format, retry } = self
+			.da_ingestion
+			.read()
+			.map_err(|e| anyhow::anyhow!("DA ingestion config poisoned: {e}"))?
+			.clone()
+			.context("DA ingestion is not configured")?;
+
+		if !verifier.verify(mode, blob, da_height).await? {
+			warn!(da_height, "DA blob failed verification, skipping it");
+			return Ok(ApplyResult::Skipped(format!(
+				"Blob at DA height {} failed {:?} verification",
+				da_height, mode
+			)));
+		}
+		let data = match verifier.decode_blob(blob) {
+			Ok(data) => data.to_vec(),
+			Err(e) => return Ok(ApplyResult::Skipped(format!("Invalid blob envelope: {}", e))),
+		};
+		let block =
+			match tokio::task::spawn_blocking(move || blob_format.codec().decode(&data)).await? {
+				Ok(block) => block,
+				Err(e) => return Ok(ApplyResult::Skipped(format!("Undecodable block: {}", e))),
+			};
+		self.apply_da_block(&block, timestamp_usecs, retry).await
+	}
+
+	async fn apply_da_block(
+		&self,
+		block: &Block,
+		mut timestamp_usecs: u64,
+		retry: BlockRetry,
+	) -> Result<ApplyResult, anyhow::Error> {
+		let block_id = block.id();
+		let block_hash = HashValue::from_slice(block_id.as_bytes())?;
+		// the block head when the executor was opened may have been committed without the
+		// caller recording it, before a crash
+		if self.block_executor.committed_block_id() == block_hash
+			|| self.opened_block_id == block_hash
+		{
+			return Ok(ApplyResult::AlreadyApplied);
+		}
+
+		// check if the transactions have already been executed to prevent replays
+		let mut new_transactions = Vec::new();
+		for transaction in block.transactions() {
+			let transaction: SignedTransaction = match serde_json::from_slice(transaction.data()) {
+				Ok(transaction) => transaction,
+				Err(e) => {
+					warn!(block_id = %block_id, "Skipping undecodable transaction: {}", e);
+					continue;
+				}
+			};
+			if !self.has_executed_transaction(transaction.committed_hash())? {
+				new_transactions.push(Transaction::UserTransaction(transaction));
+			}
+		}
+		let tx_count = u32::try_from(new_transactions.len())?;
+
+		for _ in 0..retry.count {
+			let (epoch, round) = self.get_next_epoch_and_round()?;
+			let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+				HashValue::sha3_256_of(block_id.as_bytes().as_slice()),
+				epoch,
+				round,
+				self.signer.author(),
+				vec![],
+				vec![],
+				timestamp_usecs,
+			));
+			let transactions = std::iter::once(block_metadata)
+				.chain(new_transactions.iter().cloned())
+				.collect();
+			let executable_block = ExecutableBlock::new(
+				block_hash,
+				ExecutableTransactions::Unsharded(into_signature_verified_block(transactions)),
+			);
+			let commitment = match self.execute_block(executable_block).await {
+				Ok(commitment) => commitment,
+				Err(e) => {
+					warn!("Failed to execute block: {:?}. Retrying", e);
+					timestamp_usecs += retry.increment_microseconds;
+					continue;
+				}
+			};
+
+			let state_root = self
+				.db()
+				.reader
+				.get_latest_ledger_info()?
+				.ledger_info()
+				.transaction_accumulator_hash();
+			info!(block_id = %block_id, height = commitment.height(), "Applied DA block");
+			return Ok(ApplyResult::Committed {
+				block_height: commitment.height(),
+				tx_count,
+				state_root,
+				commitment,
+			});
+		}
+
+		anyhow::bail!("Failed to execute block after {} retries", retry.count)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+	use movement_types::block::{BlockMetadata as DaBlockMetadata, Id};
+	use std::collections::BTreeSet;
+
+	/// A verifier with a fixed verdict.
+	struct StaticVerifier(bool);
+
+	#[tonic::async_trait]
+	impl Verifier for StaticVerifier {
+		async fn verify(
+			&self,
+			_verification_mode: VerificationMode,
+			_blob: &[u8],
+			_height: u64,
+		) -> Result<bool, anyhow::Error> {
+			Ok(self.0)
+		}
+
+		async fn verifiy_validator_in(
+			&self,
+			_verification_mode: VerificationMode,
+			_blob: &[u8],
+			_height: u64,
+		) -> Result<bool, anyhow::Error> {
+			Ok(self.0)
+		}
+
+		async fn verify_m_of_n(
+			&self,
+			_verification_mode: VerificationMode,
+			_blob: &[u8],
+			_height: u64,
+		) -> Result<bool, anyhow::Error> {
+			Ok(self.0)
+		}
+	}
+
+	#[tokio::test]
+	async fn test_apply_da_blob() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let root_account = test_executor.root_account();
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		let block = Block::new(
+			DaBlockMetadata::default(),
+			Id::test(),
+			BTreeSet::from([movement_types::transaction::Transaction::new(
+				serde_json::to_vec(&transaction)?,
+				transaction.sequence_number(),
+			)]),
+		);
+		let blob = BlobFormat::Bcs.codec().encode(&block)?;
+		let timestamp = chrono::Utc::now().timestamp_micros() as u64;
+		let mode = VerificationMode::Cowboy;
+		let ingestion = |verdict| DaIngestionConfig {
+			verifier: Arc::new(StaticVerifier(verdict)),
+			blob_format: BlobFormat::Bcs,
+			retry: BlockRetry { count: 2, increment_microseconds: 1 },
+		};
+
+		executor.set_da_ingestion(ingestion(false))?;
+		let result = executor.apply_da_blob(&blob, 2, timestamp, mode).await?;
+		assert!(matches!(result, ApplyResult::Skipped(_)));
+		executor.set_da_ingestion(ingestion(true))?;
+		let result = executor.apply_da_blob(&[1, 2, 3], 2, timestamp, mode).await?;
+		assert!(matches!(result, ApplyResult::Skipped(_)));
+
+		let result = executor.apply_da_blob(&blob, 2, timestamp, mode).await?;
+		let ledger_info = executor.db_reader().get_latest_ledger_info()?;
+		assert_eq!(
+			result,
+			ApplyResult::Committed {
+				block_height: executor.get_block_head_height()?,
+				tx_count: 1,
+				state_root: ledger_info.ledger_info().transaction_accumulator_hash(),
+				commitment: executor.get_block_head_commitment()?,
+			}
+		);
+		assert!(executor.has_executed_transaction(transaction.committed_hash())?);
+		let result = executor.apply_da_blob(&blob, 3, timestamp + 1, mode).await?;
+		assert_eq!(result, ApplyResult::AlreadyApplied);
+
+		// a block replaying the transaction, with an undecodable one, is committed with its
+		// block metadata alone, the timestamp of its parent being retried with a later one
+		let mut replay = Block::new(
+			DaBlockMetadata::default(),
+			Id::new([1; 32]),
+			block.transactions().cloned().collect(),
+		);
+		replay.add_transaction(movement_types::transaction::Transaction::new(vec![1, 2, 3], 0));
+		let blob = BlobFormat::Bcs.codec().encode(&replay)?;
+		let result = executor.apply_da_blob(&blob, 4, timestamp, mode).await?;
+		assert!(matches!(result, ApplyResult::Committed { tx_count: 0, .. }));
+		assert_eq!(executor.get_last_state_timestamp_micros()?, timestamp + 1);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,129 @@
+use super::Executor;
+use aptos_types::transaction::SignedTransaction;
+use thiserror::Error;
+
+/// The encoding of raw transaction bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionFormat {
+	/// Canonical BCS encoding, as carried by the mempool and the DA layer.
+	Bcs,
+	/// JSON encoding of the `SignedTransaction` serde representation.
+	Json,
+}
+
+/// Failure to decode raw transaction bytes.
+#[derive(Debug, Error)]
+#[error("failed to decode {format:?} transaction at byte offset {offset}: {message}")]
+pub struct DecodeError {
+	pub format: TransactionFormat,
+	/// Offset of the first byte the decoder could not accept.
+	pub offset: usize,
+	pub message: String,
+}
+
+impl Executor {
+	/// Decodes raw bytes into a [`SignedTransaction`].
+	pub fn decode_transaction(
+		bytes: &[u8],
+		format: TransactionFormat,
+	) -> Result<SignedTransaction, DecodeError> {
+		match format {
+			TransactionFormat::Bcs => bcs::from_bytes(bytes).map_err(|error| DecodeError {
+				format,
+				offset: bcs_error_offset(bytes),
+				message: error.to_string(),
+			}),
+			TransactionFormat::Json => serde_json::from_slice(bytes).map_err(|error| DecodeError {
+				format,
+				offset: json_error_offset(bytes, error.line(), error.column()),
+				message: error.to_string(),
+			}),
+		}
+	}
+}
+
+/// BCS errors carry no position, so the offset is located by decoding prefixes:
+/// a prefix shorter than the failure point runs out of input, while any longer
+/// prefix hits the same error (or decodes, if the failure is trailing input).
+fn bcs_error_offset(bytes: &[u8]) -> usize {
+	let truncated = |len: usize| {
+		matches!(bcs::from_bytes::<SignedTransaction>(&bytes[..len]), Err(bcs::Error::Eof))
+	};
+	let (mut low, mut high) = (0, bytes.len());
+	while low < high {
+		let mid = low + (high - low) / 2;
+		if truncated(mid) {
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+	if low == bytes.len() && truncated(low) {
+		return bytes.len();
+	}
+	match bcs::from_bytes::<SignedTransaction>(&bytes[..low]) {
+		// the transaction ends at `low`, the rest is unexpected trailing input
+		Ok(_) => low,
+		Err(_) => low.saturating_sub(1),
+	}
+}
+
+/// Converts serde_json's one-based line and column into a byte offset.
+fn json_error_offset(bytes: &[u8], line: usize, column: usize) -> usize {
+	let line_start: usize = bytes
+		.split_inclusive(|byte| *byte == b'\n')
+		.take(line.saturating_sub(1))
+		.map(<[u8]>::len)
+		.sum();
+	(line_start + column.saturating_sub(1)).min(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_types::{account_config, chain_id::ChainId, test_helpers::transaction_test_helpers};
+	use aptos_vm_genesis::GENESIS_KEYPAIR;
+
+	fn signed_transaction() -> SignedTransaction {
+		transaction_test_helpers::get_test_txn_with_chain_id(
+			account_config::aptos_test_root_address(),
+			0,
+			&GENESIS_KEYPAIR.0,
+			GENESIS_KEYPAIR.1.clone(),
+			ChainId::test(),
+		)
+	}
+
+	#[test]
+	fn test_decode_transaction_round_trips() -> Result<(), anyhow::Error> {
+		let transaction = signed_transaction();
+
+		let bcs_bytes = bcs::to_bytes(&transaction)?;
+		let decoded = Executor::decode_transaction(&bcs_bytes, TransactionFormat::Bcs)?;
+		assert_eq!(decoded, transaction);
+
+		let json_bytes = serde_json::to_vec(&transaction)?;
+		let decoded = Executor::decode_transaction(&json_bytes, TransactionFormat::Json)?;
+		assert_eq!(decoded, transaction);
+		Ok(())
+	}
+
+	#[test]
+	fn test_decode_transaction_reports_offset() -> Result<(), anyhow::Error> {
+		let bcs_bytes = bcs::to_bytes(&signed_transaction())?;
+
+		let error =
+			Executor::decode_transaction(&bcs_bytes[..10], TransactionFormat::Bcs).unwrap_err();
+		assert_eq!(error.offset, 10);
+
+		let mut trailing = bcs_bytes.clone();
+		trailing.push(0);
+		let error = Executor::decode_transaction(&trailing, TransactionFormat::Bcs).unwrap_err();
+		assert_eq!(error.offset, bcs_bytes.len());
+
+		let error = Executor::decode_transaction(b"{\n  \"raw_txn\": ?", TransactionFormat::Json)
+			.unwrap_err();
+		assert_eq!(error.offset, 15);
+		Ok(())
+	}
+}
@@ -0,0 +1,214 @@
+use super::Executor;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use aptos_sdk::{
+	move_types::{
+		identifier::Identifier,
+		language_storage::{ModuleId, TypeTag},
+	},
+	transaction_builder::TransactionFactory,
+};
+use aptos_storage_interface::state_view::LatestDbStateCheckpointView as _;
+use aptos_types::{
+	account_address::AccountAddress,
+	account_config::AccountResource,
+	contract_event::ContractEvent,
+	state_store::{
+		state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+		StateViewResult, TStateView,
+	},
+	transaction::{
+		authenticator::AuthenticationKey, EntryFunction, TransactionOutput, TransactionPayload,
+	},
+	vm_status::VMStatus,
+	write_set::WriteOp,
+};
+use aptos_vm::AptosSimulationVM;
+use aptos_vm_validator::vm_validator;
+
+/// The outcome of simulating a transaction, which is not committed.
+#[derive(Debug, Clone)]
+pub struct DryRunResult {
+	pub vm_status: VMStatus,
+	pub gas_used: u64,
+	/// The state changes the transaction would make.
+	pub changes: Vec<(StateKey, WriteOp)>,
+	pub events: Vec<ContractEvent>,
+}
+
+/// A state view where the sender's account holds the authentication key of a throwaway key,
+/// so that the prologue accepts a transaction simulated without the sender's key.
+struct DryRunStateView<S> {
+	base: S,
+	account_key: StateKey,
+	// `None` if the sender has no account, which the prologue rejects.
+	account_value: Option<StateValue>,
+}
+
+impl<S: TStateView<Key = StateKey>> TStateView for DryRunStateView<S> {
+	type Key = StateKey;
+
+	fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+		match &self.account_value {
+			Some(account_value) if state_key == &self.account_key => {
+				Ok(Some(account_value.clone()))
+			}
+			_ => self.base.get_state_value(state_key),
+		}
+	}
+
+	fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+		self.base.get_usage()
+	}
+}
+
+impl Executor {
+	/// Simulates calling an entry function as `sender` on the latest state checkpoint, with the
+	/// current gas unit price and at most `max_gas` gas units.
+	/// The sender's key is not needed, the transaction is signed with a throwaway key whose
+	/// authentication key is set on the sender's account for the simulation only.
+	/// `args` are the BCS encoded arguments of the function.
+	pub async fn dry_run_entry_function(
+		&self,
+		sender: AccountAddress,
+		module: ModuleId,
+		function: Identifier,
+		type_args: Vec<TypeTag>,
+		args: Vec<Vec<u8>>,
+		max_gas: u64,
+	) -> Result<DryRunResult, anyhow::Error> {
+		if max_gas == 0 {
+			anyhow::bail!("A dry run needs a non-zero max gas amount");
+		}
+		let gas_unit_price = self.get_current_gas_unit_price()?;
+		let chain_id = self.config.chain.maptos_chain_id;
+		let reader = self.db_reader();
+		tokio::task::spawn_blocking(move || {
+			let state_view = reader.latest_state_checkpoint_view()?;
+			let sequence_number = vm_validator::get_account_sequence_number(&state_view, sender)?;
+
+			let private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+			let public_key = private_key.public_key();
+			let raw_transaction = TransactionFactory::new(chain_id)
+				.payload(TransactionPayload::EntryFunction(EntryFunction::new(
+					module, function, type_args, args,
+				)))
+				.sender(sender)
+				.sequence_number(sequence_number)
+				.max_gas_amount(max_gas)
+				.gas_unit_price(gas_unit_price)
+				.build();
+			let transaction = raw_transaction.sign(&private_key, public_key.clone())?.into_inner();
+
+			let account_key = StateKey::resource_typed::<AccountResource>(&sender)?;
+			let authentication_key = AuthenticationKey::ed25519(&public_key);
+			let account_value = state_view
+				.get_state_value(&account_key)?
+				.map(|account| {
+					with_authentication_key(account.bytes(), &authentication_key)
+						.map(|bytes| StateValue::new_legacy(bytes.into()))
+				})
+				.transpose()?;
+			let dry_run_view = DryRunStateView { base: state_view, account_key, account_value };
+			let (vm_status, output) = AptosSimulationVM::create_vm_and_simulate_signed_transaction(
+				&transaction,
+				&dry_run_view,
+			);
+			Ok(dry_run_result(vm_status, output))
+		})
+		.await?
+	}
+}
+
+fn dry_run_result(vm_status: VMStatus, output: TransactionOutput) -> DryRunResult {
+	DryRunResult {
+		vm_status,
+		gas_used: output.gas_used(),
+		changes: output
+			.write_set()
+			.iter()
+			.map(|(state_key, write_op)| (state_key.clone(), write_op.clone()))
+			.collect(),
+		events: output.events().to_vec(),
+	}
+}
+
+/// Replaces the authentication key of a BCS encoded `account::Account`, its first field.
+fn with_authentication_key(
+	account: &[u8],
+	authentication_key: &AuthenticationKey,
+) -> Result<Vec<u8>, anyhow::Error> {
+	// the key is a 32 bytes vector, prefixed with its length as a single byte
+	let key_len = AuthenticationKey::LENGTH;
+	if account.first() != Some(&(key_len as u8)) || account.len() < 1 + key_len {
+		anyhow::bail!("Account resource does not start with an authentication key");
+	}
+	let mut patched = account.to_vec();
+	patched[1..1 + key_len].copy_from_slice(authentication_key.as_ref());
+	Ok(patched)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_sdk::{move_types::ident_str, types::LocalAccount};
+	use aptos_types::account_config::aptos_test_root_address;
+
+	#[test]
+	fn test_with_authentication_key() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let state_view = test_executor.executor.db_reader().latest_state_checkpoint_view()?;
+		let account_key = StateKey::resource_typed::<AccountResource>(&aptos_test_root_address())?;
+		let account = state_view.get_state_value(&account_key)?.expect("the root account exists");
+		let original: AccountResource = bcs::from_bytes(account.bytes())?;
+
+		let authentication_key = AuthenticationKey::new([2; 32]);
+		let patched = with_authentication_key(account.bytes(), &authentication_key)?;
+		let patched: AccountResource = bcs::from_bytes(&patched)?;
+		assert_eq!(patched.authentication_key(), authentication_key.as_ref());
+		assert_eq!(patched.sequence_number(), original.sequence_number());
+
+		assert!(with_authentication_key(&[0; 8], &authentication_key).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_dry_run_entry_function() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(receiver, 0).await?;
+
+		let module = ModuleId::new(AccountAddress::ONE, ident_str!("aptos_account").to_owned());
+		let result = executor
+			.dry_run_entry_function(
+				aptos_test_root_address(),
+				module.clone(),
+				ident_str!("transfer").to_owned(),
+				vec![],
+				vec![bcs::to_bytes(&receiver)?, bcs::to_bytes(&1_000u64)?],
+				100_000,
+			)
+			.await?;
+		assert_eq!(result.vm_status, VMStatus::Executed);
+		assert!(result.gas_used > 0);
+		assert!(!result.changes.is_empty());
+		assert!(!result.events.is_empty());
+		// nothing is committed
+		assert_eq!(executor.get_account_balance(receiver, None)?, 0);
+
+		let result = executor
+			.dry_run_entry_function(
+				aptos_test_root_address(),
+				module,
+				ident_str!("no_such_function").to_owned(),
+				vec![],
+				vec![],
+				100_000,
+			)
+			.await?;
+		assert_ne!(result.vm_status, VMStatus::Executed);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,78 @@
+use super::{Executor, ExecutorError};
+use std::sync::atomic::Ordering;
+use tracing::warn;
+
+impl Executor {
+	/// Halts block production until [`Executor::resume`] is called.
+	/// Blocks submitted in the meantime are rejected with [`ExecutorError::EmergencyStopped`].
+	pub fn emergency_stop(&self, reason: &str) -> Result<(), anyhow::Error> {
+		warn!(target: "movement_audit", reason, "emergency_stop");
+		self.emergency_stopped.store(true, Ordering::SeqCst);
+		Ok(())
+	}
+
+	/// Resumes block production after an emergency stop.
+	pub fn resume(&self, reason: &str) -> Result<(), anyhow::Error> {
+		warn!(target: "movement_audit", reason, "emergency_stop_resumed");
+		self.emergency_stopped.store(false, Ordering::SeqCst);
+		Ok(())
+	}
+
+	pub fn is_emergency_stopped(&self) -> bool {
+		self.emergency_stopped.load(Ordering::SeqCst)
+	}
+
+	pub(crate) fn ensure_not_emergency_stopped(&self) -> Result<(), ExecutorError> {
+		if self.is_emergency_stopped() {
+			return Err(ExecutorError::EmergencyStopped);
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_crypto::HashValue;
+	use aptos_types::{
+		block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+		block_metadata::BlockMetadata,
+		transaction::{signature_verified_transaction::into_signature_verified_block, Transaction},
+	};
+
+	fn metadata_block(executor: &Executor) -> Result<ExecutableBlock, anyhow::Error> {
+		let (epoch, round) = executor.get_next_epoch_and_round()?;
+		let block_id = HashValue::random();
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			executor.signer.author(),
+			vec![],
+			vec![],
+			chrono::Utc::now().timestamp_micros() as u64,
+		));
+		let transactions =
+			ExecutableTransactions::Unsharded(into_signature_verified_block(vec![block_metadata]));
+		Ok(ExecutableBlock::new(block_id, transactions))
+	}
+
+	#[tokio::test]
+	async fn test_emergency_stop_and_resume() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		executor.emergency_stop("test")?;
+		let err = executor
+			.execute_block(metadata_block(executor)?)
+			.await
+			.expect_err("block should be rejected while stopped");
+		assert!(matches!(err.downcast_ref(), Some(ExecutorError::EmergencyStopped)));
+
+		executor.resume("test")?;
+		executor.execute_block(metadata_block(executor)?).await?;
+
+		Ok(())
+	}
+}
@@ -1,5 +1,5 @@
 use super::Executor;
-use aptos_crypto::HashValue;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_types::transaction::signature_verified_transaction::into_signature_verified_block;
 use aptos_types::{
@@ -12,18 +12,50 @@ use aptos_types::{
 	block_metadata::BlockMetadata,
 	epoch_state::EpochState,
 	ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-	transaction::{Transaction, Version},
+	transaction::{
+		ChangeSet, ExecutionStatus, Transaction, TransactionInfo, TransactionOutput,
+		TransactionStatus, Version, WriteSetPayload,
+	},
 	validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
+	write_set::WriteSet,
 };
 use movement_types::block::{BlockCommitment, Commitment, Id};
-use tracing::{debug, info};
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// A block which was executed, but not committed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingProposalSummary {
+	pub block_id: HashValue,
+	pub parent_id: HashValue,
+	pub tx_count: u32,
+	/// The transaction accumulator root computed by the execution.
+	pub computed_state_root: HashValue,
+}
+
+/// A block committed by [`Executor::execute_block`], see [`Executor::subscribe_block_commits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCommitEvent {
+	pub height: u64,
+	/// The transaction accumulator root after the block.
+	pub accumulator_root: HashValue,
+	pub tx_count: u32,
+	/// The gas used by all the transactions of the block.
+	pub gas_used: u64,
+	pub timestamp_usecs: u64,
+}
 
 impl Executor {
 	pub async fn execute_block(
 		&self,
 		block: ExecutableBlock,
 	) -> Result<BlockCommitment, anyhow::Error> {
-		let (block_metadata, block) = {
+		self.ensure_not_emergency_stopped()?;
+		// only the blocks committed are timed, a failed execution records no latency
+		let started_at = Instant::now();
+
+		let (block_metadata, block, transaction_count) = {
 			// get the block metadata transaction
 			let metadata_access_block = block.transactions.clone();
 			let metadata_access_transactions = metadata_access_block.into_txns();
@@ -38,13 +70,14 @@ impl Executor {
 				}
 			};
 
+			let transaction_count = metadata_access_transactions.len();
 			// reconstruct the block
 			let block = ExecutableBlock::new(
 				block.block_id.clone(),
 				ExecutableTransactions::Unsharded(metadata_access_transactions),
 			);
 
-			(block_metadata, block)
+			(block_metadata, block, transaction_count)
 		};
 
 		let block_id = block.block_id.clone();
@@ -59,6 +92,15 @@ impl Executor {
 			)
 		})
 		.await??;
+		self.pending_proposals
+			.write()
+			.map_err(|e| anyhow::anyhow!("Pending proposals poisoned: {e}"))?
+			.push(PendingProposalSummary {
+				block_id,
+				parent_id: parent_block_id,
+				tx_count: u32::try_from(transaction_count)?,
+				computed_state_root: state_compute.root_hash(),
+			});
 
 		debug!("Block execution compute the following state: {:?}", state_compute);
 
@@ -79,16 +121,227 @@ impl Executor {
 			block_executor_clone.commit_blocks(vec![block_id], ledger_info_with_sigs)
 		})
 		.await??;
-
-		let proof = self.db().reader.get_state_proof(version)?;
+		// committing prunes the blocks which do not descend from the committed one
+		self.pending_proposals
+			.write()
+			.map_err(|e| anyhow::anyhow!("Pending proposals poisoned: {e}"))?
+			.retain(|proposal| proposal.parent_id == block_id);
+		self.metrics.db_writes.inc();
+		self.metrics.blocks_executed.inc();
+		self.metrics.transactions_per_block.observe(transaction_count as f64);
+		self.metrics.block_execution_latency.observe(started_at.elapsed().as_secs_f64());
 
 		// Context has a reach-around to the db so the block height should
 		// have been updated to the most recently committed block.
 		// Race conditions, anyone?
-		let block_height = self.get_block_head_height()?;
+		let commitment = self.get_block_head_commitment()?;
+
+		// the gas used is only summed up for subscribers
+		if self.block_commits.receiver_count() > 0 {
+			let (first_version, _, _) = self.db().reader.get_block_info_by_version(version)?;
+			let mut gas_used = 0;
+			for info in self
+				.db()
+				.reader
+				.get_transaction_info_iterator(first_version, version - first_version + 1)?
+			{
+				gas_used += info?.gas_used();
+			}
+			// sending only fails if the subscribers were dropped since
+			let _ = self.block_commits.send(BlockCommitEvent {
+				height: commitment.height(),
+				accumulator_root: state_compute.root_hash(),
+				tx_count: u32::try_from(transaction_count)?,
+				gas_used,
+				timestamp_usecs: block_metadata.timestamp_usecs(),
+			});
+		}
+
+		Ok(commitment)
+	}
 
+	/// Gets the commitment of the latest committed block, which is the one
+	/// [`Executor::execute_block`] returned when committing it.
+	pub fn get_block_head_commitment(&self) -> Result<BlockCommitment, anyhow::Error> {
+		let ledger_info = self.db().reader.get_latest_ledger_info()?;
+		let block_id = ledger_info.ledger_info().consensus_block_id();
+		let proof = self.db().reader.get_state_proof(ledger_info.ledger_info().version())?;
 		let commitment = Commitment::digest_state_proof(&proof);
-		Ok(BlockCommitment::new(block_height.into(), Id::new(*block_id.clone()), commitment))
+		Ok(BlockCommitment::new(self.get_block_head_height()?, Id::new(*block_id), commitment))
+	}
+
+	/// Subscribes to the blocks committed by [`Executor::execute_block`] from then on.
+	/// A subscriber falling behind by more than the configured channel capacity misses the
+	/// oldest blocks, and is told so by a `RecvError::Lagged`.
+	pub fn subscribe_block_commits(&self) -> broadcast::Receiver<BlockCommitEvent> {
+		self.block_commits.subscribe()
+	}
+
+	/// Gets the blocks which were executed by [`Executor::execute_block`] but not committed,
+	/// e.g. after a failed commit, in execution order.
+	pub fn get_pending_block_proposals(
+		&self,
+	) -> Result<Vec<PendingProposalSummary>, anyhow::Error> {
+		Ok(self
+			.pending_proposals
+			.read()
+			.map_err(|e| anyhow::anyhow!("Pending proposals poisoned: {e}"))?
+			.clone())
+	}
+
+	/// Computes the ID of a block proposed on top of `parent_id` with the given transactions,
+	/// as the hash of the parent ID followed by the hash of the transaction hashes.
+	pub fn proposal_block_id(parent_id: HashValue, transactions: &[Transaction]) -> HashValue {
+		let transaction_hashes: Vec<u8> = transactions
+			.iter()
+			.flat_map(|transaction| transaction.hash().to_vec())
+			.collect();
+		let transactions_hash = HashValue::sha3_256_of(&transaction_hashes);
+		HashValue::sha3_256_of(&[parent_id.to_vec(), transactions_hash.to_vec()].concat())
+	}
+
+	/// Proposes a block of the given transactions on top of the committed block `parent_id`.
+	/// The block metadata is generated with this executor's signer as the proposer and prepended,
+	/// then the block is executed and committed.
+	/// Returns the ID of the new block and the outputs of all of its transactions.
+	pub async fn propose_block(
+		&self,
+		parent_id: HashValue,
+		timestamp_usecs: u64,
+		transactions: Vec<Transaction>,
+	) -> Result<(HashValue, Vec<TransactionOutput>), anyhow::Error> {
+		self.ensure_not_emergency_stopped()?;
+
+		let committed_block_id = self.block_executor.committed_block_id();
+		if parent_id != committed_block_id {
+			anyhow::bail!(
+				"Proposed block must extend the committed block {}, not {}",
+				committed_block_id,
+				parent_id
+			);
+		}
+
+		let block_id = Self::proposal_block_id(parent_id, &transactions);
+		let (epoch, round) = self.get_next_epoch_and_round()?;
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			self.signer.author(),
+			vec![],
+			vec![],
+			timestamp_usecs,
+		));
+		let block_transactions = std::iter::once(block_metadata).chain(transactions).collect();
+		let first_version = self.db().reader.get_latest_ledger_info_version()? + 1;
+
+		let txs =
+			ExecutableTransactions::Unsharded(into_signature_verified_block(block_transactions));
+		self.execute_block(ExecutableBlock::new(block_id, txs)).await?;
+
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let outputs = self
+			.db()
+			.reader
+			.get_transaction_outputs(
+				first_version,
+				ledger_version - first_version + 1,
+				ledger_version,
+			)?
+			.transactions_and_outputs
+			.into_iter()
+			.map(|(_, output)| output)
+			.collect();
+		Ok((block_id, outputs))
+	}
+
+	/// Applies a raw write set to the ledger, bypassing normal transaction execution.
+	/// This is meant for governance emergencies and migrations only.
+	/// The `reason` is required and is recorded in the logs for auditing.
+	pub async fn apply_write_set(
+		&self,
+		write_set: WriteSet,
+		reason: String,
+	) -> Result<TransactionInfo, anyhow::Error> {
+		if reason.trim().is_empty() {
+			anyhow::bail!("A reason must be provided to apply a write set");
+		}
+		let block_id = HashValue::random();
+		warn!(target: "movement_audit", %block_id, reason = %reason, "applying_write_set");
+
+		let (version, transaction_info) =
+			self.apply_change_set(block_id, ChangeSet::new(write_set, vec![])).await?;
+		warn!(
+			target: "movement_audit",
+			%block_id,
+			version,
+			status = ?transaction_info.status(),
+			reason = %reason,
+			"applied_write_set",
+		);
+
+		Ok(transaction_info)
+	}
+
+	/// Commits a change set as a write set transaction in a block of its own.
+	/// Returns the version of the write set transaction and its info.
+	pub(super) async fn apply_change_set(
+		&self,
+		block_id: HashValue,
+		change_set: ChangeSet,
+	) -> Result<(Version, TransactionInfo), anyhow::Error> {
+		let (epoch, round) = self.get_next_epoch_and_round()?;
+		let timestamp = self.get_last_state_timestamp_micros()?;
+		debug!(%block_id, epoch, round, "applying_change_set");
+
+		// write set transactions are executed in a block of their own
+		let transaction = Transaction::GenesisTransaction(WriteSetPayload::Direct(change_set));
+		let block = ExecutableBlock::new(
+			block_id,
+			ExecutableTransactions::Unsharded(into_signature_verified_block(vec![transaction])),
+		);
+		let parent_block_id = self.block_executor.committed_block_id();
+		// the write set transaction comes first, the block's state checkpoint follows it
+		let version = self.db().reader.get_latest_ledger_info_version()? + 1;
+
+		let block_executor_clone = self.block_executor.clone();
+		let state_compute = tokio::task::spawn_blocking(move || {
+			block_executor_clone.execute_block(
+				block,
+				parent_block_id,
+				BlockExecutorConfigFromOnchain::new_no_block_limit(),
+			)
+		})
+		.await??;
+		// a discarded write set is not committed, the checkpoint alone would be
+		match state_compute.compute_status_for_input_txns().first() {
+			Some(TransactionStatus::Keep(ExecutionStatus::Success)) => {}
+			status => anyhow::bail!("Write set was not applied: {:?}", status),
+		}
+
+		let ledger_version = state_compute.version();
+		let ledger_info_with_sigs = self.ledger_info_with_sigs(
+			epoch,
+			round,
+			block_id,
+			timestamp,
+			state_compute.root_hash(),
+			ledger_version,
+		);
+		let block_executor_clone = self.block_executor.clone();
+		tokio::task::spawn_blocking(move || {
+			block_executor_clone.commit_blocks(vec![block_id], ledger_info_with_sigs)
+		})
+		.await??;
+
+		let transaction_info = self
+			.db()
+			.reader
+			.get_transaction_by_version(version, ledger_version, false)?
+			.proof
+			.transaction_info()
+			.clone();
+		Ok((version, transaction_info))
 	}
 
 	pub fn get_block_head_height(&self) -> Result<u64, anyhow::Error> {
@@ -119,6 +372,15 @@ impl Executor {
 		tokio::task::spawn_blocking(move || db_writer.revert_commit(&ledger_info)).await??;
 		// Reset the executor state to the reverted storage
 		self.block_executor.reset()?;
+		self.pending_proposals
+			.write()
+			.map_err(|e| anyhow::anyhow!("Pending proposals poisoned: {e}"))?
+			.clear();
+		// timestamps may now resolve to earlier versions
+		self.version_at_time_cache
+			.write()
+			.map_err(|e| anyhow::anyhow!("Version at time cache poisoned: {e}"))?
+			.clear();
 		Ok(())
 	}
 
@@ -175,6 +437,70 @@ impl Executor {
 		Ok(())
 	}
 
+	/// Advances to the next epoch by executing a block timestamped past the end of the current one.
+	/// Returns the new epoch.
+	/// This should only be used for testing, waiting for the epoch timer is impractical in tests.
+	#[cfg(any(test, feature = "testing"))]
+	pub async fn rotate_epoch(&self) -> Result<u64, anyhow::Error> {
+		let (epoch, round) = self.get_next_epoch_and_round()?;
+		let block_id = HashValue::random();
+		let timestamp = self.get_last_state_timestamp_micros()?
+			+ (crate::bootstrap::EPOCH_DURATION_SECS + 1) * 1_000_000;
+		info!("Rotating epoch: epoch: {}, round: {}, block_id: {}", epoch, round, block_id);
+
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			self.signer.author(),
+			vec![],
+			vec![],
+			timestamp,
+		));
+		let block = ExecutableBlock::new(
+			block_id,
+			ExecutableTransactions::Unsharded(into_signature_verified_block(vec![block_metadata])),
+		);
+		let parent_block_id = self.block_executor.committed_block_id();
+
+		let block_executor_clone = self.block_executor.clone();
+		let state_compute = tokio::task::spawn_blocking(move || {
+			block_executor_clone.execute_block(
+				block,
+				parent_block_id,
+				BlockExecutorConfigFromOnchain::new_no_block_limit(),
+			)
+		})
+		.await??;
+
+		// the reconfiguration computes the next epoch state, which must be committed with the block
+		let next_epoch_state = state_compute
+			.epoch_state()
+			.clone()
+			.ok_or(anyhow::anyhow!("Block did not trigger a reconfiguration"))?;
+		let new_epoch = next_epoch_state.epoch;
+		let block_info = BlockInfo::new(
+			epoch,
+			round,
+			block_id,
+			state_compute.root_hash(),
+			state_compute.version(),
+			timestamp,
+			Some(next_epoch_state),
+		);
+		let ledger_info_with_sigs = LedgerInfoWithSignatures::new(
+			LedgerInfo::new(block_info, HashValue::zero()),
+			AggregateSignature::empty(),
+		);
+		let block_executor_clone = self.block_executor.clone();
+		tokio::task::spawn_blocking(move || {
+			block_executor_clone.commit_blocks(vec![block_id], ledger_info_with_sigs)
+		})
+		.await??;
+
+		Ok(new_epoch)
+	}
+
 	pub fn ledger_info_with_sigs(
 		&self,
 		epoch: u64,
@@ -220,22 +546,24 @@ mod tests {
 		ed25519::{Ed25519PrivateKey, Ed25519Signature},
 		HashValue, PrivateKey, Uniform,
 	};
-	use aptos_sdk::{
-		transaction_builder::TransactionFactory,
-		types::{AccountKey, LocalAccount},
-	};
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
 	use aptos_storage_interface::state_view::DbStateViewAtVersion;
 	use aptos_types::{
 		account_address::AccountAddress,
-		account_config::{aptos_test_root_address, AccountResource},
+		account_config::AccountResource,
 		block_executor::partitioner::ExecutableTransactions,
 		block_metadata::BlockMetadata,
 		chain_id::ChainId,
-		state_store::MoveResourceExt,
+		on_chain_config,
+		state_store::{state_key::StateKey, MoveResourceExt},
 		transaction::signature_verified_transaction::{
 			into_signature_verified_block, SignatureVerifiedTransaction,
 		},
-		transaction::{RawTransaction, Script, SignedTransaction, Transaction, TransactionPayload},
+		transaction::{
+			ExecutionStatus, RawTransaction, Script, SignedTransaction, Transaction,
+			TransactionPayload,
+		},
+		write_set::{WriteOp, WriteSetMut},
 	};
 	use rand::SeedableRng;
 	use tokio::sync::mpsc;
@@ -280,7 +608,123 @@ mod tests {
 			tx,
 		]);
 		let block = ExecutableBlock::new(block_id.clone(), txs);
-		executor.execute_block(block).await?;
+
+		// a block failing to execute is not timed
+		let empty_block =
+			ExecutableBlock::new(HashValue::random(), ExecutableTransactions::Unsharded(vec![]));
+		assert!(executor.execute_block(empty_block).await.is_err());
+		assert_eq!(executor.metrics().block_execution_latency.get_sample_count(), 0);
+
+		let commitment = executor.execute_block(block).await?;
+		assert_eq!(executor.metrics().block_execution_latency.get_sample_count(), 1);
+		// the block was committed with its execution
+		assert!(executor.get_pending_block_proposals()?.is_empty());
+		assert_eq!(commitment.block_id(), &Id::new(*block_id));
+		assert_eq!(executor.get_block_head_commitment()?, commitment);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_subscribe_block_commits() -> Result<(), anyhow::Error> {
+		let test_executor = crate::TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let mut block_commits = executor.subscribe_block_commits();
+
+		let account = AccountAddress::random();
+		executor.create_account(account, 1_000).await?;
+		let event = block_commits.try_recv()?;
+		assert_eq!(event.height, executor.get_block_head_height()?);
+		assert_eq!(event.tx_count, 2);
+		let ledger_info = executor.db_reader().get_latest_ledger_info()?;
+		assert_eq!(
+			event.accumulator_root,
+			ledger_info.ledger_info().transaction_accumulator_hash()
+		);
+		// the block metadata transaction uses no gas, the transfer does
+		assert!(event.gas_used > 0);
+		assert!(block_commits.try_recv().is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rotate_epoch() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let (executor, _config, _tempdir) = Executor::try_test_default(private_key)?;
+		executor.rollover_genesis_now().await?;
+
+		let (epoch, _) = executor.get_next_epoch_and_round()?;
+		let new_epoch = executor.rotate_epoch().await?;
+		assert_eq!(new_epoch, epoch + 1);
+		assert_eq!(executor.get_next_epoch_and_round()?.0, new_epoch);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_propose_block() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let (executor, config, _tempdir) = Executor::try_test_default(private_key)?;
+		let root_account = executor.root_account();
+		let tx_factory = TransactionFactory::new(config.chain.maptos_chain_id);
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transactions =
+			vec![Transaction::UserTransaction(root_account.sign_with_transaction_builder(
+				tx_factory.create_user_account(new_account.public_key()),
+			))];
+
+		let parent_id = executor.block_executor.committed_block_id();
+		let expected_block_id = Executor::proposal_block_id(parent_id, &transactions);
+		let timestamp = chrono::Utc::now().timestamp_micros() as u64;
+		let (block_id, outputs) =
+			executor.propose_block(parent_id, timestamp, transactions.clone()).await?;
+
+		assert_eq!(block_id, expected_block_id);
+		assert_eq!(executor.block_executor.committed_block_id(), block_id);
+		// the block metadata and the user transaction, possibly followed by a state checkpoint
+		assert!(outputs.len() >= 2);
+		assert_eq!(outputs[1].status().status(), Ok(ExecutionStatus::Success));
+
+		// proposals must extend the committed block
+		assert!(executor.propose_block(parent_id, timestamp + 1, transactions).await.is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_apply_write_set() -> Result<(), anyhow::Error> {
+		let test_executor = crate::TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		assert!(executor.apply_write_set(WriteSet::default(), " ".to_string()).await.is_err());
+
+		let write_set = WriteSetMut::new(vec![(
+			StateKey::on_chain_config::<on_chain_config::Version>()?,
+			WriteOp::legacy_modification(
+				bcs::to_bytes(&on_chain_config::Version { major: 42 })?.into(),
+			),
+		)])
+		.freeze()?;
+		let transaction = Transaction::GenesisTransaction(WriteSetPayload::Direct(ChangeSet::new(
+			write_set.clone(),
+			vec![],
+		)));
+		let version = executor.db_reader().get_latest_ledger_info_version()? + 1;
+		let transaction_info =
+			executor.apply_write_set(write_set, "patch the version".to_string()).await?;
+
+		// the info is the write set transaction's, not the state checkpoint's which follows it
+		assert_eq!(transaction_info.transaction_hash(), transaction.hash());
+		assert_eq!(transaction_info.status(), &ExecutionStatus::Success);
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		assert!(ledger_version > version);
+		let committed =
+			executor
+				.db_reader()
+				.get_transaction_by_version(version, ledger_version, false)?;
+		assert_eq!(committed.proof.transaction_info(), &transaction_info);
+		let patched = executor.get_on_chain_config::<on_chain_config::Version>()?;
+		assert_eq!(patched.map(|version| version.major), Some(42));
+
 		Ok(())
 	}
 
@@ -299,11 +743,7 @@ mod tests {
 		executor.rollover_genesis_now().await?;
 
 		// Initialize a root account using a predefined keypair and the test root address.
-		let root_account = LocalAccount::new(
-			aptos_test_root_address(),
-			AccountKey::from_private_key(context.config().chain.maptos_private_key.clone()),
-			0,
-		);
+		let root_account = executor.root_account();
 
 		// Seed for random number generator, used here to generate predictable results in a test environment.
 		let seed = [3u8; 32];
@@ -402,11 +842,7 @@ mod tests {
 		executor.rollover_genesis_now().await?;
 
 		// Initialize a root account using a predefined keypair and the test root address.
-		let root_account = LocalAccount::new(
-			aptos_test_root_address(),
-			AccountKey::from_private_key(context.config().chain.maptos_private_key.clone()),
-			0,
-		);
+		let root_account = executor.root_account();
 
 		// Seed for random number generator, used here to generate predictable results in a test environment.
 		let seed = [3u8; 32];
@@ -0,0 +1,93 @@
+use super::Executor;
+use aptos_crypto::HashValue;
+use aptos_sdk::move_types::language_storage::StructTag;
+use aptos_types::{
+	access_path::AccessPath,
+	account_address::AccountAddress,
+	on_chain_config::{FeatureFlag, Features},
+	state_store::state_key::StateKey,
+};
+
+use anyhow::Context as _;
+use std::str::FromStr;
+
+/// The feature flags staged by governance for the next epoch.
+const PENDING_FEATURES_TAG: &str = "0x1::features::PendingFeatures";
+
+impl Executor {
+	/// Enables an on-chain feature flag, with the governance write set of
+	/// [`Executor::update_on_chain_config`] as `features::change_feature_flags` can only be called
+	/// by proposal scripts.
+	/// Returns the hash of the committed transaction updating the `features::Features` config.
+	pub async fn enable_feature_flag(&self, flag: FeatureFlag) -> Result<HashValue, anyhow::Error> {
+		self.set_feature_flag(flag, true).await
+	}
+
+	/// Disables an on-chain feature flag, as [`Executor::enable_feature_flag`] enables one.
+	/// Returns the hash of the committed transaction updating the `features::Features` config.
+	pub async fn disable_feature_flag(
+		&self,
+		flag: FeatureFlag,
+	) -> Result<HashValue, anyhow::Error> {
+		self.set_feature_flag(flag, false).await
+	}
+
+	/// Gets the feature flags enabled on chain.
+	/// Flags unknown to this version of the types are omitted.
+	pub fn get_enabled_features(&self) -> Result<Vec<FeatureFlag>, anyhow::Error> {
+		Ok(self.get_features()?.into_flag_vec())
+	}
+
+	fn get_features(&self) -> Result<Features, anyhow::Error> {
+		self.get_on_chain_config::<Features>()?.context("No features found on chain")
+	}
+
+	async fn set_feature_flag(
+		&self,
+		flag: FeatureFlag,
+		enabled: bool,
+	) -> Result<HashValue, anyhow::Error> {
+		// the flags staged by a governance proposal replace the config at the next epoch
+		let version = self.version_or_latest(None)?;
+		let pending_tag = StructTag::from_str(PENDING_FEATURES_TAG)?;
+		let pending_key = StateKey::access_path(AccessPath::resource_access_path(
+			AccountAddress::ONE,
+			pending_tag,
+		)?);
+		if self.get_state_value(&pending_key, version)?.is_some() {
+			anyhow::bail!("A feature flag change is already pending for the next epoch");
+		}
+
+		let mut features = self.get_features()?;
+		if enabled {
+			features.enable(flag);
+		} else {
+			features.disable(flag);
+		}
+
+		self.update_on_chain_config(features).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+
+	#[tokio::test]
+	async fn test_toggle_feature_flag() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let flag = FeatureFlag::CODE_DEPENDENCY_CHECK;
+
+		let hash = executor.disable_feature_flag(flag).await?;
+		assert!(!executor.get_enabled_features()?.contains(&flag));
+		let version = executor.db_reader().get_latest_ledger_info_version()?;
+		assert!(executor.db_reader().get_transaction_by_hash(hash, version, false)?.is_some());
+
+		executor.enable_feature_flag(flag).await?;
+		assert!(executor.get_enabled_features()?.contains(&flag));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,589 @@
+use super::Executor;
+use aptos_sdk::move_types::{
+	language_storage::{StructTag, TypeTag},
+	value::{MoveStruct, MoveTypeLayout, MoveValue},
+};
+use aptos_types::{
+	access_path::AccessPath, account_address::AccountAddress, state_store::state_key::StateKey,
+	transaction::Version,
+};
+use std::str::FromStr;
+
+const GOVERNANCE_FORUM_TAG: &str =
+	"0x1::voting::VotingForum<0x1::governance_proposal::GovernanceProposal>";
+const GOVERNANCE_PROPOSAL_TAG: &str =
+	"0x1::voting::Proposal<0x1::governance_proposal::GovernanceProposal>";
+const STAKE_POOL_TAG: &str = "0x1::stake::StakePool";
+const DELEGATION_POOL_OWNERSHIP_TAG: &str = "0x1::delegation_pool::DelegationPoolOwnership";
+const DELEGATION_POOL_TAG: &str = "0x1::delegation_pool::DelegationPool";
+const GOVERNANCE_RECORDS_TAG: &str = "0x1::delegation_pool::GovernanceRecords";
+const VOTE_DELEGATION_ENTRY_TAG: &str =
+	"0x1::smart_table::Entry<address, 0x1::delegation_pool::VoteDelegation>";
+const SHARES_POOL_TAG: &str = "0x1::pool_u64_unbound::Pool";
+
+/// The vote tally of an on-chain governance proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposalVoteInfo {
+	pub yes_votes: u128,
+	pub no_votes: u128,
+	pub is_resolved: bool,
+	/// The time the proposal was resolved at, in Unix seconds.
+	pub resolution_time_secs: Option<u64>,
+}
+
+/// The voter an account delegated the governance voting power of its stake to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelegationInfo {
+	pub delegatee: AccountAddress,
+	/// The active and pending inactive stake of the account, in octas.
+	pub delegated_power: u64,
+	/// The delegation pool the account delegated its stake to, if it does not own a stake pool.
+	pub delegation_pool: Option<AccountAddress>,
+}
+
+impl Executor {
+	/// Gets the vote tally of a governance proposal at the latest version, from the
+	/// `proposals` table of the `VotingForum` of `aptos_governance`.
+	/// The tally already sums all the votes cast, so the vote events are not replayed.
+	pub fn get_proposal_votes(&self, proposal_id: u64) -> Result<ProposalVoteInfo, anyhow::Error> {
+		let version = self.version_or_latest(None)?;
+		let forum_tag = StructTag::from_str(GOVERNANCE_FORUM_TAG)?;
+		let Some(forum) = self.get_move_resource(AccountAddress::ONE, &forum_tag, version)? else {
+			anyhow::bail!("No governance voting forum found");
+		};
+		let MoveValue::Struct(proposals) = struct_field(&forum, "proposals")? else {
+			anyhow::bail!("Voting forum proposals are not a table");
+		};
+		let MoveValue::Address(table_handle) = struct_field(proposals, "handle")? else {
+			anyhow::bail!("Voting forum proposals table has no handle");
+		};
+
+		let proposal_tag = StructTag::from_str(GOVERNANCE_PROPOSAL_TAG)?;
+		let value_type = TypeTag::Struct(Box::new(proposal_tag.clone()));
+		let raw_proposal = self
+			.get_table_item(
+				*table_handle,
+				bcs::to_bytes(&proposal_id)?,
+				&value_type,
+				Some(version),
+			)?
+			.ok_or_else(|| anyhow::anyhow!("Proposal {} not found", proposal_id))?;
+		let layout = self.get_move_type_info(&proposal_tag, Some(version))?;
+		let proposal = MoveStruct::simple_deserialize(&raw_proposal, &layout)?;
+
+		let (
+			MoveValue::U128(yes_votes),
+			MoveValue::U128(no_votes),
+			MoveValue::Bool(is_resolved),
+			MoveValue::U64(resolution_time_secs),
+		) = (
+			struct_field(&proposal, "yes_votes")?,
+			struct_field(&proposal, "no_votes")?,
+			struct_field(&proposal, "is_resolved")?,
+			struct_field(&proposal, "resolution_time_secs")?,
+		)
+		else {
+			anyhow::bail!("Proposal {} has an unexpected layout", proposal_id);
+		};
+		Ok(ProposalVoteInfo {
+			yes_votes: *yes_votes,
+			no_votes: *no_votes,
+			is_resolved: *is_resolved,
+			// the resolution time is zero until the proposal is resolved
+			resolution_time_secs: is_resolved.then_some(*resolution_time_secs),
+		})
+	}
+
+	/// Gets the voter the given account delegated its voting power to at the latest version.
+	///
+	/// If `delegation_pool` is given, the account is looked up as a delegator of that delegation
+	/// pool: its voter is the one recorded in the `delegation_pool::GovernanceRecords` of the
+	/// pool, and its power is the stake its shares are worth. Otherwise, the voter is the
+	/// `delegated_voter` of the `stake::StakePool` the account owns, or the delegator record of
+	/// the account in the delegation pool it owns through a
+	/// `delegation_pool::DelegationPoolOwnership`.
+	/// Returns `None` if the account owns no stake.
+	pub fn get_delegated_votes(
+		&self,
+		delegator: AccountAddress,
+		delegation_pool: Option<AccountAddress>,
+	) -> Result<Option<DelegationInfo>, anyhow::Error> {
+		let version = self.version_or_latest(None)?;
+		if let Some(pool_address) = delegation_pool {
+			return self.get_pool_delegated_votes(delegator, pool_address, version);
+		}
+
+		let stake_pool_tag = StructTag::from_str(STAKE_POOL_TAG)?;
+		if let Some(stake_pool) = self.get_move_resource(delegator, &stake_pool_tag, version)? {
+			let MoveValue::Address(delegatee) = struct_field(&stake_pool, "delegated_voter")?
+			else {
+				anyhow::bail!("Stake pool has no delegated voter");
+			};
+			let delegated_power = coin_value(struct_field(&stake_pool, "active")?)?
+				.checked_add(coin_value(struct_field(&stake_pool, "pending_inactive")?)?)
+				.ok_or_else(|| anyhow::anyhow!("Stake pool voting power overflows"))?;
+			return Ok(Some(DelegationInfo {
+				delegatee: *delegatee,
+				delegated_power,
+				delegation_pool: None,
+			}));
+		}
+
+		let ownership_tag = StructTag::from_str(DELEGATION_POOL_OWNERSHIP_TAG)?;
+		let Some(ownership) = self.get_move_resource(delegator, &ownership_tag, version)? else {
+			return Ok(None);
+		};
+		let MoveValue::Address(pool_address) = struct_field(&ownership, "pool_address")? else {
+			anyhow::bail!("Delegation pool ownership has no pool address");
+		};
+		self.get_pool_delegated_votes(delegator, *pool_address, version)
+	}
+
+	// Gets the delegation of a delegator of a delegation pool, see
+	// [`Executor::get_delegated_votes`].
+	fn get_pool_delegated_votes(
+		&self,
+		delegator: AccountAddress,
+		pool_address: AccountAddress,
+		version: Version,
+	) -> Result<Option<DelegationInfo>, anyhow::Error> {
+		let pool_tag = StructTag::from_str(DELEGATION_POOL_TAG)?;
+		let Some(pool) = self.get_move_resource(pool_address, &pool_tag, version)? else {
+			anyhow::bail!("No delegation pool found at {}", pool_address);
+		};
+		let stake_pool_tag = StructTag::from_str(STAKE_POOL_TAG)?;
+		let Some(stake_pool) = self.get_move_resource(pool_address, &stake_pool_tag, version)?
+		else {
+			anyhow::bail!("No stake pool found for delegation pool {}", pool_address);
+		};
+
+		// the pending inactive stake is held by the shares pool of the observed lockup cycle
+		let active =
+			self.shares_value(struct_field(&pool, "active_shares")?, delegator, version)?;
+		let MoveValue::Struct(observed_lockup_cycle) =
+			struct_field(&pool, "observed_lockup_cycle")?
+		else {
+			anyhow::bail!("Delegation pool has no observed lockup cycle");
+		};
+		let MoveValue::Struct(inactive_shares) = struct_field(&pool, "inactive_shares")? else {
+			anyhow::bail!("Delegation pool inactive shares are not a table");
+		};
+		let (MoveValue::U64(lockup_cycle), MoveValue::Address(inactive_shares_handle)) = (
+			struct_field(observed_lockup_cycle, "index")?,
+			struct_field(inactive_shares, "handle")?,
+		) else {
+			anyhow::bail!("Delegation pool has an unexpected layout");
+		};
+		let shares_pool_tag = StructTag::from_str(SHARES_POOL_TAG)?;
+		let pending_inactive = match self.get_table_item(
+			*inactive_shares_handle,
+			bcs::to_bytes(lockup_cycle)?,
+			&TypeTag::Struct(Box::new(shares_pool_tag.clone())),
+			Some(version),
+		)? {
+			Some(raw_pool) => {
+				let layout = self.get_move_type_info(&shares_pool_tag, Some(version))?;
+				let shares_pool =
+					MoveValue::simple_deserialize(&raw_pool, &MoveTypeLayout::Struct(layout))?;
+				self.shares_value(&shares_pool, delegator, version)?
+			}
+			None => 0,
+		};
+		if active == 0 && pending_inactive == 0 {
+			return Ok(None);
+		}
+		let delegated_power = active
+			.checked_add(pending_inactive)
+			.ok_or_else(|| anyhow::anyhow!("Delegator voting power overflows"))?;
+
+		// without partial governance voting, the whole pool votes with the voter of its stake pool
+		let governance_records_tag = StructTag::from_str(GOVERNANCE_RECORDS_TAG)?;
+		let delegatee =
+			match self.get_move_resource(pool_address, &governance_records_tag, version)? {
+				Some(governance_records) => {
+					let entry_tag = StructTag::from_str(VOTE_DELEGATION_ENTRY_TAG)?;
+					match self.get_smart_table_value(
+						struct_field(&governance_records, "vote_delegation")?,
+						&entry_tag,
+						&MoveValue::Address(delegator),
+						version,
+					)? {
+						Some(MoveValue::Struct(vote_delegation)) => {
+							current_voter(&vote_delegation, &stake_pool)?
+						}
+						Some(_) => anyhow::bail!("Vote delegation is not a struct"),
+						// delegators vote for themselves until they delegate
+						None => delegator,
+					}
+				}
+				None => match struct_field(&stake_pool, "delegated_voter")? {
+					MoveValue::Address(delegatee) => *delegatee,
+					_ => anyhow::bail!("Stake pool has no delegated voter"),
+				},
+			};
+
+		Ok(Some(DelegationInfo { delegatee, delegated_power, delegation_pool: Some(pool_address) }))
+	}
+
+	// Gets the stake the shares of `owner` are worth in a decoded `pool_u64_unbound::Pool`,
+	// rounded down as the pool does.
+	fn shares_value(
+		&self,
+		shares_pool: &MoveValue,
+		owner: AccountAddress,
+		version: Version,
+	) -> Result<u64, anyhow::Error> {
+		let MoveValue::Struct(shares_pool) = shares_pool else {
+			anyhow::bail!("Shares pool is not a struct");
+		};
+		let MoveValue::Struct(shares) = struct_field(shares_pool, "shares")? else {
+			anyhow::bail!("Shares pool shares are not a table");
+		};
+		let (
+			MoveValue::U64(total_coins),
+			MoveValue::U128(total_shares),
+			MoveValue::Address(handle),
+		) = (
+			struct_field(shares_pool, "total_coins")?,
+			struct_field(shares_pool, "total_shares")?,
+			struct_field(shares, "handle")?,
+		)
+		else {
+			anyhow::bail!("Shares pool has an unexpected layout");
+		};
+		let Some(raw_shares) =
+			self.get_table_item(*handle, bcs::to_bytes(&owner)?, &TypeTag::U128, Some(version))?
+		else {
+			return Ok(0);
+		};
+		let shares: u128 = Self::decode_table_value(&raw_shares)?;
+		if *total_shares == 0 {
+			return Ok(0);
+		}
+		let value = shares
+			.checked_mul(u128::from(*total_coins))
+			.ok_or_else(|| anyhow::anyhow!("Shares value overflows"))?
+			/ total_shares;
+		Ok(u64::try_from(value)?)
+	}
+
+	// Gets the value of `key` in a decoded `smart_table::SmartTable`, whose entries have the type
+	// `entry_tag`. The buckets are scanned, as the hash placing the key in its bucket is not
+	// computed here.
+	fn get_smart_table_value(
+		&self,
+		smart_table: &MoveValue,
+		entry_tag: &StructTag,
+		key: &MoveValue,
+		version: Version,
+	) -> Result<Option<MoveValue>, anyhow::Error> {
+		let MoveValue::Struct(smart_table) = smart_table else {
+			anyhow::bail!("Smart table is not a struct");
+		};
+		let MoveValue::Struct(buckets) = struct_field(smart_table, "buckets")? else {
+			anyhow::bail!("Smart table buckets are not a table");
+		};
+		let MoveValue::Struct(buckets_table) = struct_field(buckets, "inner")? else {
+			anyhow::bail!("Smart table buckets are not a table");
+		};
+		let (MoveValue::U64(num_buckets), MoveValue::Address(handle)) =
+			(struct_field(smart_table, "num_buckets")?, struct_field(buckets_table, "handle")?)
+		else {
+			anyhow::bail!("Smart table has an unexpected layout");
+		};
+
+		let bucket_type = TypeTag::Vector(Box::new(TypeTag::Struct(Box::new(entry_tag.clone()))));
+		let bucket_layout = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Struct(
+			self.get_move_type_info(entry_tag, Some(version))?,
+		)));
+		for bucket_index in 0..*num_buckets {
+			let Some(raw_bucket) = self.get_table_item(
+				*handle,
+				bcs::to_bytes(&bucket_index)?,
+				&bucket_type,
+				Some(version),
+			)?
+			else {
+				continue;
+			};
+			let MoveValue::Vector(entries) =
+				MoveValue::simple_deserialize(&raw_bucket, &bucket_layout)?
+			else {
+				anyhow::bail!("Smart table bucket is not a vector");
+			};
+			for entry in entries {
+				let MoveValue::Struct(entry) = entry else {
+					anyhow::bail!("Smart table entry is not a struct");
+				};
+				if struct_field(&entry, "key")? == key {
+					return Ok(Some(struct_field(&entry, "value")?.clone()));
+				}
+			}
+		}
+		Ok(None)
+	}
+
+	/// Gets a resource decoded with its field names, or `None` if it is absent.
+	pub(super) fn get_move_resource(
+		&self,
+		address: AccountAddress,
+		tag: &StructTag,
+		version: Version,
+	) -> Result<Option<MoveStruct>, anyhow::Error> {
+		let state_key =
+			StateKey::access_path(AccessPath::resource_access_path(address, tag.clone())?);
+		match self.get_state_value(&state_key, version)? {
+			Some(value) => {
+				let layout = self.get_move_type_info(tag, Some(version))?;
+				Ok(Some(MoveStruct::simple_deserialize(value.bytes(), &layout)?))
+			}
+			None => Ok(None),
+		}
+	}
+}
+
+/// Gets the voter of a decoded `delegation_pool::VoteDelegation`: its pending voter takes effect
+/// once the lockup of the stake pool was renewed since the delegation was recorded,
+/// as `delegation_pool` applies it.
+fn current_voter(
+	vote_delegation: &MoveStruct,
+	stake_pool: &MoveStruct,
+) -> Result<AccountAddress, anyhow::Error> {
+	let (
+		MoveValue::Address(voter),
+		MoveValue::Address(pending_voter),
+		MoveValue::U64(last_locked_until_secs),
+		MoveValue::U64(locked_until_secs),
+	) = (
+		struct_field(vote_delegation, "voter")?,
+		struct_field(vote_delegation, "pending_voter")?,
+		struct_field(vote_delegation, "last_locked_until_secs")?,
+		struct_field(stake_pool, "locked_until_secs")?,
+	)
+	else {
+		anyhow::bail!("Vote delegation has an unexpected layout");
+	};
+	Ok(if last_locked_until_secs < locked_until_secs { *pending_voter } else { *voter })
+}
+
+/// Gets the value of a decoded `0x1::coin::Coin`.
+fn coin_value(coin: &MoveValue) -> Result<u64, anyhow::Error> {
+	let MoveValue::Struct(coin) = coin else {
+		anyhow::bail!("Coin is not a struct");
+	};
+	match struct_field(coin, "value")? {
+		MoveValue::U64(value) => Ok(*value),
+		_ => anyhow::bail!("Coin value is not a u64"),
+	}
+}
+
+/// Gets a field of a struct decoded with its field names.
+pub(super) fn struct_field<'a>(
+	value: &'a MoveStruct,
+	name: &str,
+) -> Result<&'a MoveValue, anyhow::Error> {
+	let (MoveStruct::WithTypes { fields, .. } | MoveStruct::WithFields(fields)) = value else {
+		anyhow::bail!("Struct was decoded without field names");
+	};
+	fields
+		.iter()
+		.find(|(field_name, _)| field_name.as_str() == name)
+		.map(|(_, field)| field)
+		.ok_or_else(|| anyhow::anyhow!("Struct has no field {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_cached_packages::aptos_stdlib;
+	use aptos_sdk::{
+		transaction_builder::TransactionFactory,
+		types::{AccountKey, LocalAccount},
+	};
+	use aptos_types::transaction::{ExecutionStatus, TransactionPayload};
+
+	const STAKE_OCTA: u64 = 100_000_000;
+
+	/// Executes the payload as a transaction of `account`, in a block of its own,
+	/// and checks it succeeded.
+	async fn execute(
+		test_executor: &TestExecutor,
+		account: &mut LocalAccount,
+		payload: TransactionPayload,
+	) -> Result<(), anyhow::Error> {
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let transaction = account.sign_with_transaction_builder(tx_factory.payload(payload));
+		let output = test_executor.submit_and_execute(transaction).await?;
+		assert_eq!(output.status().status(), Ok(ExecutionStatus::Success));
+		Ok(())
+	}
+
+	/// Creates an account staking [`STAKE_OCTA`] in a stake pool of its own, which it votes
+	/// with, locked up past the end of the voting period of new proposals.
+	async fn create_staker(test_executor: &TestExecutor) -> Result<LocalAccount, anyhow::Error> {
+		let (address, private_key) = test_executor
+			.executor
+			.create_and_fund_test_accounts(1, 10 * STAKE_OCTA)
+			.await?
+			.remove(0);
+		let mut staker = LocalAccount::new(address, AccountKey::from_private_key(private_key), 0);
+		let payload = aptos_stdlib::stake_initialize_stake_owner(STAKE_OCTA, address, address);
+		execute(test_executor, &mut staker, payload).await?;
+		execute(test_executor, &mut staker, aptos_stdlib::stake_increase_lockup()).await?;
+		Ok(staker)
+	}
+
+	#[test]
+	fn test_get_missing_proposal_votes() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		// the voting forum is registered at genesis, without any proposal
+		let error = test_executor.executor.get_proposal_votes(0).unwrap_err();
+		assert_eq!(error.to_string(), "Proposal 0 not found");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_proposal_votes() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let mut staker = create_staker(&test_executor).await?;
+		let pool_address = staker.address();
+
+		let payload = aptos_stdlib::aptos_governance_create_proposal_v2(
+			pool_address,
+			vec![1; 32],
+			b"https://example.com/proposal.json".to_vec(),
+			vec![2; 32],
+			false,
+		);
+		execute(&test_executor, &mut staker, payload).await?;
+		assert_eq!(
+			executor.get_proposal_votes(0)?,
+			ProposalVoteInfo {
+				yes_votes: 0,
+				no_votes: 0,
+				is_resolved: false,
+				resolution_time_secs: None
+			}
+		);
+
+		let payload = aptos_stdlib::aptos_governance_vote(pool_address, 0, true);
+		execute(&test_executor, &mut staker, payload).await?;
+		// the stake is far from the early resolution threshold, so the proposal stays open
+		assert_eq!(
+			executor.get_proposal_votes(0)?,
+			ProposalVoteInfo {
+				yes_votes: STAKE_OCTA.into(),
+				no_votes: 0,
+				is_resolved: false,
+				resolution_time_secs: None
+			}
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_delegated_votes_without_stake() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let account = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(account, 1_000).await?;
+
+		// an account owning coins but no stake has no voting power to delegate
+		assert_eq!(executor.get_delegated_votes(account, None)?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_delegated_votes_of_stake_owner() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let mut staker = create_staker(&test_executor).await?;
+		let voter = LocalAccount::generate(&mut rand::thread_rng()).address();
+
+		let payload = aptos_stdlib::stake_set_delegated_voter(voter);
+		execute(&test_executor, &mut staker, payload).await?;
+		assert_eq!(
+			executor.get_delegated_votes(staker.address(), None)?,
+			Some(DelegationInfo {
+				delegatee: voter,
+				delegated_power: STAKE_OCTA,
+				delegation_pool: None
+			})
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_delegated_votes_of_pool_delegator() -> Result<(), anyhow::Error> {
+		// the minimum stake a delegator can add to a delegation pool
+		const DELEGATED_OCTA: u64 = 10 * STAKE_OCTA;
+
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let mut accounts = executor
+			.create_and_fund_test_accounts(2, 2 * DELEGATED_OCTA)
+			.await?
+			.into_iter()
+			.map(|(address, private_key)| {
+				LocalAccount::new(address, AccountKey::from_private_key(private_key), 0)
+			});
+		let (mut owner, mut delegator) = (accounts.next().unwrap(), accounts.next().unwrap());
+
+		let payload = aptos_stdlib::delegation_pool_initialize_delegation_pool(0, b"pool".to_vec());
+		execute(&test_executor, &mut owner, payload).await?;
+		let version = executor.version_or_latest(None)?;
+		let ownership_tag = StructTag::from_str(DELEGATION_POOL_OWNERSHIP_TAG)?;
+		let ownership = executor
+			.get_move_resource(owner.address(), &ownership_tag, version)?
+			.expect("the owner owns a delegation pool");
+		let MoveValue::Address(pool_address) = *struct_field(&ownership, "pool_address")? else {
+			panic!("the ownership has no pool address");
+		};
+
+		// the owner has no stake of its own in the pool
+		assert_eq!(executor.get_delegated_votes(owner.address(), None)?, None);
+		assert_eq!(executor.get_delegated_votes(delegator.address(), Some(pool_address))?, None);
+
+		let payload = aptos_stdlib::delegation_pool_add_stake(pool_address, DELEGATED_OCTA);
+		execute(&test_executor, &mut delegator, payload).await?;
+		let expected_delegation = DelegationInfo {
+			delegatee: delegator.address(),
+			delegated_power: DELEGATED_OCTA,
+			delegation_pool: Some(pool_address),
+		};
+		assert_eq!(
+			executor.get_delegated_votes(delegator.address(), Some(pool_address))?,
+			Some(expected_delegation)
+		);
+
+		// the governance records of the pool count the voters in smart tables
+		let governance_records_tag = StructTag::from_str(GOVERNANCE_RECORDS_TAG)?;
+		let table_length = |field: &str| {
+			executor.get_smart_table_length(pool_address, &governance_records_tag, field, None)
+		};
+		let vote_delegations = table_length("vote_delegation")?;
+		let delegated_votes = table_length("delegated_votes")?;
+		assert!(vote_delegations >= 1);
+
+		// the new voter only takes effect once the lockup of the pool is renewed
+		let voter = LocalAccount::generate(&mut rand::thread_rng()).address();
+		let payload = aptos_stdlib::delegation_pool_delegate_voting_power(pool_address, voter);
+		execute(&test_executor, &mut delegator, payload).await?;
+		assert_eq!(
+			executor.get_delegated_votes(delegator.address(), Some(pool_address))?,
+			Some(expected_delegation)
+		);
+		// the votes delegated to the new voter are recorded, the delegator's voter is updated
+		assert_eq!(table_length("delegated_votes")?, delegated_votes + 1);
+		assert_eq!(table_length("vote_delegation")?, vote_delegations);
+
+		Ok(())
+	}
+}
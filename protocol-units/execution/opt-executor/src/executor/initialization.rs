@@ -1,5 +1,8 @@
 use super::Executor;
-use crate::{bootstrap, Context, TransactionPipe};
+use crate::{
+	bootstrap::{self, GenesisConfigurationExt as _},
+	Context, ExecutorMetrics, TransactionPipe,
+};
 
 use aptos_config::config::NodeConfig;
 #[cfg(test)]
@@ -12,13 +15,18 @@ use maptos_execution_util::config::Config;
 
 use anyhow::Context as _;
 use futures::channel::mpsc as futures_mpsc;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 #[cfg(test)]
 use tempfile::TempDir;
 
+use std::collections::VecDeque;
 use std::net::ToSocketAddrs;
-use std::sync::{atomic::AtomicU64, Arc};
+use std::sync::{
+	atomic::{AtomicBool, AtomicU64},
+	Arc, RwLock,
+};
+use std::time::Duration;
 
 // Executor channel size.
 // Allow 2^16 transactions before appling backpressure given theoretical maximum TPS of 170k.
@@ -26,15 +34,40 @@ const EXECUTOR_CHANNEL_SIZE: usize = 2_usize.pow(16);
 
 impl Executor {
 	pub fn bootstrap(maptos_config: &Config) -> Result<Self, anyhow::Error> {
+		let mut genesis_config = bootstrap::genesis_configuration();
+		if let Some(path) = &maptos_config.chain.maptos_genesis_features_path {
+			genesis_config = genesis_config.with_features_from_toml_file(path)?;
+		}
 		let (db, signer) = bootstrap::maybe_bootstrap_empty_db(
 			maptos_config.chain.maptos_db_path.as_ref().context("No db path provided.")?,
 			maptos_config.chain.maptos_chain_id.clone(),
 			&maptos_config.chain.maptos_private_key.public_key(),
+			&maptos_config.rocksdb,
+			&genesis_config,
 		)?;
+		let capacity = maptos_config.chain.maptos_block_commit_channel_capacity;
+		if capacity == 0 {
+			anyhow::bail!("Block commit channel capacity must not be 0");
+		}
+		let transactions_in_flight = Arc::new(AtomicU64::new(0));
+		let metrics = Arc::new(ExecutorMetrics::new(Arc::clone(&transactions_in_flight))?);
+		let opened_block_id =
+			db.reader.get_latest_ledger_info()?.ledger_info().consensus_block_id();
 		Ok(Self {
 			block_executor: Arc::new(BlockExecutor::new(db.clone())),
 			signer,
-			transactions_in_flight: Arc::new(AtomicU64::new(0)),
+			transactions_in_flight,
+			emergency_stopped: Arc::new(AtomicBool::new(false)),
+			#[cfg(any(test, feature = "testing"))]
+			mempool_resets: Arc::new(AtomicU64::new(0)),
+			protocol_version: Arc::new(RwLock::new(None)),
+			gas_unit_price: Arc::new(RwLock::new(None)),
+			version_at_time_cache: Arc::new(RwLock::new(VecDeque::new())),
+			pending_proposals: Arc::new(RwLock::new(Vec::new())),
+			block_commits: broadcast::channel(capacity).0,
+			da_ingestion: Arc::new(RwLock::new(None)),
+			opened_block_id,
+			metrics,
 			config: maptos_config.clone(),
 		})
 	}
@@ -102,6 +135,8 @@ impl Executor {
 		node_config.storage.dir = "./.movement/maptos-storage".to_string().into();
 		node_config.storage.set_data_dir(node_config.storage.dir.clone());
 
+		node_config.mempool.capacity = maptos_config.load_shedding.mempool_capacity;
+
 		// use the default signer, block executor, and mempool
 		let (mempool_client_sender, mempool_client_receiver) =
 			futures_mpsc::channel::<MempoolClientRequest>(EXECUTOR_CHANNEL_SIZE);
@@ -112,13 +147,17 @@ impl Executor {
 			&node_config,
 			Arc::clone(&self.transactions_in_flight),
 			maptos_config.load_shedding.max_transactions_in_flight,
+			Duration::from_millis(maptos_config.load_shedding.mempool_gc_interval_ms),
 		);
+		#[cfg(any(test, feature = "testing"))]
+		let transaction_pipe = transaction_pipe.with_mempool_resets(Arc::clone(&self.mempool_resets));
 
 		let cx = Context::new(
 			self.db().clone(),
 			mempool_client_sender,
 			maptos_config.clone(),
 			node_config,
+			self.metrics(),
 		);
 
 		Ok((cx, transaction_pipe))
@@ -0,0 +1,962 @@
+use super::Executor;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_sdk::move_types::{language_storage::TypeTag, move_resource::MoveStructType};
+use aptos_types::{
+	account_address::AccountAddress,
+	aggregate_signature::AggregateSignature,
+	contract_event::ContractEvent,
+	epoch_change::EpochChangeProof,
+	fee_statement::FeeStatement,
+	ledger_info::LedgerInfoWithSignatures,
+	proof::AccumulatorConsistencyProof,
+	transaction::{
+		authenticator::TransactionAuthenticator, Transaction, TransactionWithProof, Version,
+	},
+	trusted_state::{TrustedState, TrustedStateChange},
+	validator_verifier::ValidatorVerifier,
+};
+
+use anyhow::Context as _;
+use std::collections::HashMap;
+
+// Number of timestamps remembered by `Executor::get_version_at_time`.
+const VERSION_AT_TIME_CACHE_SIZE: usize = 128;
+
+/// A summary of a committed block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+	pub height: u64,
+	/// The version of the block metadata transaction.
+	pub first_version: Version,
+	/// The version of the last transaction of the block, inclusive.
+	pub last_version: Version,
+	pub epoch: u64,
+	pub round: u64,
+	pub timestamp_usecs: u64,
+	pub tx_count: u32,
+}
+
+/// The outcome of checking committed transactions against the transaction accumulator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyReport {
+	pub versions_checked: u64,
+	/// The versions whose transaction or transaction info is not committed to by the accumulator.
+	pub inconsistencies: Vec<Version>,
+}
+
+/// The sponsored transactions committed in a range of versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SponsoredTxnStats {
+	pub total_count: u64,
+	pub total_fees_octa: u64,
+	/// The fees paid by each fee payer, highest first.
+	pub top_fee_payers: Vec<(AccountAddress, u64)>,
+}
+
+/// The gas used by the transactions of a committed block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockGasSummary {
+	pub total_gas_used: u64,
+	/// The gas used per transaction of the block, including the gas free block metadata.
+	pub average_gas_per_txn: f64,
+	/// The version of the transaction which used the most gas, the first one on ties.
+	/// `None` if no transaction of the block used gas.
+	pub max_gas_txn_version: Option<Version>,
+	/// The fees paid by the user transactions, their gas used times their gas unit price.
+	pub total_fees_octa: u64,
+}
+
+/// The start of an epoch, as recorded by the ledger info ending the previous one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochTransition {
+	pub new_epoch: u64,
+	/// The version of the transaction which triggered the reconfiguration.
+	pub trigger_version: Version,
+	pub timestamp_usecs: u64,
+	pub new_validator_set: ValidatorVerifier,
+}
+
+impl Executor {
+	/// Gets the root hash of the transaction accumulator at the given version.
+	pub fn get_transaction_accumulator_hash(
+		&self,
+		version: Version,
+	) -> Result<HashValue, anyhow::Error> {
+		Ok(self.db().reader.get_accumulator_root_hash(version)?)
+	}
+
+	/// Gets a proof that the accumulator at version `to` extends the one at version `from`.
+	pub fn get_accumulator_proof_for_version(
+		&self,
+		from: Version,
+		to: Version,
+	) -> Result<AccumulatorConsistencyProof, anyhow::Error> {
+		if from > to {
+			anyhow::bail!("Version {} is ahead of version {}", from, to);
+		}
+		Ok(self.db().reader.get_accumulator_consistency_proof(Some(from), to)?)
+	}
+
+	/// Gets the consistency proof a light client needs to advance its trusted accumulator
+	/// from `client_known_version` to `server_version`.
+	/// `client_known_version` must be less than or equal to `server_version`.
+	pub fn get_accumulator_consistency_proof(
+		&self,
+		client_known_version: Version,
+		server_version: Version,
+	) -> Result<AccumulatorConsistencyProof, anyhow::Error> {
+		self.get_accumulator_proof_for_version(client_known_version, server_version)
+	}
+
+	/// Checks that the transactions from `start_version` to `end_version` inclusive hash to
+	/// their stored transaction infos, and that the accumulator of the latest ledger info
+	/// proves each of these transaction infos.
+	pub async fn verify_ledger_consistency(
+		&self,
+		start_version: Version,
+		end_version: Version,
+	) -> Result<ConsistencyReport, anyhow::Error> {
+		if start_version > end_version {
+			anyhow::bail!("Version {} is ahead of version {}", start_version, end_version);
+		}
+		let reader = self.db_reader();
+		tokio::task::spawn_blocking(move || {
+			let ledger_info = reader.get_latest_ledger_info()?;
+			let ledger_version = ledger_info.ledger_info().version();
+			if end_version > ledger_version {
+				anyhow::bail!(
+					"Version {} is ahead of the ledger version {}",
+					end_version,
+					ledger_version
+				);
+			}
+
+			let mut inconsistencies = Vec::new();
+			for version in start_version..=end_version {
+				let transaction =
+					reader.get_transaction_by_version(version, ledger_version, false)?;
+				let transaction_info = transaction.proof.transaction_info();
+				let consistent = transaction_info.transaction_hash()
+					== transaction.transaction.hash()
+					&& transaction.proof.verify(ledger_info.ledger_info(), version).is_ok();
+				if !consistent {
+					inconsistencies.push(version);
+				}
+			}
+			Ok(ConsistencyReport {
+				versions_checked: end_version - start_version + 1,
+				inconsistencies,
+			})
+		})
+		.await?
+	}
+
+	/// Gets the epoch ending ledger infos from `start_epoch` up to, but excluding, `end_epoch`.
+	pub fn get_epoch_change_proof(
+		&self,
+		start_epoch: u64,
+		end_epoch: u64,
+	) -> Result<EpochChangeProof, anyhow::Error> {
+		if start_epoch > end_epoch {
+			anyhow::bail!("Epoch {} is ahead of epoch {}", start_epoch, end_epoch);
+		}
+		Ok(self.db().reader.get_epoch_ending_ledger_infos(start_epoch, end_epoch)?)
+	}
+
+	/// Gets the epoch transitions triggered by transactions from `start_version` to
+	/// `end_version` inclusive, in order.
+	/// Genesis triggers the transition to epoch 1 at version 0.
+	pub fn get_epoch_transitions(
+		&self,
+		start_version: Version,
+		end_version: Version,
+	) -> Result<Vec<EpochTransition>, anyhow::Error> {
+		if start_version > end_version {
+			anyhow::bail!("Version {} is ahead of version {}", start_version, end_version);
+		}
+		let current_epoch =
+			self.db().reader.get_latest_ledger_info()?.ledger_info().next_block_epoch();
+
+		let mut transitions = Vec::new();
+		let mut epoch = 0;
+		// the reader caps the number of ledger infos returned at once
+		while epoch < current_epoch {
+			let proof = self.get_epoch_change_proof(epoch, current_epoch)?;
+			if proof.ledger_info_with_sigs.is_empty() {
+				break;
+			}
+			epoch += proof.ledger_info_with_sigs.len() as u64;
+			for ledger_info_with_sigs in proof.ledger_info_with_sigs {
+				let ledger_info = ledger_info_with_sigs.ledger_info();
+				if !(start_version..=end_version).contains(&ledger_info.version()) {
+					continue;
+				}
+				let Some(next_epoch_state) = ledger_info.next_epoch_state() else {
+					anyhow::bail!(
+						"Epoch ending ledger info at version {} has no next epoch state",
+						ledger_info.version()
+					);
+				};
+				transitions.push(EpochTransition {
+					new_epoch: next_epoch_state.epoch,
+					trigger_version: ledger_info.version(),
+					timestamp_usecs: ledger_info.timestamp_usecs(),
+					new_validator_set: next_epoch_state.verifier.clone(),
+				});
+			}
+		}
+		Ok(transitions)
+	}
+
+	/// Verifies an epoch change proof against a trusted state, returning the ratcheted state.
+	/// An empty proof leaves the trusted state unchanged.
+	pub fn verify_epoch_change_proof(
+		proof: &EpochChangeProof,
+		trusted_state: &TrustedState,
+	) -> Result<TrustedState, anyhow::Error> {
+		let Some(latest_ledger_info) = proof.ledger_info_with_sigs.last() else {
+			return Ok(trusted_state.clone());
+		};
+		match trusted_state.verify_and_ratchet_inner(latest_ledger_info, proof)? {
+			TrustedStateChange::Epoch { new_state, .. } => Ok(new_state),
+			TrustedStateChange::Version { new_state } => Ok(new_state),
+			TrustedStateChange::NoChange => Ok(trusted_state.clone()),
+		}
+	}
+
+	/// Gets the root hash of the transaction accumulator at the latest ledger info,
+	/// together with the signed ledger info committing to it.
+	/// The accumulator commits to the state checkpoint hash of every transaction.
+	pub fn get_finalized_state_root(
+		&self,
+	) -> Result<(HashValue, LedgerInfoWithSignatures), anyhow::Error> {
+		let ledger_info_with_sigs = self.db().reader.get_latest_ledger_info()?;
+		let root_hash = ledger_info_with_sigs.ledger_info().transaction_accumulator_hash();
+		Ok((root_hash, ledger_info_with_sigs))
+	}
+
+	/// Verifies that a ledger info is signed by a quorum of the given committee.
+	pub fn verify_signed_ledger_info(
+		info: &LedgerInfoWithSignatures,
+		committee: &ValidatorVerifier,
+	) -> Result<(), anyhow::Error> {
+		Ok(info.verify_signatures(committee)?)
+	}
+
+	/// Gets the quorum signature of the ledger info committed at the given version.
+	/// Ledger infos are only kept for the latest version and the versions ending an epoch,
+	/// other versions are an error.
+	/// Returns `None` for ledger infos not signed by a quorum, such as genesis.
+	pub fn get_aggregated_signature(
+		&self,
+		version: Version,
+	) -> Result<Option<AggregateSignature>, anyhow::Error> {
+		let latest_ledger_info = self.db().reader.get_latest_ledger_info()?;
+		let ledger_info_with_sigs = if latest_ledger_info.ledger_info().version() == version {
+			latest_ledger_info
+		} else {
+			self.db().reader.get_epoch_ending_ledger_info(version)?
+		};
+		let signature = ledger_info_with_sigs.signatures();
+		Ok(signature.sig().is_some().then(|| signature.clone()))
+	}
+
+	/// Gets the fee statement event emitted by the transaction at the given version.
+	/// Returns `None` for transactions which do not charge fees, such as genesis and block metadata.
+	pub fn get_fee_statement(
+		&self,
+		version: Version,
+	) -> Result<Option<FeeStatement>, anyhow::Error> {
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transaction =
+			self.db().reader.get_transaction_by_version(version, ledger_version, true)?;
+		let fee_statement_tag = TypeTag::Struct(Box::new(FeeStatement::struct_tag()));
+		for event in transaction.events.unwrap_or_default() {
+			if event.type_tag() == &fee_statement_tag {
+				return Ok(Some(bcs::from_bytes(event.event_data())?));
+			}
+		}
+		Ok(None)
+	}
+
+	/// Gets the account which paid the fees of the transaction at the given version,
+	/// if it was sponsored by an account other than its sender.
+	/// Returns `None` for transactions other than user transactions, and those without a fee payer.
+	pub fn get_fee_payer(&self, version: Version) -> Result<Option<AccountAddress>, anyhow::Error> {
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transaction =
+			self.db().reader.get_transaction_by_version(version, ledger_version, false)?;
+		let Transaction::UserTransaction(signed_transaction) = transaction.transaction else {
+			return Ok(None);
+		};
+		match signed_transaction.authenticator() {
+			TransactionAuthenticator::FeePayer { fee_payer_address, .. } => {
+				Ok(Some(fee_payer_address))
+			}
+			_ => Ok(None),
+		}
+	}
+
+	/// Gets the transactions from `start_version` to `end_version` inclusive whose fees were
+	/// paid by a fee payer, see [`Executor::get_fee_payer`].
+	/// Fees are the gas used times the gas unit price of the transaction.
+	pub fn get_sponsored_transaction_stats(
+		&self,
+		start_version: Version,
+		end_version: Version,
+	) -> Result<SponsoredTxnStats, anyhow::Error> {
+		if start_version > end_version {
+			anyhow::bail!("Version {} is ahead of version {}", start_version, end_version);
+		}
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		if end_version > ledger_version {
+			anyhow::bail!(
+				"Version {} is ahead of the ledger version {}",
+				end_version,
+				ledger_version
+			);
+		}
+
+		let mut total_count = 0;
+		let mut total_fees_octa: u64 = 0;
+		let mut fees_by_payer: HashMap<AccountAddress, u64> = HashMap::new();
+		for version in start_version..=end_version {
+			let transaction =
+				self.db().reader.get_transaction_by_version(version, ledger_version, false)?;
+			let Transaction::UserTransaction(signed_transaction) = &transaction.transaction else {
+				continue;
+			};
+			let TransactionAuthenticator::FeePayer { fee_payer_address, .. } =
+				signed_transaction.authenticator()
+			else {
+				continue;
+			};
+			let fee = transaction
+				.proof
+				.transaction_info()
+				.gas_used()
+				.checked_mul(signed_transaction.gas_unit_price())
+				.context("Transaction fee overflows a u64")?;
+			total_count += 1;
+			total_fees_octa =
+				total_fees_octa.checked_add(fee).context("Total fees overflow a u64")?;
+			*fees_by_payer.entry(fee_payer_address).or_default() += fee;
+		}
+
+		let mut top_fee_payers: Vec<_> = fees_by_payer.into_iter().collect();
+		top_fee_payers.sort_by(|(payer, fees), (other_payer, other_fees)| {
+			other_fees.cmp(fees).then(payer.cmp(other_payer))
+		});
+		Ok(SponsoredTxnStats { total_count, total_fees_octa, top_fee_payers })
+	}
+
+	/// Gets the events emitted by the committed transaction at the given version.
+	/// Transactions which cannot emit events, such as state checkpoints, have none.
+	pub fn get_transaction_events(
+		&self,
+		version: Version,
+	) -> Result<Vec<ContractEvent>, anyhow::Error> {
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transaction =
+			self.db().reader.get_transaction_by_version(version, ledger_version, true)?;
+		Ok(transaction.events.unwrap_or_default())
+	}
+
+	/// Gets the gas used by the committed transaction at the given version.
+	/// Genesis and block metadata transactions do not consume gas and report `0`.
+	pub fn get_transaction_gas_cost(&self, version: Version) -> Result<u64, anyhow::Error> {
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transaction =
+			self.db().reader.get_transaction_by_version(version, ledger_version, false)?;
+		Ok(transaction.proof.transaction_info().gas_used())
+	}
+
+	/// Gets the committed transaction sent by an account with the given sequence number,
+	/// with its proof against the latest ledger info.
+	/// Returns `None` if the account has not committed a transaction with that sequence number.
+	pub fn get_transaction_by_account_sequence_number(
+		&self,
+		address: AccountAddress,
+		sequence_number: u64,
+		include_events: bool,
+	) -> Result<Option<TransactionWithProof>, anyhow::Error> {
+		let ledger_version = self.version_or_latest(None)?;
+		Ok(self.db().reader.get_account_transaction(
+			address,
+			sequence_number,
+			include_events,
+			ledger_version,
+		)?)
+	}
+
+	/// Gets the summary of the block at the given height.
+	pub fn get_block_info(&self, height: u64) -> Result<BlockInfo, anyhow::Error> {
+		let (first_version, last_version, new_block_event) =
+			self.db().reader.get_block_info_by_height(height)?;
+		Ok(BlockInfo {
+			height: new_block_event.height,
+			first_version,
+			last_version,
+			epoch: new_block_event.epoch(),
+			round: new_block_event.round(),
+			timestamp_usecs: new_block_event.proposed_time(),
+			tx_count: u32::try_from(last_version - first_version + 1)?,
+		})
+	}
+
+	/// Gets the gas used by the transactions of the block at the given height.
+	pub fn get_block_gas_summary(
+		&self,
+		block_height: u64,
+	) -> Result<BlockGasSummary, anyhow::Error> {
+		let block_info = self.get_block_info(block_height)?;
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+
+		let mut total_gas_used: u64 = 0;
+		let mut total_fees_octa: u64 = 0;
+		let mut max_gas: Option<(Version, u64)> = None;
+		for version in block_info.first_version..=block_info.last_version {
+			let transaction =
+				self.db().reader.get_transaction_by_version(version, ledger_version, false)?;
+			let gas_used = transaction.proof.transaction_info().gas_used();
+			total_gas_used =
+				total_gas_used.checked_add(gas_used).context("Block gas overflows a u64")?;
+			if gas_used > 0 && max_gas.map_or(true, |(_, max)| gas_used > max) {
+				max_gas = Some((version, gas_used));
+			}
+			if let Transaction::UserTransaction(signed_transaction) = &transaction.transaction {
+				let fee = gas_used
+					.checked_mul(signed_transaction.gas_unit_price())
+					.context("Transaction fee overflows a u64")?;
+				total_fees_octa =
+					total_fees_octa.checked_add(fee).context("Block fees overflow a u64")?;
+			}
+		}
+
+		Ok(BlockGasSummary {
+			total_gas_used,
+			average_gas_per_txn: total_gas_used as f64 / block_info.tx_count as f64,
+			max_gas_txn_version: max_gas.map(|(version, _)| version),
+			total_fees_octa,
+		})
+	}
+
+	/// Gets the number of committed blocks, including genesis.
+	/// Genesis is block 0, so this is one more than the height of the latest block.
+	pub fn get_block_count(&self) -> Result<u64, anyhow::Error> {
+		Ok(self.get_block_head_height()? + 1)
+	}
+
+	/// Gets the version of the first transaction of the block at the given height,
+	/// which is its block metadata transaction.
+	pub fn get_version_by_block_height(&self, height: u64) -> Result<Version, anyhow::Error> {
+		let (first_version, _, _) = self.db().reader.get_block_info_by_height(height)?;
+		Ok(first_version)
+	}
+
+	/// Gets the height of the block containing the transaction at the given version.
+	pub fn get_block_height_by_version(&self, version: Version) -> Result<u64, anyhow::Error> {
+		let (_, _, new_block_event) = self.db().reader.get_block_info_by_version(version)?;
+		Ok(new_block_event.height)
+	}
+
+	/// Gets the latest version whose block timestamp is at most `timestamp_usecs`,
+	/// by binary search over the block heights.
+	/// Returns `None` if the timestamp is before genesis.
+	/// Timestamps before the latest block are cached, as their version only changes on revert.
+	pub fn get_version_at_time(
+		&self,
+		timestamp_usecs: u64,
+	) -> Result<Option<Version>, anyhow::Error> {
+		if let Some((_, version)) = self
+			.version_at_time_cache
+			.read()
+			.map_err(|e| anyhow::anyhow!("Version at time cache poisoned: {e}"))?
+			.iter()
+			.find(|(cached_timestamp, _)| *cached_timestamp == timestamp_usecs)
+		{
+			return Ok(Some(*version));
+		}
+
+		if self.get_block_info(0)?.timestamp_usecs > timestamp_usecs {
+			return Ok(None);
+		}
+		let head_height = self.get_block_head_height()?;
+		// block timestamps do not decrease, the block at `low` is never after the timestamp
+		let (mut low, mut high) = (0, head_height);
+		while low < high {
+			let mid = low + (high - low + 1) / 2;
+			if self.get_block_info(mid)?.timestamp_usecs <= timestamp_usecs {
+				low = mid;
+			} else {
+				high = mid - 1;
+			}
+		}
+		let version = self.get_block_info(low)?.last_version;
+
+		// the version found in the latest block moves on as blocks are committed
+		if low < head_height {
+			let mut cache = self
+				.version_at_time_cache
+				.write()
+				.map_err(|e| anyhow::anyhow!("Version at time cache poisoned: {e}"))?;
+			if cache.len() == VERSION_AT_TIME_CACHE_SIZE {
+				cache.pop_front();
+			}
+			cache.push_back((timestamp_usecs, version));
+		}
+		Ok(Some(version))
+	}
+
+	/// Gets the timestamp recorded in the metadata transaction of the block at the given height,
+	/// in Unix microseconds.
+	pub fn get_block_timestamp(&self, block_height: u64) -> Result<u64, anyhow::Error> {
+		let (start_version, _, _) = self.db().reader.get_block_info_by_height(block_height)?;
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transactions =
+			self.db().reader.get_transactions(start_version, 1, ledger_version, false)?;
+		match transactions.transactions.first() {
+			Some(Transaction::BlockMetadata(block_metadata)) => {
+				Ok(block_metadata.timestamp_usecs())
+			}
+			Some(Transaction::BlockMetadataExt(block_metadata)) => {
+				Ok(block_metadata.timestamp_usecs())
+			}
+			_ => anyhow::bail!("Block {} has no block metadata transaction", block_height),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Executor, TestExecutor};
+	use aptos_crypto::hash::CryptoHash as _;
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+	use aptos_types::{
+		transaction::Transaction,
+		trusted_state::TrustedState,
+		validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
+		waypoint::Waypoint,
+	};
+
+	#[tokio::test]
+	async fn test_get_transaction_accumulator_hash() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let ledger_info = executor.db_reader().get_latest_ledger_info()?;
+		let version = ledger_info.ledger_info().version();
+		let root_hash = executor.get_transaction_accumulator_hash(version)?;
+		assert_eq!(root_hash, ledger_info.ledger_info().transaction_accumulator_hash());
+
+		let proof = executor.get_accumulator_proof_for_version(0, version)?;
+		assert!(!proof.subtrees().is_empty());
+		assert!(executor.get_accumulator_proof_for_version(version, 0).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_verify_ledger_consistency() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let report = executor.verify_ledger_consistency(0, ledger_version).await?;
+		assert_eq!(report.versions_checked, ledger_version + 1);
+		assert!(report.inconsistencies.is_empty());
+
+		assert!(executor.verify_ledger_consistency(1, 0).await.is_err());
+		assert!(executor.verify_ledger_consistency(0, ledger_version + 1).await.is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_epoch_transitions() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+		let new_epoch = executor.rotate_epoch().await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let transitions = executor.get_epoch_transitions(0, ledger_version)?;
+		assert_eq!(transitions.len() as u64, new_epoch);
+		assert_eq!(transitions[0].new_epoch, 1);
+		assert_eq!(transitions[0].trigger_version, 0);
+		let last = transitions.last().expect("transitions are not empty");
+		assert_eq!(last.new_epoch, new_epoch);
+		assert_eq!(last.trigger_version, ledger_version);
+
+		// genesis is out of the range
+		assert_eq!(executor.get_epoch_transitions(1, ledger_version)?.len() as u64, new_epoch - 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_fee_statement() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let root_account = test_executor.root_account();
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		let transaction_hash = transaction.committed_hash();
+		let output = test_executor.submit_and_execute(transaction).await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let version = executor
+			.db_reader()
+			.get_transaction_by_hash(transaction_hash, ledger_version, false)?
+			.expect("transaction was committed")
+			.version;
+		let fee_statement =
+			executor.get_fee_statement(version)?.expect("user transactions pay fees");
+		assert_eq!(fee_statement.gas_used(), output.gas_used());
+
+		// genesis does not charge fees
+		assert_eq!(executor.get_fee_statement(0)?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_fee_payer() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let sender = LocalAccount::generate(&mut rand::thread_rng());
+		let fee_payer = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 0).await?;
+		let funding_hash = executor.create_account(fee_payer.address(), 1_000_000_000).await?;
+
+		let transaction = sender.sign_fee_payer_with_transaction_builder(
+			vec![],
+			&fee_payer,
+			tx_factory.transfer(fee_payer.address(), 0),
+		);
+		let transaction_hash = transaction.committed_hash();
+		test_executor.submit_and_execute(transaction).await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let version_of = |hash| -> Result<u64, anyhow::Error> {
+			Ok(executor
+				.db_reader()
+				.get_transaction_by_hash(hash, ledger_version, false)?
+				.expect("transaction was committed")
+				.version)
+		};
+		assert_eq!(
+			executor.get_fee_payer(version_of(transaction_hash)?)?,
+			Some(fee_payer.address())
+		);
+		// the funding transaction was paid for by its sender
+		assert_eq!(executor.get_fee_payer(version_of(funding_hash)?)?, None);
+		// genesis is not a user transaction
+		assert_eq!(executor.get_fee_payer(0)?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_sponsored_transaction_stats() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let sender = LocalAccount::generate(&mut rand::thread_rng());
+		let fee_payer = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 0).await?;
+		executor.create_account(fee_payer.address(), 1_000_000_000).await?;
+		let unsponsored_end = executor.db_reader().get_latest_ledger_info_version()?;
+
+		let transaction = sender.sign_fee_payer_with_transaction_builder(
+			vec![],
+			&fee_payer,
+			tx_factory.transfer(fee_payer.address(), 0),
+		);
+		let gas_unit_price = transaction.gas_unit_price();
+		let output = test_executor.submit_and_execute(transaction).await?;
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+
+		let stats = executor.get_sponsored_transaction_stats(0, ledger_version)?;
+		let fee = output.gas_used() * gas_unit_price;
+		assert_eq!(stats.total_count, 1);
+		assert_eq!(stats.total_fees_octa, fee);
+		assert_eq!(stats.top_fee_payers, vec![(fee_payer.address(), fee)]);
+
+		let unsponsored = executor.get_sponsored_transaction_stats(0, unsponsored_end)?;
+		assert_eq!(unsponsored.total_count, 0);
+		assert!(executor.get_sponsored_transaction_stats(0, ledger_version + 1).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_transaction_events() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let account = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(account, 1_000).await?;
+
+		let block_info = executor.get_block_info(executor.get_block_head_height()?)?;
+		let transfer_version = block_info.first_version + 1;
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let (_, output) = executor
+			.db_reader()
+			.get_transaction_outputs(transfer_version, 1, ledger_version)?
+			.transactions_and_outputs
+			.pop()
+			.expect("the transfer was committed");
+		let events = executor.get_transaction_events(transfer_version)?;
+		assert!(!events.is_empty());
+		assert_eq!(events, output.events());
+		// the block ends with a state checkpoint, which emits no events
+		assert!(executor.get_transaction_events(block_info.last_version)?.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_transaction_gas_cost() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let root_account = test_executor.root_account();
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let create_account = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		test_executor.submit_and_execute(create_account).await?;
+		let transfer = root_account
+			.sign_with_transaction_builder(tx_factory.transfer(new_account.address(), 100));
+		let transaction_hash = transfer.committed_hash();
+		test_executor.submit_and_execute(transfer).await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let version = executor
+			.db_reader()
+			.get_transaction_by_hash(transaction_hash, ledger_version, false)?
+			.expect("transaction was committed")
+			.version;
+		assert!(executor.get_transaction_gas_cost(version)? > 0);
+
+		// genesis does not consume gas
+		assert_eq!(executor.get_transaction_gas_cost(0)?, 0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_finalized_state_root() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let (root_hash, ledger_info) = executor.get_finalized_state_root()?;
+		let version = ledger_info.ledger_info().version();
+		assert_eq!(root_hash, executor.get_transaction_accumulator_hash(version)?);
+
+		// this executor commits ledger infos without signatures
+		let committee = ValidatorVerifier::new(vec![ValidatorConsensusInfo::new(
+			executor.signer.author(),
+			executor.signer.public_key(),
+			1,
+		)]);
+		assert!(Executor::verify_signed_ledger_info(&ledger_info, &committee).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_aggregated_signature() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		// genesis and the blocks committed by this executor carry no quorum signature
+		assert_eq!(executor.get_aggregated_signature(0)?, None);
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		assert_eq!(executor.get_aggregated_signature(ledger_version)?, None);
+		assert!(executor.get_aggregated_signature(ledger_version + 1).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_transaction_by_account_sequence_number() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let sender = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 1_000_000_000).await?;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+		let transaction_hash = executor.transfer_coins(&sender, receiver, 100).await?;
+
+		let transaction = executor
+			.get_transaction_by_account_sequence_number(sender.address(), 0, true)?
+			.expect("the transfer was committed");
+		assert_eq!(transaction.transaction.hash(), transaction_hash);
+		assert!(transaction.events.is_some());
+		assert!(executor
+			.get_transaction_by_account_sequence_number(sender.address(), 1, false)?
+			.is_none());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_block_info() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let height = executor.get_block_head_height()?;
+		let block_info = executor.get_block_info(height)?;
+		assert_eq!(block_info.height, height);
+		assert_eq!(block_info.timestamp_usecs, executor.get_block_timestamp(height)?);
+		assert_eq!(block_info.last_version, executor.db_reader().get_latest_ledger_info_version()?);
+		assert_eq!(
+			block_info.tx_count as u64,
+			block_info.last_version - block_info.first_version + 1
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_block_gas_summary() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let account = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(account, 1_000).await?;
+
+		let height = executor.get_block_head_height()?;
+		let block_info = executor.get_block_info(height)?;
+		let summary = executor.get_block_gas_summary(height)?;
+		// the transfer follows the gas free block metadata transaction
+		let transfer_version = block_info.first_version + 1;
+		let transfer_gas = executor.get_transaction_gas_cost(transfer_version)?;
+		assert_eq!(summary.max_gas_txn_version, Some(transfer_version));
+		assert_eq!(summary.total_gas_used, transfer_gas);
+		assert_eq!(summary.average_gas_per_txn, transfer_gas as f64 / block_info.tx_count as f64);
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let transaction = executor.db_reader().get_transaction_by_version(
+			transfer_version,
+			ledger_version,
+			false,
+		)?;
+		let Transaction::UserTransaction(transfer) = transaction.transaction else {
+			panic!("the transfer is a user transaction");
+		};
+		assert_eq!(summary.total_fees_octa, transfer_gas * transfer.gas_unit_price());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_block_count() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		// only the genesis block is committed
+		assert_eq!(executor.get_block_count()?, 1);
+
+		executor.rollover_genesis_now().await?;
+		assert_eq!(executor.get_block_count()?, 2);
+		assert_eq!(executor.get_block_count()?, executor.get_block_head_height()? + 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_block_height_version_mapping() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let height = executor.get_block_head_height()?;
+		let block_info = executor.get_block_info(height)?;
+		let version = executor.get_version_by_block_height(height)?;
+		assert_eq!(version, block_info.first_version);
+		for version in block_info.first_version..=block_info.last_version {
+			assert_eq!(executor.get_block_height_by_version(version)?, height);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_version_at_time() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		for _ in 0..3 {
+			let address = LocalAccount::generate(&mut rand::thread_rng()).address();
+			executor.create_account(address, 0).await?;
+		}
+
+		let head_height = executor.get_block_head_height()?;
+		let block = executor.get_block_info(head_height - 1)?;
+		assert_eq!(executor.get_version_at_time(block.timestamp_usecs)?, Some(block.last_version));
+		// the lookup is now cached
+		assert_eq!(executor.get_version_at_time(block.timestamp_usecs)?, Some(block.last_version));
+		assert_eq!(
+			executor.get_version_at_time(block.timestamp_usecs - 1)?,
+			Some(executor.get_block_info(head_height - 2)?.last_version)
+		);
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		assert_eq!(executor.get_version_at_time(u64::MAX)?, Some(ledger_version));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_block_timestamp() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		let block_height = executor.get_block_head_height()?;
+		assert_eq!(
+			executor.get_block_timestamp(block_height)?,
+			executor.get_last_state_timestamp_micros()?
+		);
+		// the genesis block has no block metadata transaction
+		assert!(executor.get_block_timestamp(0).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_epoch_change_proof() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+		let new_epoch = executor.rotate_epoch().await?;
+
+		let proof = executor.get_epoch_change_proof(1, new_epoch)?;
+		let ledger_infos = &proof.ledger_info_with_sigs;
+		assert_eq!(ledger_infos.len() as u64, new_epoch - 1);
+		let last = ledger_infos.last().expect("proof is not empty").ledger_info();
+		assert_eq!(last.next_block_epoch(), new_epoch);
+		assert!(executor.get_epoch_change_proof(new_epoch, 1).is_err());
+
+		let genesis = executor.db_reader().get_epoch_ending_ledger_info(0)?;
+		let trusted_state =
+			TrustedState::from_epoch_waypoint(Waypoint::new_epoch_boundary(genesis.ledger_info())?);
+		let empty_proof = executor.get_epoch_change_proof(1, 1)?;
+		assert_eq!(
+			Executor::verify_epoch_change_proof(&empty_proof, &trusted_state)?,
+			trusted_state
+		);
+		// the executor commits ledger infos without signatures, so they do not verify
+		assert!(Executor::verify_epoch_change_proof(&proof, &trusted_state).is_err());
+
+		Ok(())
+	}
+}
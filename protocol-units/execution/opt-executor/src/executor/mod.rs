@@ -1,7 +1,25 @@
 // Implementation is split over multiple files to make the code more manageable.
 // TODO: code smell, refactor the god object.
+pub mod account;
+pub mod custom_events;
+pub mod da;
+pub mod decode;
+pub mod dry_run;
+pub mod emergency_stop;
 pub mod execution;
+pub mod feature_flags;
+pub mod governance;
 pub mod initialization;
+pub mod ledger;
+pub mod move_type_info;
+pub mod offline_signing;
+pub mod on_chain_config;
+pub mod replay;
+pub mod rewards;
+pub mod snapshot;
+pub mod state;
+pub mod token;
+pub mod transaction_types;
 
 use aptos_crypto::HashValue;
 use aptos_executor::block_executor::BlockExecutor;
@@ -9,11 +27,43 @@ use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_types::validator_signer::ValidatorSigner;
 use aptos_vm::AptosVM;
 
+use crate::ExecutorMetrics;
+
+pub use account::{CoinEventEntry, CoinEventType};
+pub use custom_events::CustomEvent;
+pub use da::{ApplyResult, BlockRetry, DaIngestionConfig};
+pub use decode::{DecodeError, TransactionFormat};
+pub use dry_run::DryRunResult;
+pub use execution::{BlockCommitEvent, PendingProposalSummary};
+pub use governance::{DelegationInfo, ProposalVoteInfo};
+pub use offline_signing::UnsignedBlockProposal;
+pub use on_chain_config::{
+	GasCurveParameters, PendingConfigChange, ReconfigSummary, VotingPowerDistribution,
+};
+pub use replay::ReplayVerificationResult;
+pub use rewards::EpochRewardInfo;
+pub use snapshot::SnapshotStats;
+pub use state::{DispatchableFunctionStore, FunctionInfo, SupplyChangeEvent, SupplyChangeType};
+pub use token::TokenId;
+pub use transaction_types::TransactionTypeDescriptor;
+
+use thiserror::Error;
 use tracing::info;
 
 use maptos_execution_util::config::Config;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+/// Domain error for the executor
+#[derive(Debug, Clone, Error)]
+pub enum ExecutorError {
+	#[error("Block production is halted by an emergency stop")]
+	EmergencyStopped,
+	#[error("Custom events are disabled by the chain configuration")]
+	CustomEventsDisabled,
+}
 
 /// The `Executor` is responsible for executing blocks and managing the state of the execution
 /// against the `AptosVM`.
@@ -24,6 +74,27 @@ pub struct Executor {
 	pub signer: ValidatorSigner,
 	// Shared reference on the counter of transactions in flight.
 	transactions_in_flight: Arc<AtomicU64>,
+	// Set while block production is halted by an emergency stop.
+	emergency_stopped: Arc<AtomicBool>,
+	// The number of mempool resets requested, applied by the transaction pipe.
+	#[cfg(any(test, feature = "testing"))]
+	mempool_resets: Arc<AtomicU64>,
+	// The framework major version, with the epoch it was read in.
+	protocol_version: Arc<RwLock<Option<(u64, u64)>>>,
+	// The minimum gas unit price, with the epoch it was read in.
+	gas_unit_price: Arc<RwLock<Option<(u64, u64)>>>,
+	// The versions found for recently queried timestamps, oldest first.
+	version_at_time_cache: Arc<RwLock<VecDeque<(u64, u64)>>>,
+	// The blocks executed but not committed yet, in execution order.
+	pending_proposals: Arc<RwLock<Vec<PendingProposalSummary>>>,
+	// Notifies the subscribers of each committed block.
+	block_commits: broadcast::Sender<BlockCommitEvent>,
+	// How the blobs read from the DA are ingested, once configured.
+	da_ingestion: Arc<RwLock<Option<DaIngestionConfig>>>,
+	// The id of the block head when the executor was opened.
+	opened_block_id: HashValue,
+	// The Prometheus metrics of the executor.
+	metrics: Arc<ExecutorMetrics>,
 	// The config for the executor.
 	pub(crate) config: Config,
 }
@@ -53,6 +124,18 @@ impl Executor {
 			.unwrap_or_else(|_| 0);
 	}
 
+	/// Discards all transactions held in the mempool and in flight, for teardown between test
+	/// cases. The transaction pipe discards its mempool before it serves any further request.
+	#[cfg(any(test, feature = "testing"))]
+	pub fn reset_mempool(&self) {
+		self.transactions_in_flight.store(0, Ordering::Relaxed);
+		self.mempool_resets.fetch_add(1, Ordering::Release);
+	}
+
+	pub fn metrics(&self) -> Arc<ExecutorMetrics> {
+		Arc::clone(&self.metrics)
+	}
+
 	pub fn config(&self) -> &Config {
 		&self.config
 	}
@@ -0,0 +1,254 @@
+use super::Executor;
+use aptos_move_binary_format::{
+	access::ModuleAccess,
+	file_format::{SignatureToken, StructFieldInformation, StructHandleIndex},
+	CompiledModule,
+};
+use aptos_sdk::move_types::{
+	language_storage::{ModuleId, StructTag, TypeTag},
+	value::{MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
+};
+use aptos_types::{contract_event::ContractEvent, transaction::Version};
+use serde_json::{Map, Value};
+
+impl Executor {
+	/// Gets the field names and types of an on-chain Move struct.
+	/// Generic parameters are substituted with the type arguments of `type_tag`,
+	/// and nested structs are resolved recursively from their own modules.
+	pub fn get_move_type_info(
+		&self,
+		type_tag: &StructTag,
+		version: Option<Version>,
+	) -> Result<MoveStructLayout, anyhow::Error> {
+		// pin the version so nested structs are read from the same state
+		let version = self.version_or_latest(version)?;
+		let module_id = ModuleId::new(type_tag.address, type_tag.module.clone());
+		let bytecode = self
+			.get_module_bytecode(&module_id, Some(version))?
+			.ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))?;
+		let module = CompiledModule::deserialize(&bytecode)?;
+
+		let struct_def = module
+			.struct_defs()
+			.iter()
+			.find(|def| {
+				module.identifier_at(module.struct_handle_at(def.struct_handle).name)
+					== type_tag.name.as_ident_str()
+			})
+			.ok_or_else(|| anyhow::anyhow!("Struct {} not found", type_tag))?;
+		let fields = match &struct_def.field_information {
+			StructFieldInformation::Declared(fields) => fields,
+			StructFieldInformation::Native => {
+				anyhow::bail!("Native struct {} has no field layout", type_tag)
+			}
+		};
+
+		let fields = fields
+			.iter()
+			.map(|field| {
+				let tag = signature_token_to_type_tag(
+					&module,
+					&field.signature.0,
+					&type_tag.type_params,
+				)?;
+				let layout = self.type_tag_to_layout(&tag, version)?;
+				Ok(MoveFieldLayout::new(module.identifier_at(field.name).to_owned(), layout))
+			})
+			.collect::<Result<Vec<_>, anyhow::Error>>()?;
+		Ok(MoveStructLayout::WithTypes { type_: type_tag.clone(), fields })
+	}
+
+	/// Decodes the BCS payload of an event into JSON, using the layout of its on-chain struct.
+	/// Integers wider than 32 bits are rendered as strings, as the Aptos API does,
+	/// so that they survive JSON parsers using doubles.
+	pub fn decode_event(
+		&self,
+		event: &ContractEvent,
+		version: Option<Version>,
+	) -> Result<Value, anyhow::Error> {
+		let TypeTag::Struct(type_tag) = event.type_tag() else {
+			anyhow::bail!("Event type {} is not a struct", event.type_tag());
+		};
+		let layout = self.get_move_type_info(type_tag, version)?;
+		let value = MoveStruct::simple_deserialize(event.event_data(), &layout)?;
+		Ok(move_value_to_json(MoveValue::Struct(value)))
+	}
+
+	fn type_tag_to_layout(
+		&self,
+		tag: &TypeTag,
+		version: Version,
+	) -> Result<MoveTypeLayout, anyhow::Error> {
+		Ok(match tag {
+			TypeTag::Bool => MoveTypeLayout::Bool,
+			TypeTag::U8 => MoveTypeLayout::U8,
+			TypeTag::U16 => MoveTypeLayout::U16,
+			TypeTag::U32 => MoveTypeLayout::U32,
+			TypeTag::U64 => MoveTypeLayout::U64,
+			TypeTag::U128 => MoveTypeLayout::U128,
+			TypeTag::U256 => MoveTypeLayout::U256,
+			TypeTag::Address => MoveTypeLayout::Address,
+			TypeTag::Signer => MoveTypeLayout::Signer,
+			TypeTag::Vector(inner) => {
+				MoveTypeLayout::Vector(Box::new(self.type_tag_to_layout(inner, version)?))
+			}
+			TypeTag::Struct(struct_tag) => {
+				MoveTypeLayout::Struct(self.get_move_type_info(struct_tag, Some(version))?)
+			}
+		})
+	}
+}
+
+fn move_value_to_json(value: MoveValue) -> Value {
+	match value {
+		MoveValue::Bool(value) => Value::Bool(value),
+		MoveValue::U8(value) => Value::from(value),
+		MoveValue::U16(value) => Value::from(value),
+		MoveValue::U32(value) => Value::from(value),
+		MoveValue::U64(value) => Value::String(value.to_string()),
+		MoveValue::U128(value) => Value::String(value.to_string()),
+		MoveValue::U256(value) => Value::String(value.to_string()),
+		MoveValue::Address(address) | MoveValue::Signer(address) => {
+			Value::String(address.to_hex_literal())
+		}
+		MoveValue::Vector(values) | MoveValue::Struct(MoveStruct::Runtime(values)) => {
+			Value::Array(values.into_iter().map(move_value_to_json).collect())
+		}
+		MoveValue::Struct(MoveStruct::WithTypes { fields, .. })
+		| MoveValue::Struct(MoveStruct::WithFields(fields)) => Value::Object(
+			fields
+				.into_iter()
+				.map(|(name, value)| (name.into_string(), move_value_to_json(value)))
+				.collect::<Map<_, _>>(),
+		),
+	}
+}
+
+/// Resolves a field's signature token to a type tag, substituting type parameters.
+fn signature_token_to_type_tag(
+	module: &CompiledModule,
+	token: &SignatureToken,
+	type_args: &[TypeTag],
+) -> Result<TypeTag, anyhow::Error> {
+	Ok(match token {
+		SignatureToken::Bool => TypeTag::Bool,
+		SignatureToken::U8 => TypeTag::U8,
+		SignatureToken::U16 => TypeTag::U16,
+		SignatureToken::U32 => TypeTag::U32,
+		SignatureToken::U64 => TypeTag::U64,
+		SignatureToken::U128 => TypeTag::U128,
+		SignatureToken::U256 => TypeTag::U256,
+		SignatureToken::Address => TypeTag::Address,
+		SignatureToken::Signer => TypeTag::Signer,
+		SignatureToken::Vector(inner) => {
+			TypeTag::Vector(Box::new(signature_token_to_type_tag(module, inner, type_args)?))
+		}
+		SignatureToken::Struct(handle) => {
+			TypeTag::Struct(Box::new(struct_handle_to_tag(module, *handle, vec![])))
+		}
+		SignatureToken::StructInstantiation(handle, tokens) => {
+			let type_params = tokens
+				.iter()
+				.map(|token| signature_token_to_type_tag(module, token, type_args))
+				.collect::<Result<Vec<_>, anyhow::Error>>()?;
+			TypeTag::Struct(Box::new(struct_handle_to_tag(module, *handle, type_params)))
+		}
+		SignatureToken::TypeParameter(index) => type_args
+			.get(*index as usize)
+			.cloned()
+			.ok_or_else(|| anyhow::anyhow!("Missing type argument {}", index))?,
+		SignatureToken::Reference(_) | SignatureToken::MutableReference(_) => {
+			anyhow::bail!("Struct fields cannot be references")
+		}
+	})
+}
+
+fn struct_handle_to_tag(
+	module: &CompiledModule,
+	handle: StructHandleIndex,
+	type_params: Vec<TypeTag>,
+) -> StructTag {
+	let struct_handle = module.struct_handle_at(handle);
+	let module_handle = module.module_handle_at(struct_handle.module);
+	StructTag {
+		address: *module.address_identifier_at(module_handle.address),
+		module: module.identifier_at(module_handle.name).to_owned(),
+		name: module.identifier_at(struct_handle.name).to_owned(),
+		type_params,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::TestExecutor;
+	use aptos_sdk::{
+		move_types::{
+			language_storage::{StructTag, TypeTag},
+			value::{MoveStructLayout, MoveTypeLayout},
+		},
+		types::LocalAccount,
+	};
+	use std::str::FromStr;
+
+	#[test]
+	fn test_get_coin_store_type_info() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let tag = StructTag::from_str("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>")?;
+
+		let layout = test_executor.executor.get_move_type_info(&tag, None)?;
+		let MoveStructLayout::WithTypes { type_, fields } = layout else {
+			panic!("expected a layout with types");
+		};
+		assert_eq!(type_, tag);
+		let names: Vec<String> = fields.iter().map(|field| field.name.to_string()).collect();
+		assert_eq!(names, vec!["coin", "frozen", "deposit_events", "withdraw_events"]);
+		assert_eq!(fields[1].layout, MoveTypeLayout::Bool);
+
+		// the generic `Coin<CoinType>` field is instantiated with the tag's type argument
+		let MoveTypeLayout::Struct(MoveStructLayout::WithTypes { type_: coin, .. }) =
+			&fields[0].layout
+		else {
+			panic!("expected a nested struct layout");
+		};
+		assert_eq!(
+			coin.type_params,
+			vec![TypeTag::Struct(Box::new(StructTag::from_str("0x1::aptos_coin::AptosCoin")?))]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_missing_struct_type_info() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let tag = StructTag::from_str("0x1::coin::NotAStruct")?;
+		assert!(test_executor.executor.get_move_type_info(&tag, None).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_decode_event() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let new_address = LocalAccount::generate(&mut rand::thread_rng()).address();
+		let transaction_hash = executor.create_account(new_address, 1_000).await?;
+
+		let ledger_version = executor.db_reader().get_latest_ledger_info_version()?;
+		let transaction = executor
+			.db_reader()
+			.get_transaction_by_hash(transaction_hash, ledger_version, true)?
+			.expect("transaction was committed");
+		let deposit_tag = TypeTag::from_str("0x1::coin::DepositEvent")?;
+		let deposit = transaction
+			.events
+			.unwrap_or_default()
+			.into_iter()
+			.find(|event| event.type_tag() == &deposit_tag)
+			.expect("the transfer deposits coins");
+
+		let decoded = executor.decode_event(&deposit, Some(transaction.version))?;
+		assert_eq!(decoded, serde_json::json!({ "amount": "1000" }));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,127 @@
+use super::Executor;
+use anyhow::Context;
+use aptos_crypto::{ed25519::Ed25519Signature, HashValue, Signature};
+use aptos_types::transaction::{Transaction, TransactionOutput};
+use serde::{Deserialize, Serialize};
+
+/// A block proposal awaiting a signature from an offline signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedBlockProposal {
+	/// The ID of the proposed block, committing to the parent and the transactions.
+	pub block_id: HashValue,
+	/// The ID of the committed block the proposal extends.
+	pub parent_id: HashValue,
+	pub timestamp_usecs: u64,
+	pub transactions: Vec<Transaction>,
+}
+
+impl UnsignedBlockProposal {
+	/// The bytes the offline signer is expected to sign.
+	/// The block ID already commits to the transactions, so they are not repeated here.
+	pub fn signing_message(&self) -> Result<Vec<u8>, anyhow::Error> {
+		Ok(bcs::to_bytes(&(self.block_id, self.parent_id, self.timestamp_usecs))?)
+	}
+}
+
+impl Executor {
+	/// Builds a proposal of the given transactions on top of the committed block `parent_id`,
+	/// to be signed offline and passed back to [`Executor::submit_signed_block_proposal`].
+	pub fn build_unsigned_block_proposal(
+		&self,
+		parent_id: HashValue,
+		timestamp_usecs: u64,
+		transactions: Vec<Transaction>,
+	) -> Result<UnsignedBlockProposal, anyhow::Error> {
+		let committed_block_id = self.block_executor.committed_block_id();
+		if parent_id != committed_block_id {
+			anyhow::bail!(
+				"Proposed block must extend the committed block {}, not {}",
+				committed_block_id,
+				parent_id
+			);
+		}
+		let block_id = Self::proposal_block_id(parent_id, &transactions);
+		Ok(UnsignedBlockProposal { block_id, parent_id, timestamp_usecs, transactions })
+	}
+
+	/// Verifies the signature over the proposal against the configured public key of the
+	/// offline signer, then executes and commits the block as [`Executor::propose_block`] does.
+	pub async fn submit_signed_block_proposal(
+		&self,
+		proposal: UnsignedBlockProposal,
+		signature: Ed25519Signature,
+	) -> Result<(HashValue, Vec<TransactionOutput>), anyhow::Error> {
+		let expected_block_id = Self::proposal_block_id(proposal.parent_id, &proposal.transactions);
+		if proposal.block_id != expected_block_id {
+			anyhow::bail!(
+				"Proposal block ID {} does not match its contents, expected {}",
+				proposal.block_id,
+				expected_block_id
+			);
+		}
+
+		let public_key = self
+			.config
+			.chain
+			.maptos_proposal_signer_public_key
+			.as_ref()
+			.context("No block proposal signer public key is configured")?;
+		signature
+			.verify_arbitrary_msg(&proposal.signing_message()?, public_key)
+			.map_err(|e| anyhow::anyhow!("Invalid block proposal signature: {e}"))?;
+
+		self.propose_block(proposal.parent_id, proposal.timestamp_usecs, proposal.transactions)
+			.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_crypto::{
+		ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+		SigningKey, Uniform,
+	};
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+
+	#[tokio::test]
+	async fn test_submit_signed_block_proposal() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate_for_testing();
+		let (mut executor, config, _tempdir) = Executor::try_test_default(private_key)?;
+		// the offline signer holds its own key
+		let signer_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+		executor.config.chain.maptos_proposal_signer_public_key =
+			Some(Ed25519PublicKey::from(&signer_key));
+		let root_account = executor.root_account();
+		let tx_factory = TransactionFactory::new(config.chain.maptos_chain_id);
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transactions =
+			vec![Transaction::UserTransaction(root_account.sign_with_transaction_builder(
+				tx_factory.create_user_account(new_account.public_key()),
+			))];
+
+		let parent_id = executor.block_executor.committed_block_id();
+		let timestamp = chrono::Utc::now().timestamp_micros() as u64;
+		let proposal =
+			executor.build_unsigned_block_proposal(parent_id, timestamp, transactions)?;
+
+		// the proposal survives the round trip to the offline signer
+		let proposal: UnsignedBlockProposal = bcs::from_bytes(&bcs::to_bytes(&proposal)?)?;
+		let message = proposal.signing_message()?;
+
+		// the node's own key is not the signer's
+		let wrong_signature = config.chain.maptos_private_key.sign_arbitrary_message(&message);
+		assert!(executor
+			.submit_signed_block_proposal(proposal.clone(), wrong_signature)
+			.await
+			.is_err());
+
+		let signature = signer_key.sign_arbitrary_message(&message);
+		let (block_id, _outputs) =
+			executor.submit_signed_block_proposal(proposal.clone(), signature).await?;
+		assert_eq!(block_id, proposal.block_id);
+		assert_eq!(executor.block_executor.committed_block_id(), block_id);
+
+		Ok(())
+	}
+}
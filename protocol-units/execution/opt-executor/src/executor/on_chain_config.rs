@@ -0,0 +1,922 @@
+use super::Executor;
+use aptos_crypto::{bls12381, HashValue};
+use aptos_sdk::move_types::{
+	ident_str,
+	identifier::IdentStr,
+	language_storage::StructTag,
+	move_resource::{MoveResource, MoveStructType},
+};
+use aptos_storage_interface::state_view::{
+	DbStateViewAtVersion as _, LatestDbStateCheckpointView as _,
+};
+use aptos_types::{
+	access_path::AccessPath,
+	account_address::AccountAddress,
+	dkg::DKGState,
+	jwks::{jwk::JWK, PatchedJWKs},
+	keyless::Configuration as KeylessConfiguration,
+	network_address::NetworkAddress,
+	on_chain_config::{
+		self, GasScheduleV2, OnChainConfig, OnChainExecutionConfig, OnChainJWKConsensusConfig,
+		ValidatorSet,
+	},
+	state_store::state_key::StateKey,
+	transaction::{ChangeSet, Version},
+	validator_config::ValidatorConfig,
+	validator_performances::ValidatorPerformances,
+	write_set::{WriteOp, WriteSetMut},
+};
+use serde::{Deserialize, Serialize};
+
+use anyhow::Context as _;
+use std::str::FromStr;
+use tracing::info;
+
+/// The gas schedule entry holding the minimum gas unit price.
+const MIN_GAS_UNIT_PRICE_KEY: &str = "txn.min_price_per_gas_unit";
+/// The gas schedule entry holding the maximum gas unit price.
+const MAX_GAS_UNIT_PRICE_KEY: &str = "txn.max_price_per_gas_unit";
+const VALIDATOR_CONFIG_TAG: &str = "0x1::stake::ValidatorConfig";
+
+/// Proposal statistics of an active validator in the current epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorPerformance {
+	pub account: AccountAddress,
+	pub proposals_created: u64,
+	pub proposals_missed: u64,
+	/// The last epoch the validator took part in, which is the current one for active validators.
+	/// Votes are not tracked on chain, only proposals are.
+	pub last_vote_epoch: u64,
+}
+
+/// An epoch change started on chain but not yet completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconfigSummary {
+	/// The version of the transaction which started the reconfiguration.
+	pub trigger_version: Version,
+	/// The epoch the reconfiguration will move to.
+	pub new_epoch: u64,
+}
+
+/// How the voting power of the current epoch is spread over the active validators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VotingPowerDistribution {
+	pub total_voting_power: u64,
+	pub validator_count: u64,
+	/// The Gini coefficient of the voting powers, from 0 for equal powers to almost 1 when a
+	/// single validator holds all the power.
+	pub gini_coefficient: f64,
+	/// The share of the total voting power held by the 3 most powerful validators.
+	pub top_3_share: f64,
+}
+
+/// The bounds of the gas unit price and the block gas target, for fee estimation.
+///
+/// The gas unit price is set by the sender within `[min_price, max_price]`, there is no base fee
+/// adjusted from block to block as with EIP-1559.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasCurveParameters {
+	pub min_price: u64,
+	pub max_price: u64,
+	/// The block gas limit of the execution config, or `None` if blocks are not limited by gas.
+	pub target_block_gas: Option<u64>,
+	/// The most the minimum price can increase by from one block to the next, always `0` as
+	/// the price only changes with the gas schedule.
+	pub gas_price_increase_limit: u64,
+}
+
+/// An on-chain config change buffered until the next epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingConfigChange {
+	/// The Move type of the config, e.g. `0x1::consensus_config::ConsensusConfig`.
+	pub config_type_name: String,
+	/// The BCS encoding of the new config.
+	pub encoded_new_value: Vec<u8>,
+	pub will_activate_at_epoch: u64,
+}
+
+/// The `0x1::config_buffer::PendingConfigs` resource, holding the configs set for the next
+/// epoch in a `SimpleMap<String, Any>` keyed by config type name.
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingConfigs {
+	configs: Vec<(String, AnyValue)>,
+}
+
+/// The `0x1::any::Any` holding a buffered config.
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct AnyValue {
+	type_name: String,
+	data: Vec<u8>,
+}
+
+impl MoveStructType for PendingConfigs {
+	const MODULE_NAME: &'static IdentStr = ident_str!("config_buffer");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("PendingConfigs");
+}
+
+impl MoveResource for PendingConfigs {}
+
+/// The `0x1::randomness::PerBlockRandomness` resource, set by the prologue of each block.
+#[derive(Debug, Deserialize)]
+struct PerBlockRandomness {
+	epoch: u64,
+	round: u64,
+	seed: Option<Vec<u8>>,
+}
+
+impl MoveStructType for PerBlockRandomness {
+	const MODULE_NAME: &'static IdentStr = ident_str!("randomness");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("PerBlockRandomness");
+}
+
+impl MoveResource for PerBlockRandomness {}
+
+impl Executor {
+	/// Reads an on-chain config from the latest state checkpoint.
+	/// Returns `None` if the config is not published on chain.
+	pub fn get_on_chain_config<T: OnChainConfig>(&self) -> Result<Option<T>, anyhow::Error> {
+		let state_view = self.db().reader.latest_state_checkpoint_view()?;
+		Ok(T::fetch_config(&state_view))
+	}
+
+	/// Replaces an on-chain config, taking effect from the next block.
+	/// Only configs stored as their plain BCS encoding can be replaced, configs published as
+	/// serialized bytes, such as the consensus and execution configs, are rejected.
+	/// The framework signer needed to update configs through `aptos_governance` is only
+	/// available to proposal scripts, and the validator signer holds a consensus key which cannot
+	/// sign transactions, so the config is written as a governance write set in a block of its
+	/// own. Returns the hash of the transaction applying it.
+	pub async fn update_on_chain_config<T: OnChainConfig + Serialize>(
+		&self,
+		new_value: T,
+	) -> Result<HashValue, anyhow::Error> {
+		let bytes = bcs::to_bytes(&new_value)?;
+		// the value must be read back as it is written, or `fetch_config` would fail on it
+		let round_trips = T::deserialize_into_config(&bytes)
+			.ok()
+			.and_then(|config| bcs::to_bytes(&config).ok())
+			.is_some_and(|encoded| encoded == bytes);
+		if !round_trips {
+			anyhow::bail!(
+				"On-chain config {}::{} is not stored as its BCS encoding and cannot be updated",
+				T::MODULE_IDENTIFIER,
+				T::TYPE_IDENTIFIER
+			);
+		}
+
+		let write_set = WriteSetMut::new(vec![(
+			StateKey::on_chain_config::<T>()?,
+			WriteOp::legacy_modification(bytes.into()),
+		)])
+		.freeze()?;
+		let block_id = HashValue::random();
+		info!(
+			%block_id,
+			module = T::MODULE_IDENTIFIER,
+			config = T::TYPE_IDENTIFIER,
+			validator = %self.signer.author(),
+			"updating_on_chain_config",
+		);
+		let (_, transaction_info) =
+			self.apply_change_set(block_id, ChangeSet::new(write_set, vec![])).await?;
+		// the config is changed without an epoch change, which the config caches rely on
+		self.clear_on_chain_config_caches()?;
+		Ok(transaction_info.transaction_hash())
+	}
+
+	// Clears the values derived from on-chain configs and cached for the current epoch.
+	fn clear_on_chain_config_caches(&self) -> Result<(), anyhow::Error> {
+		*self
+			.gas_unit_price
+			.write()
+			.map_err(|e| anyhow::anyhow!("Gas unit price cache poisoned: {e}"))? = None;
+		*self
+			.protocol_version
+			.write()
+			.map_err(|e| anyhow::anyhow!("Protocol version cache poisoned: {e}"))? = None;
+		Ok(())
+	}
+
+	/// Gets the current on-chain gas schedule.
+	pub fn get_gas_schedule(&self) -> Result<GasScheduleV2, anyhow::Error> {
+		self.get_on_chain_config::<GasScheduleV2>()?
+			.context("No gas schedule found on chain")
+	}
+
+	/// Gets the JWK consensus config, which sets the OIDC providers whose keys validators
+	/// agree on for keyless accounts.
+	/// Returns `None` if JWK consensus was never configured on chain.
+	pub fn get_jwk_consensus_config(
+		&self,
+	) -> Result<Option<OnChainJWKConsensusConfig>, anyhow::Error> {
+		self.get_on_chain_config::<OnChainJWKConsensusConfig>()
+	}
+
+	/// Gets the `keyless_account::Configuration` checked when validating keyless signatures.
+	/// Returns `None` if keyless accounts are not deployed on chain.
+	pub fn get_keyless_configuration(&self) -> Result<Option<KeylessConfiguration>, anyhow::Error> {
+		self.get_on_chain_config::<KeylessConfiguration>()
+	}
+
+	/// Gets the JWKs of an OIDC provider checked when validating keyless signatures, from the
+	/// `jwks::PatchedJWKs` config, which holds the observed JWKs with the `jwks::Patches` applied.
+	/// Returns no JWKs if the issuer is not configured.
+	pub fn get_jwk_set(&self, issuer: &str) -> Result<Vec<JWK>, anyhow::Error> {
+		let Some(patched_jwks) = self.get_on_chain_config::<PatchedJWKs>()? else {
+			return Ok(Vec::new());
+		};
+		let Some(provider_jwks) = patched_jwks.jwks.get_provider_jwks(issuer.as_bytes()) else {
+			return Ok(Vec::new());
+		};
+		provider_jwks
+			.jwks
+			.iter()
+			.map(|jwk| JWK::try_from(jwk).map_err(|e| anyhow::anyhow!("Invalid JWK: {e}")))
+			.collect()
+	}
+
+	/// Gets the config changes buffered in `config_buffer::PendingConfigs`, which are applied on
+	/// the next reconfiguration, ordered by config type name.
+	pub fn get_config_buffer(&self) -> Result<Vec<PendingConfigChange>, anyhow::Error> {
+		let (epoch, _) = self.get_next_epoch_and_round()?;
+		let version = self.version_or_latest(None)?;
+		let state_key = StateKey::resource_typed::<PendingConfigs>(&AccountAddress::ONE)?;
+		let Some(value) = self.get_state_value(&state_key, version)? else {
+			return Ok(Vec::new());
+		};
+		let pending_configs: PendingConfigs = bcs::from_bytes(value.bytes())?;
+		let mut changes: Vec<_> = pending_configs
+			.configs
+			.into_iter()
+			.map(|(config_type_name, value)| PendingConfigChange {
+				config_type_name,
+				encoded_new_value: value.data,
+				will_activate_at_epoch: epoch + 1,
+			})
+			.collect();
+		changes.sort_by(|a, b| a.config_type_name.cmp(&b.config_type_name));
+		Ok(changes)
+	}
+
+	/// Gets the proposal statistics of the active validators in the current epoch,
+	/// from the `stake::ValidatorPerformance` resource.
+	pub fn get_current_validators_performance(
+		&self,
+	) -> Result<Vec<ValidatorPerformance>, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		let performances = self
+			.get_on_chain_config::<ValidatorPerformances>()?
+			.context("No validator performances found on chain")?;
+		let (epoch, _) = self.get_next_epoch_and_round()?;
+
+		validator_set
+			.active_validators
+			.iter()
+			.map(|validator| {
+				let index = validator.config().validator_index as usize;
+				let performance = performances.validators.get(index).with_context(|| {
+					format!("No performance recorded for validator {}", validator.account_address())
+				})?;
+				Ok(ValidatorPerformance {
+					account: *validator.account_address(),
+					proposals_created: performance.successful_proposals(),
+					proposals_missed: performance.failed_proposals(),
+					last_vote_epoch: epoch,
+				})
+			})
+			.collect()
+	}
+
+	/// Gets the validators leaving the validator set at the end of the epoch.
+	/// Validators which fall below the minimum stake or request to leave are moved to
+	/// `pending_inactive`, and no longer take part in consensus from the next epoch on.
+	pub fn get_pending_inactive_validators(&self) -> Result<Vec<AccountAddress>, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		Ok(validator_set
+			.pending_inactive
+			.iter()
+			.map(|validator| *validator.account_address())
+			.collect())
+	}
+
+	/// Checks whether the validator is leaving the validator set, see
+	/// [`Executor::get_pending_inactive_validators`].
+	pub fn is_validator_pending_inactive(
+		&self,
+		address: AccountAddress,
+	) -> Result<bool, anyhow::Error> {
+		Ok(self.get_pending_inactive_validators()?.contains(&address))
+	}
+
+	/// Gets the validator network addresses of the active validators, from the live
+	/// `stake::ValidatorConfig` of their pools, so address updates made during the epoch
+	/// are included.
+	pub fn get_peer_node_addresses(
+		&self,
+	) -> Result<Vec<(AccountAddress, Vec<NetworkAddress>)>, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		let version = self.version_or_latest(None)?;
+
+		validator_set
+			.active_validators
+			.iter()
+			.map(|validator| {
+				let address = *validator.account_address();
+				let config = self
+					.get_validator_config(address, version)?
+					.with_context(|| format!("No validator config found for {}", address))?;
+				let network_addresses =
+					config.validator_network_addresses().with_context(|| {
+						format!("Invalid network addresses in the validator config of {}", address)
+					})?;
+				Ok((address, network_addresses))
+			})
+			.collect()
+	}
+
+	/// Gets the BLS12-381 consensus key registered in the `stake::ValidatorConfig` of an account.
+	/// Returns `None` if the account has no validator config.
+	pub fn get_validator_consensus_key(
+		&self,
+		validator: AccountAddress,
+		version: Option<Version>,
+	) -> Result<Option<bls12381::PublicKey>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		Ok(self
+			.get_validator_config(validator, version)?
+			.map(|config| config.consensus_public_key))
+	}
+
+	fn get_validator_config(
+		&self,
+		address: AccountAddress,
+		version: Version,
+	) -> Result<Option<ValidatorConfig>, anyhow::Error> {
+		let state_key = StateKey::access_path(AccessPath::resource_access_path(
+			address,
+			StructTag::from_str(VALIDATOR_CONFIG_TAG)?,
+		)?);
+		match self.get_state_value(&state_key, version)? {
+			Some(value) => Ok(Some(bcs::from_bytes(value.bytes())?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Gets the number of stake pools in the validator set, active or joining at the next epoch.
+	pub fn get_active_stake_pool_count(&self) -> Result<u64, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		Ok((validator_set.active_validators.len() + validator_set.pending_active.len()) as u64)
+	}
+
+	/// Gets the total voting power of the active validators for the current epoch,
+	/// which is the active and pending inactive stake of their pools at the start of the epoch.
+	pub fn get_total_staked(&self) -> Result<u64, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		validator_set.active_validators.iter().try_fold(0u64, |total, validator| {
+			total
+				.checked_add(validator.consensus_voting_power())
+				.context("Total stake overflows a u64")
+		})
+	}
+
+	/// Gets the active validators of the current epoch with their voting power,
+	/// by decreasing voting power.
+	pub fn get_validators_by_voting_power(
+		&self,
+	) -> Result<Vec<(AccountAddress, u64)>, anyhow::Error> {
+		let validator_set = self
+			.get_on_chain_config::<ValidatorSet>()?
+			.context("No validator set found on chain")?;
+		let mut validators: Vec<_> = validator_set
+			.active_validators
+			.iter()
+			.map(|validator| (validator.account_address, validator.consensus_voting_power()))
+			.collect();
+		validators.sort_by(|(_, a), (_, b)| b.cmp(a));
+		Ok(validators)
+	}
+
+	/// Computes how concentrated the voting power of the current epoch is.
+	pub fn compute_voting_power_distribution(
+		&self,
+	) -> Result<VotingPowerDistribution, anyhow::Error> {
+		let validators = self.get_validators_by_voting_power()?;
+		let powers: Vec<u64> = validators.iter().map(|(_, power)| *power).collect();
+		voting_power_distribution(&powers)
+	}
+
+	/// Gets the major version of the deployed framework, from the `version::Version` config.
+	/// Framework upgrades only take effect on reconfiguration, so the version is cached
+	/// for the current epoch.
+	pub fn get_protocol_version(&self) -> Result<u64, anyhow::Error> {
+		let (epoch, _) = self.get_next_epoch_and_round()?;
+		if let Some((cached_epoch, major)) = *self
+			.protocol_version
+			.read()
+			.map_err(|e| anyhow::anyhow!("Protocol version cache poisoned: {e}"))?
+		{
+			if cached_epoch == epoch {
+				return Ok(major);
+			}
+		}
+
+		let major = self
+			.get_on_chain_config::<on_chain_config::Version>()?
+			.context("No framework version found on chain")?
+			.major;
+		*self
+			.protocol_version
+			.write()
+			.map_err(|e| anyhow::anyhow!("Protocol version cache poisoned: {e}"))? = Some((epoch, major));
+		Ok(major)
+	}
+
+	/// Gets the major version of the deployed Move stdlib.
+	/// The stdlib is released with the rest of the framework, so this is the
+	/// [protocol version](Self::get_protocol_version).
+	pub fn get_move_stdlib_version(&self) -> Result<u64, anyhow::Error> {
+		self.get_protocol_version()
+	}
+
+	/// Gets the reconfiguration in progress, if any.
+	/// With randomness enabled, epoch changes wait for the DKG session started by
+	/// `reconfiguration_with_dkg::try_start` to complete; other reconfigurations are immediate.
+	pub fn get_pending_reconfig(&self) -> Result<Option<ReconfigSummary>, anyhow::Error> {
+		let Some(session) =
+			self.get_on_chain_config::<DKGState>()?.and_then(|state| state.in_progress)
+		else {
+			return Ok(None);
+		};
+		let dealer_epoch = session.metadata.dealer_epoch;
+
+		// the session of the current epoch stays in progress from its start to the epoch change,
+		// so the start is the first version at which it is observed
+		let in_progress_at = |version: Version| -> Result<bool, anyhow::Error> {
+			let state_view = self.db().reader.state_view_at_version(Some(version))?;
+			Ok(DKGState::fetch_config(&state_view)
+				.and_then(|state| state.in_progress)
+				.is_some_and(|session| session.metadata.dealer_epoch == dealer_epoch))
+		};
+		let (mut low, mut high) = (0, self.db().reader.get_latest_ledger_info_version()?);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			if in_progress_at(mid)? {
+				high = mid;
+			} else {
+				low = mid + 1;
+			}
+		}
+
+		Ok(Some(ReconfigSummary { trigger_version: low, new_epoch: dealer_epoch + 1 }))
+	}
+
+	/// Gets the randomness seed of the block at the given epoch and round, from the
+	/// `randomness::PerBlockRandomness` resource after its block metadata transaction.
+	/// Returns `None` if no such block was committed, or if it has no seed, which is the case
+	/// while randomness is disabled.
+	pub fn get_randomness_seed(
+		&self,
+		epoch: u64,
+		round: u64,
+	) -> Result<Option<[u8; 32]>, anyhow::Error> {
+		// blocks are ordered by epoch and round, find the first one not before the slot
+		let head_height = self.get_block_head_height()?;
+		let (mut low, mut high) = (0, head_height + 1);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			let block = self.get_block_info(mid)?;
+			if (block.epoch, block.round) < (epoch, round) {
+				low = mid + 1;
+			} else {
+				high = mid;
+			}
+		}
+		if low > head_height {
+			return Ok(None);
+		}
+		let block = self.get_block_info(low)?;
+		if (block.epoch, block.round) != (epoch, round) {
+			return Ok(None);
+		}
+
+		let state_key = StateKey::resource_typed::<PerBlockRandomness>(&AccountAddress::ONE)?;
+		let Some(value) = self.get_state_value(&state_key, block.first_version)? else {
+			return Ok(None);
+		};
+		let randomness: PerBlockRandomness = bcs::from_bytes(value.bytes())?;
+		if (randomness.epoch, randomness.round) != (epoch, round) {
+			return Ok(None);
+		}
+		randomness
+			.seed
+			.map(|seed| {
+				<[u8; 32]>::try_from(seed)
+					.map_err(|seed| anyhow::anyhow!("Randomness seed of {} bytes", seed.len()))
+			})
+			.transpose()
+	}
+
+	/// Gets the minimum gas unit price to set on transactions, or `1` if there is no gas
+	/// schedule on chain.
+	/// The gas schedule only changes on reconfiguration, so the price is cached for the current
+	/// epoch.
+	pub fn get_current_gas_unit_price(&self) -> Result<u64, anyhow::Error> {
+		let (epoch, _) = self.get_next_epoch_and_round()?;
+		if let Some((cached_epoch, price)) = *self
+			.gas_unit_price
+			.read()
+			.map_err(|e| anyhow::anyhow!("Gas unit price cache poisoned: {e}"))?
+		{
+			if cached_epoch == epoch {
+				return Ok(price);
+			}
+		}
+
+		let price = match self.get_on_chain_config::<GasScheduleV2>()? {
+			Some(gas_schedule) => min_gas_unit_price(&gas_schedule)?,
+			None => 1,
+		};
+		*self
+			.gas_unit_price
+			.write()
+			.map_err(|e| anyhow::anyhow!("Gas unit price cache poisoned: {e}"))? = Some((epoch, price));
+		Ok(price)
+	}
+
+	/// Gets the minimum gas unit price transactions must set to be accepted.
+	pub fn get_min_gas_unit_price(&self) -> Result<u64, anyhow::Error> {
+		min_gas_unit_price(&self.get_gas_schedule()?)
+	}
+
+	/// Gets the gas unit price bounds of the gas schedule and the block gas limit of the
+	/// execution config.
+	pub fn get_gas_curve_parameters(&self) -> Result<GasCurveParameters, anyhow::Error> {
+		let gas_schedule = self.get_gas_schedule()?;
+		let target_block_gas = self
+			.get_on_chain_config::<OnChainExecutionConfig>()?
+			.context("No execution config found on chain")?
+			.block_gas_limit_type()
+			.block_gas_limit();
+		Ok(GasCurveParameters {
+			min_price: min_gas_unit_price(&gas_schedule)?,
+			max_price: gas_schedule_entry(&gas_schedule, MAX_GAS_UNIT_PRICE_KEY)?,
+			target_block_gas,
+			gas_price_increase_limit: 0,
+		})
+	}
+}
+
+fn min_gas_unit_price(gas_schedule: &GasScheduleV2) -> Result<u64, anyhow::Error> {
+	gas_schedule_entry(gas_schedule, MIN_GAS_UNIT_PRICE_KEY)
+}
+
+fn gas_schedule_entry(gas_schedule: &GasScheduleV2, key: &str) -> Result<u64, anyhow::Error> {
+	gas_schedule
+		.entries
+		.iter()
+		.find(|(name, _)| name == key)
+		.map(|(_, value)| *value)
+		.with_context(|| format!("No {} entry in the gas schedule", key))
+}
+
+/// Computes the distribution of voting powers given by decreasing power.
+fn voting_power_distribution(powers: &[u64]) -> Result<VotingPowerDistribution, anyhow::Error> {
+	let total_voting_power = powers.iter().try_fold(0u64, |total, power| {
+		total.checked_add(*power).context("Total voting power overflows a u64")
+	})?;
+	if total_voting_power == 0 {
+		return Ok(VotingPowerDistribution {
+			total_voting_power,
+			validator_count: powers.len() as u64,
+			gini_coefficient: 0.0,
+			top_3_share: 0.0,
+		});
+	}
+	let count = powers.len() as f64;
+	let total = total_voting_power as f64;
+	// with the powers in increasing order, G = 2 * sum(i * x_i) / (n * sum(x_i)) - (n + 1) / n
+	let weighted_sum: f64 = powers
+		.iter()
+		.rev()
+		.enumerate()
+		.map(|(index, power)| (index + 1) as f64 * *power as f64)
+		.sum();
+	let gini_coefficient = 2.0 * weighted_sum / (count * total) - (count + 1.0) / count;
+	let top_3_power: u64 = powers.iter().take(3).sum();
+	Ok(VotingPowerDistribution {
+		total_voting_power,
+		validator_count: powers.len() as u64,
+		gini_coefficient,
+		top_3_share: top_3_power as f64 / total,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{bootstrap, TestExecutor};
+	use aptos_types::on_chain_config::{ConfigV1, OIDCProvider};
+
+	#[test]
+	fn test_get_min_gas_unit_price() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let gas_schedule = test_executor.executor.get_gas_schedule()?;
+		assert!(!gas_schedule.entries.is_empty());
+		let min_gas_unit_price = test_executor.executor.get_min_gas_unit_price()?;
+		assert!(gas_schedule
+			.entries
+			.contains(&(MIN_GAS_UNIT_PRICE_KEY.to_string(), min_gas_unit_price)));
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_gas_curve_parameters() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let parameters = executor.get_gas_curve_parameters()?;
+		assert_eq!(parameters.min_price, executor.get_min_gas_unit_price()?);
+		assert!(parameters.max_price >= parameters.min_price);
+		assert_eq!(parameters.gas_price_increase_limit, 0);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_update_on_chain_config() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let mut gas_schedule = executor.get_gas_schedule()?;
+		gas_schedule.feature_version += 1;
+		executor.update_on_chain_config(gas_schedule.clone()).await?;
+		assert_eq!(executor.get_on_chain_config::<GasScheduleV2>()?, Some(gas_schedule));
+
+		// the versions cached for the epoch are refreshed
+		let major = executor.get_protocol_version()?;
+		executor
+			.update_on_chain_config(on_chain_config::Version { major: major + 1 })
+			.await?;
+		assert_eq!(executor.get_protocol_version()?, major + 1);
+
+		// the execution config is published as serialized bytes
+		let execution_config = executor
+			.get_on_chain_config::<OnChainExecutionConfig>()?
+			.context("No execution config found on chain")?;
+		assert!(executor.update_on_chain_config(execution_config.clone()).await.is_err());
+		assert_eq!(
+			executor.get_on_chain_config::<OnChainExecutionConfig>()?,
+			Some(execution_config)
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_jwk_consensus_config() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		// genesis publishes the JWK consensus config, even when it is off
+		assert!(test_executor.executor.get_jwk_consensus_config()?.is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_jwk_set_unknown_issuer() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		assert!(test_executor.executor.get_jwk_set("https://unknown.example.com")?.is_empty());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_config_buffer() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		assert!(executor.get_config_buffer()?.is_empty());
+
+		let type_name = "0x1::consensus_config::ConsensusConfig".to_string();
+		let pending_configs = PendingConfigs {
+			configs: vec![(
+				type_name.clone(),
+				AnyValue { type_name: type_name.clone(), data: vec![1, 2, 3] },
+			)],
+		};
+		let write_set = WriteSetMut::new(vec![(
+			StateKey::resource_typed::<PendingConfigs>(&AccountAddress::ONE)?,
+			WriteOp::legacy_modification(bcs::to_bytes(&pending_configs)?.into()),
+		)])
+		.freeze()?;
+		executor
+			.apply_write_set(write_set, "buffer a config change".to_string())
+			.await?;
+
+		let (epoch, _) = executor.get_next_epoch_and_round()?;
+		assert_eq!(
+			executor.get_config_buffer()?,
+			vec![PendingConfigChange {
+				config_type_name: type_name,
+				encoded_new_value: vec![1, 2, 3],
+				will_activate_at_epoch: epoch + 1,
+			}]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_keyless_configuration() -> Result<(), anyhow::Error> {
+		let jwk_consensus_config = OnChainJWKConsensusConfig::V1(ConfigV1 {
+			oidc_providers: vec![OIDCProvider {
+				name: "https://accounts.google.com".to_string(),
+				config_url: "https://accounts.google.com/.well-known/openid-configuration"
+					.to_string(),
+			}],
+		});
+		let mut genesis_config = bootstrap::genesis_configuration();
+		genesis_config.jwk_consensus_config_override = Some(jwk_consensus_config.clone());
+		let test_executor = TestExecutor::with_genesis_config(&genesis_config)?;
+		let executor = &test_executor.executor;
+
+		// keyless accounts are configured along with the OIDC providers they rely on
+		assert_eq!(executor.get_jwk_consensus_config()?, Some(jwk_consensus_config));
+		let configuration = executor.get_keyless_configuration()?;
+		assert!(configuration.is_some_and(|configuration| configuration.max_signatures_per_txn > 0));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_current_validators_performance() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		// genesis sets up a single validator, which is the executor's signer
+		let performances = executor.get_current_validators_performance()?;
+		assert_eq!(performances.len(), 1);
+		assert_eq!(performances[0].account, executor.signer.author());
+		assert_eq!(performances[0].last_vote_epoch, executor.get_next_epoch_and_round()?.0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_pending_inactive_validators() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		// the genesis validator stays in the set
+		assert!(executor.get_pending_inactive_validators()?.is_empty());
+		assert!(!executor.is_validator_pending_inactive(executor.signer.author())?);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_peer_node_addresses() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let peers = executor.get_peer_node_addresses()?;
+		let accounts: Vec<_> = peers.iter().map(|(account, _)| *account).collect();
+		assert_eq!(accounts, vec![executor.signer.author()]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_validator_consensus_key() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let consensus_key = executor.get_validator_consensus_key(executor.signer.author(), None)?;
+		assert_eq!(consensus_key, Some(executor.signer.public_key()));
+		assert_eq!(executor.get_validator_consensus_key(AccountAddress::random(), None)?, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_active_stake_pool_count() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		assert_eq!(executor.get_active_stake_pool_count()?, 1);
+		let validator_set = executor.get_on_chain_config::<ValidatorSet>()?.unwrap();
+		assert_eq!(
+			executor.get_total_staked()?,
+			validator_set.active_validators[0].consensus_voting_power()
+		);
+		assert!(executor.get_total_staked()? > 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_voting_power_distribution() -> Result<(), anyhow::Error> {
+		let equal = voting_power_distribution(&[10, 10, 10, 10])?;
+		assert_eq!(equal.total_voting_power, 40);
+		assert_eq!(equal.gini_coefficient, 0.0);
+		assert_eq!(equal.top_3_share, 0.75);
+
+		let concentrated = voting_power_distribution(&[100, 0, 0, 0])?;
+		assert_eq!(concentrated.gini_coefficient, 0.75);
+		assert_eq!(concentrated.top_3_share, 1.0);
+
+		let empty = voting_power_distribution(&[])?;
+		assert_eq!(empty.validator_count, 0);
+		assert_eq!(empty.gini_coefficient, 0.0);
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_validators_by_voting_power() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		// the genesis has a single validator, holding all the voting power
+		let validators = executor.get_validators_by_voting_power()?;
+		assert_eq!(validators, vec![(executor.signer.author(), executor.get_total_staked()?)]);
+		let distribution = executor.compute_voting_power_distribution()?;
+		assert_eq!(distribution.validator_count, 1);
+		assert_eq!(distribution.gini_coefficient, 0.0);
+		assert_eq!(distribution.top_3_share, 1.0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_pending_reconfig() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		assert_eq!(executor.get_pending_reconfig()?, None);
+
+		// without randomness, epoch changes complete in the transaction which starts them
+		executor.rotate_epoch().await?;
+		assert_eq!(executor.get_pending_reconfig()?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_randomness_seed_without_randomness() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+
+		// randomness is not enabled at genesis, so committed blocks have no seed
+		let block = executor.get_block_info(executor.get_block_head_height()?)?;
+		assert_eq!(executor.get_randomness_seed(block.epoch, block.round)?, None);
+		// no block was committed in future epochs
+		assert_eq!(executor.get_randomness_seed(block.epoch + 1, 0)?, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_current_gas_unit_price() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let price = executor.get_current_gas_unit_price()?;
+		assert_eq!(price, executor.get_min_gas_unit_price()?);
+		assert!(executor.gas_unit_price.read().unwrap().is_some());
+
+		// updating the gas schedule invalidates the cached price
+		let mut gas_schedule = executor.get_gas_schedule()?;
+		for (name, value) in gas_schedule.entries.iter_mut() {
+			if name == MIN_GAS_UNIT_PRICE_KEY {
+				*value = price + 1;
+			}
+		}
+		executor.update_on_chain_config(gas_schedule).await?;
+		assert_eq!(executor.get_current_gas_unit_price()?, price + 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_protocol_version() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let version = executor.get_protocol_version()?;
+		assert!(version > 0);
+		// the cached value is served for the same epoch
+		assert!(executor.protocol_version.read().unwrap().is_some());
+		assert_eq!(executor.get_protocol_version()?, version);
+		assert_eq!(executor.get_move_stdlib_version()?, version);
+
+		Ok(())
+	}
+}
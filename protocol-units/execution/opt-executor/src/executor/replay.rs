@@ -0,0 +1,95 @@
+use super::Executor;
+use aptos_crypto::hash::CryptoHash;
+use aptos_storage_interface::state_view::DbStateViewAtVersion as _;
+use aptos_types::{
+	proof::accumulator::InMemoryEventAccumulator,
+	transaction::{
+		signature_verified_transaction::into_signature_verified_block, TransactionInfo,
+		TransactionOutput, TransactionStatus, Version,
+	},
+};
+use aptos_vm::{AptosVM, VMExecutor};
+
+/// The outcome of re-executing a committed block.
+#[derive(Debug, Clone)]
+pub struct ReplayVerificationResult {
+	pub all_match: bool,
+	/// The version, replayed output and committed info of each transaction whose output differs.
+	pub mismatches: Vec<(Version, TransactionOutput, TransactionInfo)>,
+}
+
+impl Executor {
+	/// Re-executes the transactions of a committed block on the state before it, and checks
+	/// the status, gas used, write set hash and event root hash of each output against its
+	/// committed `TransactionInfo`.
+	/// The genesis block has no prior state and cannot be replayed.
+	pub async fn replay_block(
+		&self,
+		block_height: u64,
+	) -> Result<ReplayVerificationResult, anyhow::Error> {
+		if block_height == 0 {
+			anyhow::bail!("The genesis block cannot be replayed");
+		}
+		let block = self.get_block_info(block_height)?;
+		let reader = self.db_reader();
+		tokio::task::spawn_blocking(move || {
+			let ledger_version = reader.get_latest_ledger_info_version()?;
+			let transactions = reader.get_transactions(
+				block.first_version,
+				u64::from(block.tx_count),
+				ledger_version,
+				false,
+			)?;
+			let state_view = reader.state_view_at_version(Some(block.first_version - 1))?;
+			let outputs = AptosVM::execute_block_no_limit(
+				&into_signature_verified_block(transactions.transactions),
+				&state_view,
+			)
+			.map_err(|e| anyhow::anyhow!("Failed to replay block {}: {:?}", block_height, e))?;
+
+			let mismatches: Vec<_> = outputs
+				.into_iter()
+				.zip(transactions.proof.transaction_infos)
+				.zip(block.first_version..)
+				.filter(|((output, info), _)| !output_matches(output, info))
+				.map(|((output, info), version)| (version, output, info))
+				.collect();
+			Ok(ReplayVerificationResult { all_match: mismatches.is_empty(), mismatches })
+		})
+		.await?
+	}
+}
+
+/// Checks a replayed output against the info committed for the transaction.
+fn output_matches(output: &TransactionOutput, info: &TransactionInfo) -> bool {
+	let TransactionStatus::Keep(status) = output.status() else {
+		return false;
+	};
+	let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+	status == info.status()
+		&& output.gas_used() == info.gas_used()
+		&& CryptoHash::hash(output.write_set()) == info.state_change_hash()
+		&& InMemoryEventAccumulator::from_leaves(&event_hashes).root_hash()
+			== info.event_root_hash()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::TestExecutor;
+	use aptos_sdk::types::LocalAccount;
+
+	#[tokio::test]
+	async fn test_replay_block() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let account = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(account, 1_000).await?;
+
+		let result = executor.replay_block(executor.get_block_head_height()?).await?;
+		assert!(result.all_match, "mismatches: {:?}", result.mismatches);
+
+		assert!(executor.replay_block(0).await.is_err());
+
+		Ok(())
+	}
+}
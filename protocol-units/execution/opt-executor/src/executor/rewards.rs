@@ -0,0 +1,150 @@
+use super::Executor;
+use aptos_sdk::move_types::{
+	ident_str,
+	identifier::IdentStr,
+	language_storage::TypeTag,
+	move_resource::{MoveResource, MoveStructType},
+};
+use aptos_types::{account_address::AccountAddress, state_store::state_key::StateKey};
+use serde::Deserialize;
+
+/// The rewards distributed to the validators at the end of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochRewardInfo {
+	pub total_reward_octa: u64,
+	/// The per epoch rewards rate of the staking config, in basis points.
+	pub reward_rate_bps: u64,
+	/// The reward of each stake pool, in the order they were distributed.
+	pub proposer_rewards: Vec<(AccountAddress, u64)>,
+}
+
+/// The `0x1::stake::DistributeRewardsEvent` emitted for each pool on reconfiguration.
+#[derive(Debug, Deserialize)]
+struct DistributeRewardsEvent {
+	pool_address: AccountAddress,
+	rewards_amount: u64,
+}
+
+impl MoveStructType for DistributeRewardsEvent {
+	const MODULE_NAME: &'static IdentStr = ident_str!("stake");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("DistributeRewardsEvent");
+}
+
+/// The `0x1::staking_config::StakingConfig` resource.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct StakingConfig {
+	minimum_stake: u64,
+	maximum_stake: u64,
+	recurring_lockup_duration_secs: u64,
+	allow_validator_set_change: bool,
+	rewards_rate: u64,
+	rewards_rate_denominator: u64,
+	voting_power_increase_limit: u64,
+}
+
+impl MoveStructType for StakingConfig {
+	const MODULE_NAME: &'static IdentStr = ident_str!("staking_config");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("StakingConfig");
+}
+
+impl MoveResource for StakingConfig {}
+
+impl Executor {
+	/// Gets the rewards distributed by the reconfiguration ending the given epoch,
+	/// from the `stake::DistributeRewardsEvent`s of the transaction ending it.
+	/// The rewards rate is read from the state after that transaction.
+	/// Returns `None` if the epoch has not ended yet.
+	pub fn get_block_reward(&self, epoch: u64) -> Result<Option<EpochRewardInfo>, anyhow::Error> {
+		let (current_epoch, _) = self.get_next_epoch_and_round()?;
+		if epoch >= current_epoch {
+			return Ok(None);
+		}
+		let proof = self.get_epoch_change_proof(epoch, epoch + 1)?;
+		let Some(ledger_info_with_sigs) = proof.ledger_info_with_sigs.first() else {
+			return Ok(None);
+		};
+		let version = ledger_info_with_sigs.ledger_info().version();
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let transaction =
+			self.db().reader.get_transaction_by_version(version, ledger_version, true)?;
+
+		let distribute_rewards_tag =
+			TypeTag::Struct(Box::new(DistributeRewardsEvent::struct_tag()));
+		let mut proposer_rewards = Vec::new();
+		for event in transaction.events.unwrap_or_default() {
+			if event.type_tag() == &distribute_rewards_tag {
+				let event: DistributeRewardsEvent = bcs::from_bytes(event.event_data())?;
+				proposer_rewards.push((event.pool_address, event.rewards_amount));
+			}
+		}
+		let total_reward_octa = proposer_rewards.iter().map(|(_, reward)| reward).sum();
+
+		let state_key = StateKey::resource_typed::<StakingConfig>(&AccountAddress::ONE)?;
+		let reward_rate_bps = match self.get_state_value(&state_key, version)? {
+			Some(value) => {
+				let config: StakingConfig = bcs::from_bytes(value.bytes())?;
+				rate_to_bps(config.rewards_rate, config.rewards_rate_denominator)
+			}
+			None => anyhow::bail!("No staking config found at version {}", version),
+		};
+
+		Ok(Some(EpochRewardInfo { total_reward_octa, reward_rate_bps, proposer_rewards }))
+	}
+}
+
+fn rate_to_bps(rate: u64, denominator: u64) -> u64 {
+	if denominator == 0 {
+		return 0;
+	}
+	(rate as u128 * 10_000 / denominator as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{bootstrap, TestExecutor};
+
+	#[test]
+	fn test_rate_to_bps() {
+		assert_eq!(rate_to_bps(10, 100_000), 1);
+		assert_eq!(rate_to_bps(1, 2), 5_000);
+		assert_eq!(rate_to_bps(1, 0), 0);
+	}
+
+	#[tokio::test]
+	async fn test_get_block_reward() -> Result<(), anyhow::Error> {
+		// the yearly rewards rate is spread over the epochs of a year, so they must be shorter
+		let mut genesis_config = bootstrap::genesis_configuration();
+		genesis_config.epoch_duration_secs = 60 * 60;
+		genesis_config.rewards_apy_percentage = 10;
+		let test_executor = TestExecutor::with_genesis_config(&genesis_config)?;
+		let executor = &test_executor.executor;
+		// roll over within the first epoch
+		executor.rollover_genesis(1_000_000).await?;
+
+		// genesis ends epoch 0 without distributing rewards
+		let genesis_rewards = executor.get_block_reward(0)?.expect("genesis ends epoch 0");
+		assert_eq!(genesis_rewards.total_reward_octa, 0);
+		assert!(genesis_rewards.proposer_rewards.is_empty());
+
+		let (current_epoch, _) = executor.get_next_epoch_and_round()?;
+		assert_eq!(executor.get_block_reward(current_epoch)?, None);
+
+		let validators = executor.get_validators_by_voting_power()?;
+		assert_eq!(validators.len(), 1);
+		let (pool_address, stake) = validators[0];
+		assert_eq!(pool_address, executor.signer.author());
+
+		// the test validator proposed every block of the epoch, the rewards are added to its stake
+		executor.rotate_epoch().await?;
+		let rewards = executor.get_block_reward(current_epoch)?.expect("the epoch ended");
+		let (_, next_stake) = executor.get_validators_by_voting_power()?[0];
+		let reward = next_stake - stake;
+		assert!(reward > 0);
+		assert_eq!(rewards.proposer_rewards, vec![(pool_address, reward)]);
+		assert_eq!(rewards.total_reward_octa, reward);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,126 @@
+use super::Executor;
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// The file holding the state values of a snapshot.
+pub const SNAPSHOT_STATE_FILE: &str = "state_values.bcs";
+/// The file describing a snapshot, written once the state values are complete.
+pub const SNAPSHOT_METADATA_FILE: &str = "snapshot_metadata.json";
+
+// Number of state values read from the db at once.
+const SNAPSHOT_CHUNK_SIZE: usize = 10_000;
+
+/// A summary of an exported state snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotStats {
+	/// The state checkpoint version the snapshot was taken at.
+	pub state_version: u64,
+	pub key_count: u64,
+	/// The size of the state values file.
+	pub bytes_written: u64,
+	pub duration_ms: u64,
+}
+
+impl Executor {
+	/// Exports all state values at the latest state checkpoint to `output_dir`.
+	/// Each `(StateKey, StateValue)` pair is written to [`SNAPSHOT_STATE_FILE`] as a BCS record
+	/// prefixed with its big endian `u32` length, in state key hash order, and the stats are
+	/// written to [`SNAPSHOT_METADATA_FILE`].
+	pub async fn export_state_snapshot(
+		&self,
+		output_dir: &Path,
+	) -> Result<SnapshotStats, anyhow::Error> {
+		let reader = self.db_reader();
+		let output_dir = output_dir.to_path_buf();
+		tokio::task::spawn_blocking(move || {
+			let start = Instant::now();
+			let state_version = reader
+				.get_latest_state_checkpoint_version()?
+				.context("No state checkpoint to export")?;
+			let expected_key_count = reader.get_state_item_count(state_version)?;
+
+			fs::create_dir_all(&output_dir)?;
+			let mut writer = BufWriter::new(File::create(output_dir.join(SNAPSHOT_STATE_FILE))?);
+			let mut key_count = 0;
+			let mut bytes_written = 0;
+			while key_count < expected_key_count {
+				let chunk = reader.get_state_value_chunk_iter(
+					state_version,
+					key_count,
+					SNAPSHOT_CHUNK_SIZE,
+				)?;
+				let chunk_start = key_count;
+				for entry in chunk {
+					let record = bcs::to_bytes(&entry?)?;
+					writer.write_all(&u32::try_from(record.len())?.to_be_bytes())?;
+					writer.write_all(&record)?;
+					bytes_written += 4 + record.len() as u64;
+					key_count += 1;
+				}
+				if key_count == chunk_start {
+					anyhow::bail!(
+						"State at version {} ended after {} of {} keys",
+						state_version,
+						key_count,
+						expected_key_count
+					);
+				}
+			}
+			writer.flush()?;
+
+			let stats = SnapshotStats {
+				state_version,
+				key_count: key_count as u64,
+				bytes_written,
+				duration_ms: start.elapsed().as_millis() as u64,
+			};
+			fs::write(output_dir.join(SNAPSHOT_METADATA_FILE), serde_json::to_vec_pretty(&stats)?)?;
+			Ok(stats)
+		})
+		.await?
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_types::state_store::{state_key::StateKey, state_value::StateValue};
+
+	#[tokio::test]
+	async fn test_export_state_snapshot() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		executor.rollover_genesis_now().await?;
+		let dir = tempfile::tempdir()?;
+
+		let stats = executor.export_state_snapshot(dir.path()).await?;
+		let state_version = executor.db_reader().get_latest_state_checkpoint_version()?;
+		assert_eq!(Some(stats.state_version), state_version);
+		assert!(stats.key_count > 0);
+
+		let metadata = fs::read(dir.path().join(SNAPSHOT_METADATA_FILE))?;
+		assert_eq!(serde_json::from_slice::<SnapshotStats>(&metadata)?, stats);
+
+		// the state file is a sequence of length prefixed records
+		let state_file = fs::read(dir.path().join(SNAPSHOT_STATE_FILE))?;
+		assert_eq!(state_file.len() as u64, stats.bytes_written);
+		let mut records = 0;
+		let mut rest = state_file.as_slice();
+		while !rest.is_empty() {
+			let (len, tail) = rest.split_at(4);
+			let (record, tail) = tail.split_at(u32::from_be_bytes(len.try_into()?) as usize);
+			bcs::from_bytes::<(StateKey, StateValue)>(record)?;
+			records += 1;
+			rest = tail;
+		}
+		assert_eq!(records, stats.key_count);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,930 @@
+use super::{governance::struct_field, Executor};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_sdk::move_types::{
+	ident_str,
+	identifier::IdentStr,
+	language_storage::{ModuleId, StructTag, TypeTag},
+	move_resource::{MoveResource, MoveStructType},
+	value::MoveValue,
+};
+use aptos_types::{
+	access_path::{AccessPath, Path},
+	account_address::AccountAddress,
+	account_config::{AccountResource, CoinInfoResource, CoinStoreResource, ObjectGroupResource},
+	proof::SparseMerkleProof,
+	state_store::{
+		state_key::{inner::StateKeyInner, prefix::StateKeyPrefix, StateKey},
+		state_value::StateValue,
+		table::TableHandle,
+	},
+	transaction::{Transaction, Version},
+	write_set::TransactionWrite,
+};
+use serde::Deserialize;
+use tracing::debug;
+
+use std::collections::{BTreeMap, HashMap};
+
+/// A proof that an account exists, or does not, in the state at some version.
+#[derive(Debug, Clone)]
+pub struct AccountExistenceProof {
+	pub exists: bool,
+	/// The state root the proof is checked against.
+	pub state_root: HashValue,
+	/// The `0x1::account::Account` resource of the account, which is what the proof commits to.
+	pub account_resource: Option<StateValue>,
+	pub proof: SparseMerkleProof,
+}
+
+/// The `0x1::fungible_asset::FungibleStore` resource, a member of the `ObjectGroup` of a store.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct FungibleStore {
+	metadata: AccountAddress,
+	balance: u64,
+	frozen: bool,
+}
+
+impl MoveStructType for FungibleStore {
+	const MODULE_NAME: &'static IdentStr = ident_str!("fungible_asset");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("FungibleStore");
+}
+
+/// Whether a change of the APT supply created or destroyed coins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyChangeType {
+	Mint,
+	Burn,
+}
+
+/// A change of the APT supply made by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyChangeEvent {
+	pub version: Version,
+	pub change_type: SupplyChangeType,
+	pub amount: u64,
+	/// The account the transaction was sent by.
+	pub account: AccountAddress,
+}
+
+/// A Move function, as `0x1::function_info::FunctionInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FunctionInfo {
+	pub module_address: AccountAddress,
+	pub module_name: String,
+	pub function_name: String,
+}
+
+/// The functions a dispatchable fungible asset overrides, from the
+/// `0x1::fungible_asset::DispatchFunctionStore` resource of its metadata object.
+/// Fields are decoded by position, so their order must match the resource's.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DispatchableFunctionStore {
+	pub withdraw_fn: Option<FunctionInfo>,
+	pub deposit_fn: Option<FunctionInfo>,
+	pub derived_balance_fn: Option<FunctionInfo>,
+}
+
+impl MoveStructType for DispatchableFunctionStore {
+	const MODULE_NAME: &'static IdentStr = ident_str!("fungible_asset");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("DispatchFunctionStore");
+}
+
+// The scheme byte of the addresses of objects derived from another address.
+const OBJECT_DERIVED_SCHEME: u8 = 0xFC;
+
+impl AccountExistenceProof {
+	/// Verifies the proof for the given address, without needing access to the database.
+	pub fn verify(&self, address: AccountAddress) -> Result<bool, anyhow::Error> {
+		let key = StateKey::resource_typed::<AccountResource>(&address)?;
+		Ok(self.exists == self.account_resource.is_some()
+			&& Executor::verify_state_proof(
+				self.state_root,
+				&key,
+				&self.proof,
+				&self.account_resource,
+			))
+	}
+}
+
+impl Executor {
+	/// Resolves an optional version to the given one or the latest ledger version.
+	pub(crate) fn version_or_latest(
+		&self,
+		version: Option<Version>,
+	) -> Result<Version, anyhow::Error> {
+		match version {
+			Some(version) => Ok(version),
+			None => Ok(self.db().reader.get_latest_ledger_info_version()?),
+		}
+	}
+
+	/// Reads the value at a state key from the db, counting the read.
+	pub(crate) fn get_state_value(
+		&self,
+		state_key: &StateKey,
+		version: Version,
+	) -> Result<Option<StateValue>, anyhow::Error> {
+		self.metrics.db_reads.inc();
+		Ok(self.db().reader.get_state_value_by_version(state_key, version)?)
+	}
+
+	/// Gets the raw BCS values stored in a Move `Table` for each of the given BCS encoded keys.
+	/// The result preserves the order of `keys`, with `None` for absent entries.
+	/// The key and value types are not used to decode anything, callers decode the bytes.
+	pub fn get_table_entries(
+		&self,
+		table_handle: AccountAddress,
+		key_type: &TypeTag,
+		value_type: &TypeTag,
+		keys: &[Vec<u8>],
+		version: Option<Version>,
+	) -> Result<Vec<Option<Vec<u8>>>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		debug!(
+			%table_handle,
+			%key_type,
+			%value_type,
+			key_count = keys.len(),
+			version,
+			"getting_table_entries"
+		);
+
+		let handle = TableHandle(table_handle);
+		keys.iter()
+			.map(|key| {
+				let state_key = StateKey::table_item(&handle, key);
+				let value = self.get_state_value(&state_key, version)?;
+				Ok(value.map(|value| value.bytes().to_vec()))
+			})
+			.collect()
+	}
+
+	/// Gets the raw BCS value stored in a Move `Table` for a single BCS encoded key,
+	/// or `None` if the entry is absent.
+	/// The value type is not used to decode anything, see [`Executor::decode_table_value`].
+	pub fn get_table_item(
+		&self,
+		table_handle: AccountAddress,
+		key: Vec<u8>,
+		value_type: &TypeTag,
+		version: Option<Version>,
+	) -> Result<Option<Vec<u8>>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		debug!(%table_handle, %value_type, version, "getting_table_item");
+
+		let state_key = StateKey::table_item(&TableHandle(table_handle), &key);
+		let value = self.get_state_value(&state_key, version)?;
+		Ok(value.map(|value| value.bytes().to_vec()))
+	}
+
+	/// Gets the number of entries of a `smart_table::SmartTable` held in the `field` of a
+	/// resource, from the `size` the table keeps, without reading its buckets.
+	/// A smart table is a plain struct stored within its owner's resource, so it is reached from
+	/// the resource rather than from the handle of its buckets table.
+	pub fn get_smart_table_length(
+		&self,
+		address: AccountAddress,
+		resource: &StructTag,
+		field: &str,
+		version: Option<Version>,
+	) -> Result<u64, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let resource_value = self
+			.get_move_resource(address, resource, version)?
+			.ok_or_else(|| anyhow::anyhow!("No {} resource found at {}", resource, address))?;
+		smart_table_size(struct_field(&resource_value, field)?)
+	}
+
+	/// Decodes a raw table value returned by [`Executor::get_table_item`]
+	/// or [`Executor::get_table_entries`].
+	pub fn decode_table_value<T: serde::de::DeserializeOwned>(
+		raw: &[u8],
+	) -> Result<T, anyhow::Error> {
+		Ok(bcs::from_bytes(raw)?)
+	}
+
+	/// Gets the resources stored in a resource group, keyed by their struct tag, as raw BCS bytes.
+	/// Returns an empty map if the group does not exist at the address.
+	pub fn get_resource_group(
+		&self,
+		address: AccountAddress,
+		group_tag: &StructTag,
+		version: Option<Version>,
+	) -> Result<HashMap<StructTag, Vec<u8>>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let state_key = StateKey::resource_group(&address, group_tag);
+		match self.get_state_value(&state_key, version)? {
+			Some(value) => {
+				let group: BTreeMap<StructTag, Vec<u8>> = bcs::from_bytes(value.bytes())?;
+				Ok(group.into_iter().collect())
+			}
+			None => Ok(HashMap::new()),
+		}
+	}
+
+	/// Gets all the resources of a Move object, from its `object::ObjectGroup` resource group,
+	/// as raw BCS bytes ordered by struct tag.
+	/// Returns no resources if there is no object at the address.
+	pub fn get_object_resources(
+		&self,
+		object_address: AccountAddress,
+		version: Option<Version>,
+	) -> Result<Vec<(StructTag, Vec<u8>)>, anyhow::Error> {
+		let group =
+			self.get_resource_group(object_address, &ObjectGroupResource::struct_tag(), version)?;
+		let mut resources: Vec<_> = group.into_iter().collect();
+		resources.sort_by(|(tag, _), (other_tag, _)| tag.cmp(other_tag));
+		Ok(resources)
+	}
+
+	/// Gets the bytecode of all modules deployed at an address, in state key order.
+	pub fn get_account_modules(
+		&self,
+		address: AccountAddress,
+		version: Option<Version>,
+	) -> Result<Vec<(ModuleId, Vec<u8>)>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let prefix = StateKeyPrefix::from(address);
+		let mut modules = Vec::new();
+		self.metrics.db_reads.inc();
+		for entry in self.db().reader.get_prefixed_state_value_iterator(&prefix, None, version)? {
+			let (state_key, state_value) = entry?;
+			if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+				if let Path::Code(module_id) = access_path.get_path() {
+					modules.push((module_id, state_value.bytes().to_vec()));
+				}
+			}
+		}
+		Ok(modules)
+	}
+
+	/// Gets the bytecode of a module, or `None` if it is not deployed.
+	pub fn get_module_bytecode(
+		&self,
+		module_id: &ModuleId,
+		version: Option<Version>,
+	) -> Result<Option<Vec<u8>>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let state_key = StateKey::access_path(AccessPath::code_access_path(module_id.clone()));
+		let value = self.get_state_value(&state_key, version)?;
+		Ok(value.map(|value| value.bytes().to_vec()))
+	}
+
+	/// Gets the state changes made by the transactions of the block at the given height.
+	/// Each key appears once with its final value in the block, `None` if it was deleted.
+	pub fn get_resource_changes_in_block(
+		&self,
+		block_height: u64,
+	) -> Result<Vec<(StateKey, Option<StateValue>)>, anyhow::Error> {
+		let (start_version, end_version, _) =
+			self.db().reader.get_block_info_by_height(block_height)?;
+		let ledger_version = self.db().reader.get_latest_ledger_info_version()?;
+		let outputs = self.db().reader.get_transaction_outputs(
+			start_version,
+			end_version - start_version + 1,
+			ledger_version,
+		)?;
+
+		// keep the order in which keys are first written, with the last written value
+		let mut positions = HashMap::new();
+		let mut changes: Vec<(StateKey, Option<StateValue>)> = Vec::new();
+		for (_, output) in outputs.transactions_and_outputs {
+			for (state_key, write_op) in output.write_set().iter() {
+				let value = write_op.as_state_value();
+				match positions.get(state_key) {
+					Some(&position) => changes[position].1 = value,
+					None => {
+						positions.insert(state_key.clone(), changes.len());
+						changes.push((state_key.clone(), value));
+					}
+				}
+			}
+		}
+		Ok(changes)
+	}
+
+	/// Gets the resource of type `T` of each of the given accounts which holds one.
+	/// Accounts without the resource are left out of the map.
+	/// Every account is a separate db read, so scanning large account sets is expensive.
+	pub fn get_resources_by_type<T: MoveResource>(
+		&self,
+		accounts: &[AccountAddress],
+		version: Option<Version>,
+	) -> Result<HashMap<AccountAddress, T>, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let mut resources = HashMap::new();
+		for address in accounts {
+			let state_key = StateKey::resource_typed::<T>(address)?;
+			if let Some(value) = self.get_state_value(&state_key, version)? {
+				resources.insert(*address, bcs::from_bytes(value.bytes())?);
+			}
+		}
+		Ok(resources)
+	}
+
+	/// Gets the APT coin balance of an account, or zero if it has no coin store.
+	pub fn get_account_balance(
+		&self,
+		address: AccountAddress,
+		version: Option<Version>,
+	) -> Result<u64, anyhow::Error> {
+		let version = self.version_or_latest(version)?;
+		let state_key = StateKey::resource_typed::<CoinStoreResource>(&address)?;
+		match self.get_state_value(&state_key, version)? {
+			Some(value) => Ok(bcs::from_bytes::<CoinStoreResource>(value.bytes())?.coin()),
+			None => Ok(0),
+		}
+	}
+
+	/// Gets the balance of the primary fungible store of `owner` for the asset with the given
+	/// metadata object, or zero if the owner has no store for it.
+	/// Balances are read from the `FungibleStore` only, concurrent balances are not supported.
+	pub fn get_fungible_asset_balance(
+		&self,
+		owner: AccountAddress,
+		metadata: AccountAddress,
+		version: Option<Version>,
+	) -> Result<u128, anyhow::Error> {
+		let store_address = primary_store_address(owner, metadata);
+		let group =
+			self.get_resource_group(store_address, &ObjectGroupResource::struct_tag(), version)?;
+		match group.get(&FungibleStore::struct_tag()) {
+			Some(bytes) => Ok(u128::from(bcs::from_bytes::<FungibleStore>(bytes)?.balance)),
+			None => Ok(0),
+		}
+	}
+
+	/// Gets the functions registered through `dispatchable_fungible_asset` for the asset with
+	/// the given metadata object, or `None` if the asset is not dispatchable.
+	pub fn get_dispatchable_fa_functions(
+		&self,
+		metadata_address: AccountAddress,
+	) -> Result<Option<DispatchableFunctionStore>, anyhow::Error> {
+		let group =
+			self.get_resource_group(metadata_address, &ObjectGroupResource::struct_tag(), None)?;
+		group
+			.get(&DispatchableFunctionStore::struct_tag())
+			.map(|bytes| Ok(bcs::from_bytes(bytes)?))
+			.transpose()
+	}
+
+	/// Gets the total supply of APT, or `None` if supply tracking is disabled.
+	pub fn get_coin_supply(&self) -> Result<Option<u128>, anyhow::Error> {
+		let version = self.version_or_latest(None)?;
+		self.get_coin_supply_at(version)
+	}
+
+	/// Gets the net changes of the APT supply made by the transactions from `start_version` on,
+	/// looking at up to `limit` transactions.
+	/// Mints and burns are not evented, so the supply is compared before and after each
+	/// transaction writing to it, and a transaction which both mints and burns, e.g. by
+	/// burning its gas fee, reports its net change only.
+	/// Changes are attributed to the sender of user transactions, and to the framework for
+	/// other transactions. Nothing is reported while supply tracking is disabled.
+	pub fn get_total_supply_events(
+		&self,
+		start_version: Version,
+		limit: u64,
+	) -> Result<Vec<SupplyChangeEvent>, anyhow::Error> {
+		let ledger_version = self.version_or_latest(None)?;
+		if start_version > ledger_version || limit == 0 {
+			return Ok(Vec::new());
+		}
+		let limit = limit.min(ledger_version - start_version + 1);
+
+		let coin_info_key = StateKey::resource_typed::<CoinInfoResource>(&AccountAddress::ONE)?;
+		let aggregator_key = match self.get_coin_info_at(ledger_version)? {
+			Some(coin_info)
+				if coin_info.supply().as_ref().is_some_and(|s| s.aggregator.is_some()) =>
+			{
+				Some(coin_info.supply_aggregator_state_key())
+			}
+			_ => None,
+		};
+		let outputs =
+			self.db().reader.get_transaction_outputs(start_version, limit, ledger_version)?;
+
+		// there is no supply before genesis
+		let mut previous_supply = match start_version.checked_sub(1) {
+			Some(version) => self.get_coin_supply_at(version)?,
+			None => Some(0),
+		};
+		let mut events = Vec::new();
+		for ((transaction, output), version) in
+			outputs.transactions_and_outputs.into_iter().zip(start_version..)
+		{
+			let writes_supply = output
+				.write_set()
+				.iter()
+				.any(|(key, _)| key == &coin_info_key || Some(key) == aggregator_key.as_ref());
+			if !writes_supply {
+				continue;
+			}
+			let supply = self.get_coin_supply_at(version)?;
+			if let (Some(previous), Some(current)) = (previous_supply, supply) {
+				let change_type = match current.cmp(&previous) {
+					std::cmp::Ordering::Greater => Some(SupplyChangeType::Mint),
+					std::cmp::Ordering::Less => Some(SupplyChangeType::Burn),
+					std::cmp::Ordering::Equal => None,
+				};
+				if let Some(change_type) = change_type {
+					let account = match &transaction {
+						Transaction::UserTransaction(transaction) => transaction.sender(),
+						_ => AccountAddress::ONE,
+					};
+					events.push(SupplyChangeEvent {
+						version,
+						change_type,
+						amount: u64::try_from(current.abs_diff(previous))?,
+						account,
+					});
+				}
+			}
+			previous_supply = supply;
+		}
+		Ok(events)
+	}
+
+	/// Gets the APT coin info at the given version, or `None` if APT is not initialized.
+	fn get_coin_info_at(
+		&self,
+		version: Version,
+	) -> Result<Option<CoinInfoResource>, anyhow::Error> {
+		let state_key = StateKey::resource_typed::<CoinInfoResource>(&AccountAddress::ONE)?;
+		match self.get_state_value(&state_key, version)? {
+			Some(value) => Ok(Some(bcs::from_bytes(value.bytes())?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Gets the total supply of APT at the given version, see [`Executor::get_coin_supply`].
+	fn get_coin_supply_at(&self, version: Version) -> Result<Option<u128>, anyhow::Error> {
+		let Some(coin_info) = self.get_coin_info_at(version)? else {
+			anyhow::bail!("No APT coin info found");
+		};
+
+		let Some(supply) = coin_info.supply() else {
+			return Ok(None);
+		};
+		if let Some(integer) = &supply.integer {
+			return Ok(Some(integer.value));
+		}
+		// a parallelizable supply is kept in an aggregator, which lives in a table item
+		match self.get_state_value(&coin_info.supply_aggregator_state_key(), version)? {
+			Some(value) => Ok(Some(bcs::from_bytes(value.bytes())?)),
+			None => anyhow::bail!("No APT supply aggregator found"),
+		}
+	}
+
+	/// Gets the value at a state key together with its sparse Merkle proof against the
+	/// state root at the given version. The proof also covers absence when the value is `None`.
+	pub fn get_proof_of_state(
+		&self,
+		key: StateKey,
+		version: Version,
+	) -> Result<(Option<StateValue>, SparseMerkleProof), anyhow::Error> {
+		self.metrics.db_reads.inc();
+		Ok(self.db().reader.get_state_value_with_proof_by_version(&key, version)?)
+	}
+
+	/// Proves whether an account exists at the given version, which must end with a state
+	/// checkpoint, as the last version of every block does.
+	pub fn get_proof_of_account_existence(
+		&self,
+		address: AccountAddress,
+		version: Version,
+	) -> Result<AccountExistenceProof, anyhow::Error> {
+		let state_root = self
+			.db()
+			.reader
+			.get_transaction_by_version(
+				version,
+				self.db().reader.get_latest_ledger_info_version()?,
+				false,
+			)?
+			.proof
+			.transaction_info()
+			.state_checkpoint_hash()
+			.ok_or_else(|| anyhow::anyhow!("Version {} is not a state checkpoint", version))?;
+		let key = StateKey::resource_typed::<AccountResource>(&address)?;
+		let (account_resource, proof) = self.get_proof_of_state(key, version)?;
+		Ok(AccountExistenceProof {
+			exists: account_resource.is_some(),
+			state_root,
+			account_resource,
+			proof,
+		})
+	}
+
+	/// Verifies a proof returned by [`Executor::get_proof_of_state`] against a state root,
+	/// without needing access to the database.
+	pub fn verify_state_proof(
+		root: HashValue,
+		key: &StateKey,
+		proof: &SparseMerkleProof,
+		value: &Option<StateValue>,
+	) -> bool {
+		proof.verify(root, key.hash(), value.as_ref()).is_ok()
+	}
+}
+
+/// Gets the `size` of a decoded `smart_table::SmartTable`.
+fn smart_table_size(smart_table: &MoveValue) -> Result<u64, anyhow::Error> {
+	let MoveValue::Struct(smart_table) = smart_table else {
+		anyhow::bail!("Smart table is not a struct");
+	};
+	// the buckets are only checked for, to tell smart tables apart from other structs
+	struct_field(smart_table, "buckets")?;
+	match struct_field(smart_table, "size")? {
+		MoveValue::U64(size) => Ok(*size),
+		_ => anyhow::bail!("Smart table size is not a u64"),
+	}
+}
+
+/// Derives the address of the primary fungible store of `owner`,
+/// as `primary_fungible_store::primary_store_address` does.
+fn primary_store_address(owner: AccountAddress, metadata: AccountAddress) -> AccountAddress {
+	let mut bytes = owner.to_vec();
+	bytes.extend(metadata.as_ref());
+	bytes.push(OBJECT_DERIVED_SCHEME);
+	AccountAddress::new(*HashValue::sha3_256_of(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{primary_store_address, smart_table_size, FungibleStore, SupplyChangeType};
+	use crate::{bootstrap, Executor, TestExecutor};
+	use aptos_cached_packages::{aptos_stdlib, aptos_token_objects_sdk_builder};
+	use aptos_sdk::{
+		move_types::{
+			ident_str,
+			language_storage::{StructTag, TypeTag},
+			move_resource::MoveStructType,
+			value::{MoveStruct, MoveValue},
+		},
+		transaction_builder::TransactionFactory,
+		types::LocalAccount,
+	};
+	use aptos_types::{
+		account_address::AccountAddress,
+		account_config::{
+			aptos_test_root_address, AccountResource, CoinStoreResource, ObjectCoreResource,
+			ObjectGroupResource,
+		},
+		on_chain_config::{FeatureFlag, Features},
+		state_store::state_key::StateKey,
+		transaction::{ExecutionStatus, TransactionStatus},
+	};
+	use std::str::FromStr;
+
+	#[test]
+	fn test_get_fungible_asset_metadata_group() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		// the metadata object of the APT fungible asset lives at 0xa
+		let group = test_executor.executor.get_resource_group(
+			AccountAddress::from_hex_literal("0xa")?,
+			&ObjectGroupResource::struct_tag(),
+			None,
+		)?;
+		assert!(group.contains_key(&ObjectCoreResource::struct_tag()));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_framework_modules() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		let modules = test_executor.executor.get_account_modules(AccountAddress::ONE, None)?;
+		let names: Vec<String> =
+			modules.iter().map(|(module_id, _)| module_id.name().to_string()).collect();
+		for name in ["vector", "signer", "coin", "aptos_account"] {
+			assert!(names.iter().any(|n| n == name), "missing module {}", name);
+		}
+		assert!(modules.iter().all(|(module_id, bytecode)| {
+			module_id.address() == &AccountAddress::ONE && !bytecode.is_empty()
+		}));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_resource_changes_in_block() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let root_account = test_executor.root_account();
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		test_executor.submit_and_execute(transaction).await?;
+
+		let changes = executor.get_resource_changes_in_block(executor.get_block_head_height()?)?;
+		let account_key = StateKey::resource_typed::<AccountResource>(&new_account.address())?;
+		let (_, value) = changes
+			.iter()
+			.find(|(state_key, _)| state_key == &account_key)
+			.expect("the new account resource was written");
+		assert!(value.is_some());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_resources_by_type() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let funded = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(funded, 1_000).await?;
+		let missing = LocalAccount::generate(&mut rand::thread_rng()).address();
+
+		let coin_stores =
+			executor.get_resources_by_type::<CoinStoreResource>(&[funded, missing], None)?;
+		assert_eq!(coin_stores.len(), 1);
+		assert_eq!(coin_stores[&funded].coin(), 1_000);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_missing_fungible_asset_balance() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let owner = LocalAccount::generate(&mut rand::thread_rng()).address();
+		let metadata = LocalAccount::generate(&mut rand::thread_rng()).address();
+
+		let balance = test_executor.executor.get_fungible_asset_balance(owner, metadata, None)?;
+		assert_eq!(balance, 0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_object_resources_of_token() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let mut creator = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(creator.address(), 1_000_000_000).await?;
+
+		let create_collection = creator.sign_with_transaction_builder(tx_factory.payload(
+			aptos_token_objects_sdk_builder::aptos_token_create_collection(
+				b"description".to_vec(),
+				10,
+				b"collection".to_vec(),
+				b"uri".to_vec(),
+				false,
+				false,
+				false,
+				false,
+				false,
+				false,
+				false,
+				false,
+				false,
+				0,
+				1,
+			),
+		));
+		let mint = creator.sign_with_transaction_builder(tx_factory.payload(
+			aptos_token_objects_sdk_builder::aptos_token_mint(
+				b"collection".to_vec(),
+				b"description".to_vec(),
+				b"token".to_vec(),
+				b"uri".to_vec(),
+				vec![],
+				vec![],
+				vec![],
+			),
+		));
+		for transaction in [create_collection, mint] {
+			let output = test_executor.submit_and_execute(transaction).await?;
+			assert_eq!(output.status(), &TransactionStatus::Keep(ExecutionStatus::Success));
+		}
+
+		let tokens = executor.list_nft_token_ids(creator.address(), None, None)?;
+		assert_eq!(tokens.len(), 1);
+		assert_eq!(tokens[0].name, "token");
+		let resources = executor.get_object_resources(tokens[0].token_address, None)?;
+		let token_tag = StructTag::from_str("0x4::token::Token")?;
+		assert!(resources.iter().any(|(tag, _)| tag == &token_tag));
+		assert!(resources.iter().any(|(tag, _)| tag == &ObjectCoreResource::struct_tag()));
+
+		// accounts are not objects
+		assert!(executor.get_object_resources(creator.address(), None)?.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_dispatchable_fa_functions() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		// the APT fungible asset at 0xa uses the default transfer functions
+		let functions = test_executor
+			.executor
+			.get_dispatchable_fa_functions(AccountAddress::from_hex_literal("0xa")?)?;
+		assert_eq!(functions, None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_fungible_asset_balance() -> Result<(), anyhow::Error> {
+		// coins are moved to primary fungible stores by the migration only
+		let mut features = Features::default();
+		features.enable(FeatureFlag::COIN_TO_FUNGIBLE_ASSET_MIGRATION);
+		let mut genesis_config = bootstrap::genesis_configuration();
+		genesis_config.initial_features_override = Some(features);
+		let test_executor = TestExecutor::with_genesis_config(&genesis_config)?;
+		let executor = &test_executor.executor;
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let mut sender = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(sender.address(), 1_000_000_000).await?;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+		// the metadata object of the APT fungible asset lives at 0xa
+		let metadata = AccountAddress::from_hex_literal("0xa")?;
+
+		let migrate = sender.sign_with_transaction_builder(tx_factory.payload(
+			aptos_stdlib::coin_migrate_to_fungible_store(TypeTag::Struct(Box::new(
+				StructTag::from_str("0x1::aptos_coin::AptosCoin")?,
+			))),
+		));
+		let transfer = sender.sign_with_transaction_builder(tx_factory.payload(
+			aptos_stdlib::aptos_account_transfer_fungible_assets(metadata, receiver, 1_000),
+		));
+		for transaction in [migrate, transfer] {
+			let output = test_executor.submit_and_execute(transaction).await?;
+			assert_eq!(output.status(), &TransactionStatus::Keep(ExecutionStatus::Success));
+		}
+
+		// the balance is read from the store at the derived address, created by the transfer
+		assert_eq!(executor.get_fungible_asset_balance(receiver, metadata, None)?, 1_000);
+		let group = executor.get_resource_group(
+			primary_store_address(receiver, metadata),
+			&ObjectGroupResource::struct_tag(),
+			None,
+		)?;
+		assert!(group.contains_key(&FungibleStore::struct_tag()));
+		assert_ne!(
+			primary_store_address(receiver, metadata),
+			primary_store_address(metadata, receiver)
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_absent_table_item() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+
+		let item = test_executor.executor.get_table_item(
+			AccountAddress::random(),
+			bcs::to_bytes(&1u64)?,
+			&TypeTag::U64,
+			None,
+		)?;
+		assert!(item.is_none());
+
+		let raw = bcs::to_bytes(&42u128)?;
+		assert_eq!(Executor::decode_table_value::<u128>(&raw)?, 42);
+		assert!(Executor::decode_table_value::<u128>(&raw[..4]).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_smart_table_size() -> Result<(), anyhow::Error> {
+		let smart_table = MoveValue::Struct(MoveStruct::WithFields(vec![
+			(ident_str!("buckets").to_owned(), MoveValue::Bool(false)),
+			(ident_str!("size").to_owned(), MoveValue::U64(7)),
+		]));
+		assert_eq!(smart_table_size(&smart_table)?, 7);
+
+		let other = MoveValue::Struct(MoveStruct::WithFields(vec![(
+			ident_str!("size").to_owned(),
+			MoveValue::U64(7),
+		)]));
+		assert!(smart_table_size(&other).is_err());
+		assert!(smart_table_size(&MoveValue::U64(7)).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_smart_table_length_of_other_field() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let coin_info = StructTag::from_str("0x1::coin::CoinInfo<0x1::aptos_coin::AptosCoin>")?;
+
+		// the name of the coin is a string, not a smart table
+		let error = test_executor
+			.executor
+			.get_smart_table_length(AccountAddress::ONE, &coin_info, "name", None)
+			.unwrap_err();
+		assert_eq!(error.to_string(), "Struct has no field buckets");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_counts_db_reads() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let db_reads = executor.metrics().db_reads.get();
+		executor.get_account_balance(aptos_test_root_address(), None)?;
+		assert_eq!(executor.metrics().db_reads.get(), db_reads + 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_coin_supply() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let supply = executor.get_coin_supply()?.expect("APT supply is tracked");
+		// genesis stakes 100_000_000 for each test validator and mints the rest of the supply
+		// to the test root account, which has not spent any of it yet
+		let count = executor.get_validators_by_voting_power()?.len() as u128;
+		assert_eq!(count, 1);
+		let root_balance = executor.get_account_balance(aptos_test_root_address(), None)?;
+		assert_eq!(supply - u128::from(root_balance), count * 100_000_000);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_total_supply_events() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		// genesis creates the whole supply
+		let genesis_events = executor.get_total_supply_events(0, 1)?;
+		assert_eq!(genesis_events.len(), 1);
+		assert_eq!(genesis_events[0].change_type, SupplyChangeType::Mint);
+		assert_eq!(Some(u128::from(genesis_events[0].amount)), executor.get_coin_supply_at(0)?);
+
+		// transfers burn their gas fee
+		let start_version = executor.db_reader().get_latest_ledger_info_version()? + 1;
+		let receiver = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(receiver, 1_000).await?;
+		let events = executor.get_total_supply_events(start_version, 100)?;
+		assert_eq!(events.len(), 1);
+		assert_eq!(events[0].change_type, SupplyChangeType::Burn);
+		assert_eq!(events[0].account, aptos_test_root_address());
+		assert!(events[0].amount > 0);
+
+		assert!(executor.get_total_supply_events(u64::MAX, 100)?.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_and_verify_proof_of_state() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		// the genesis transaction always ends with a state checkpoint
+		let version = 0;
+		let root = executor
+			.db()
+			.reader
+			.get_transaction_by_version(version, version, false)?
+			.proof
+			.transaction_info()
+			.state_checkpoint_hash()
+			.ok_or(anyhow::anyhow!("genesis has no state checkpoint"))?;
+
+		let key = StateKey::resource_typed::<AccountResource>(&AccountAddress::ONE)?;
+		let (value, proof) = executor.get_proof_of_state(key.clone(), version)?;
+		assert!(value.is_some());
+		assert!(Executor::verify_state_proof(root, &key, &proof, &value));
+
+		// the proof does not hold for a different value
+		assert!(!Executor::verify_state_proof(root, &key, &proof, &None));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_proof_of_account_existence() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+
+		let proof = executor.get_proof_of_account_existence(AccountAddress::ONE, 0)?;
+		assert!(proof.exists);
+		assert!(proof.verify(AccountAddress::ONE)?);
+		// the proof is bound to the address
+		assert!(!proof.verify(AccountAddress::TWO)?);
+
+		let absent = LocalAccount::generate(&mut rand::thread_rng()).address();
+		let proof = executor.get_proof_of_account_existence(absent, 0)?;
+		assert!(!proof.exists);
+		assert!(proof.verify(absent)?);
+
+		Ok(())
+	}
+}
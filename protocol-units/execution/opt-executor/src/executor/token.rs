@@ -0,0 +1,240 @@
+use super::{governance::struct_field, Executor};
+use aptos_sdk::move_types::{
+	ident_str,
+	identifier::IdentStr,
+	language_storage::StructTag,
+	move_resource::MoveStructType,
+	value::{MoveStruct, MoveValue},
+};
+use aptos_types::{
+	access_path::Path, account_address::AccountAddress, account_config::ObjectGroupResource,
+	event::EventHandle, state_store::state_key::inner::StateKeyInner, transaction::Version,
+};
+use serde::Deserialize;
+
+use anyhow::Context as _;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+const TOKEN_TAG: &str = "0x4::token::Token";
+
+// Number of state values read from the db at once.
+const TOKEN_SCAN_CHUNK_SIZE: usize = 10_000;
+
+/// A digital asset token, identified by the address of its object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenId {
+	pub token_address: AccountAddress,
+	pub collection: AccountAddress,
+	pub name: String,
+}
+
+/// The `0x1::object::ObjectCore` resource.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ObjectCore {
+	guid_creation_num: u64,
+	owner: AccountAddress,
+	allow_ungated_transfer: bool,
+	transfer_events: EventHandle,
+}
+
+impl MoveStructType for ObjectCore {
+	const MODULE_NAME: &'static IdentStr = ident_str!("object");
+	const STRUCT_NAME: &'static IdentStr = ident_str!("ObjectCore");
+}
+
+impl Executor {
+	/// Lists the `token::Token` objects directly owned by `owner`, optionally only those of
+	/// the given collection, ordered by the hash of their state key.
+	/// Objects are not indexed by owner, so this scans all the state at the version, which
+	/// must be a state checkpoint, the latest one if `None`.
+	pub fn list_nft_token_ids(
+		&self,
+		owner: AccountAddress,
+		collection: Option<AccountAddress>,
+		version: Option<Version>,
+	) -> Result<Vec<TokenId>, anyhow::Error> {
+		let reader = self.db_reader();
+		let version = match version {
+			Some(version) => version,
+			None => reader
+				.get_latest_state_checkpoint_version()?
+				.context("No state checkpoint to scan")?,
+		};
+		let object_group_tag = ObjectGroupResource::struct_tag();
+		let token_tag = StructTag::from_str(TOKEN_TAG)?;
+		let mut token_layout = None;
+
+		let key_count = reader.get_state_item_count(version)?;
+		let mut tokens = Vec::new();
+		let mut scanned = 0;
+		while scanned < key_count {
+			let chunk_start = scanned;
+			for entry in
+				reader.get_state_value_chunk_iter(version, scanned, TOKEN_SCAN_CHUNK_SIZE)?
+			{
+				let (state_key, state_value) = entry?;
+				scanned += 1;
+				let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+					continue;
+				};
+				match access_path.get_path() {
+					Path::ResourceGroup(tag) if tag == object_group_tag => {}
+					_ => continue,
+				}
+				let group: BTreeMap<StructTag, Vec<u8>> = bcs::from_bytes(state_value.bytes())?;
+				let (Some(object_core), Some(token)) =
+					(group.get(&ObjectCore::struct_tag()), group.get(&token_tag))
+				else {
+					continue;
+				};
+				if bcs::from_bytes::<ObjectCore>(object_core)?.owner != owner {
+					continue;
+				}
+
+				if token_layout.is_none() {
+					token_layout = Some(self.get_move_type_info(&token_tag, Some(version))?);
+				}
+				let layout = token_layout.as_ref().expect("the token layout was just set");
+				let token = MoveStruct::simple_deserialize(token, layout)?;
+				let token_id = token_id(access_path.address, &token)?;
+				if collection.map_or(true, |collection| collection == token_id.collection) {
+					tokens.push(token_id);
+				}
+			}
+			if scanned == chunk_start {
+				anyhow::bail!(
+					"State at version {} ended after {} of {} keys",
+					version,
+					scanned,
+					key_count
+				);
+			}
+		}
+		Ok(tokens)
+	}
+}
+
+/// Reads the collection and name of a decoded `token::Token`.
+fn token_id(token_address: AccountAddress, token: &MoveStruct) -> Result<TokenId, anyhow::Error> {
+	// the collection is an `Object<Collection>`, which wraps its address
+	let MoveValue::Struct(collection) = struct_field(token, "collection")? else {
+		anyhow::bail!("Token collection is not an object");
+	};
+	let MoveValue::Address(collection) = struct_field(collection, "inner")? else {
+		anyhow::bail!("Token collection is not an object");
+	};
+	let MoveValue::Struct(name) = struct_field(token, "name")? else {
+		anyhow::bail!("Token name is not a string");
+	};
+	let MoveValue::Vector(name) = struct_field(name, "bytes")? else {
+		anyhow::bail!("Token name is not a string");
+	};
+	let name = name
+		.iter()
+		.map(|byte| match byte {
+			MoveValue::U8(byte) => Ok(*byte),
+			_ => anyhow::bail!("Token name is not a string"),
+		})
+		.collect::<Result<Vec<u8>, _>>()?;
+	Ok(TokenId { token_address, collection: *collection, name: String::from_utf8(name)? })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TestExecutor;
+	use aptos_cached_packages::aptos_token_objects_sdk_builder;
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+	use aptos_types::{
+		account_address::create_collection_address,
+		transaction::{ExecutionStatus, TransactionStatus},
+	};
+
+	#[tokio::test]
+	async fn test_list_nft_token_ids_without_tokens() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let owner = LocalAccount::generate(&mut rand::thread_rng()).address();
+		executor.create_account(owner, 1_000).await?;
+
+		// genesis mints no digital assets
+		assert!(executor.list_nft_token_ids(owner, None, None)?.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_list_nft_token_ids() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let executor = &test_executor.executor;
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+		let mut creator = LocalAccount::generate(&mut rand::thread_rng());
+		executor.create_account(creator.address(), 1_000_000_000).await?;
+
+		// mint a token in each of two collections through the 0x4 `aptos_token` module
+		for (collection, token) in [("collection", "token"), ("other collection", "other token")] {
+			let create_collection = creator.sign_with_transaction_builder(tx_factory.payload(
+				aptos_token_objects_sdk_builder::aptos_token_create_collection(
+					b"description".to_vec(),
+					10,
+					collection.as_bytes().to_vec(),
+					b"uri".to_vec(),
+					false,
+					false,
+					false,
+					false,
+					false,
+					false,
+					false,
+					false,
+					false,
+					0,
+					1,
+				),
+			));
+			let mint = creator.sign_with_transaction_builder(tx_factory.payload(
+				aptos_token_objects_sdk_builder::aptos_token_mint(
+					collection.as_bytes().to_vec(),
+					b"description".to_vec(),
+					token.as_bytes().to_vec(),
+					b"uri".to_vec(),
+					vec![],
+					vec![],
+					vec![],
+				),
+			));
+			for transaction in [create_collection, mint] {
+				let output = test_executor.submit_and_execute(transaction).await?;
+				assert_eq!(output.status(), &TransactionStatus::Keep(ExecutionStatus::Success));
+			}
+		}
+
+		let collection = create_collection_address(creator.address(), "collection");
+		let tokens = executor.list_nft_token_ids(creator.address(), Some(collection), None)?;
+		assert_eq!(tokens.len(), 1);
+		assert_eq!(tokens[0].collection, collection);
+		assert_eq!(tokens[0].name, "token");
+		// the token is identified by the address of its object
+		let token_tag = StructTag::from_str(TOKEN_TAG)?;
+		let resources = executor.get_object_resources(tokens[0].token_address, None)?;
+		assert!(resources.iter().any(|(tag, _)| tag == &token_tag));
+
+		let mut tokens = executor.list_nft_token_ids(creator.address(), None, None)?;
+		tokens.sort_by(|a, b| a.name.cmp(&b.name));
+		let other_collection = create_collection_address(creator.address(), "other collection");
+		let collections_and_names: Vec<_> =
+			tokens.iter().map(|token| (token.collection, token.name.as_str())).collect();
+		assert_eq!(
+			collections_and_names,
+			vec![(other_collection, "other token"), (collection, "token")]
+		);
+
+		// the tokens are owned by their creator only
+		let other_owner = LocalAccount::generate(&mut rand::thread_rng()).address();
+		assert!(executor.list_nft_token_ids(other_owner, None, None)?.is_empty());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,57 @@
+use super::Executor;
+
+/// Describes a kind of transaction the executor handles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionTypeDescriptor {
+	/// The name of the `Transaction` variant.
+	pub name: &'static str,
+	/// Whether clients may submit transactions of this kind.
+	pub can_be_submitted: bool,
+	pub description: &'static str,
+}
+
+const SUPPORTED_TRANSACTION_TYPES: &[TransactionTypeDescriptor] = &[
+	TransactionTypeDescriptor {
+		name: "UserTransaction",
+		can_be_submitted: true,
+		description: "A transaction signed by an account, submitted through the REST API.",
+	},
+	TransactionTypeDescriptor {
+		name: "BlockMetadata",
+		can_be_submitted: false,
+		description: "The first transaction of every block, generated by the executor.",
+	},
+	TransactionTypeDescriptor {
+		name: "GenesisTransaction",
+		can_be_submitted: false,
+		description: "The write set bootstrapping the ledger, also used for emergency write sets.",
+	},
+	TransactionTypeDescriptor {
+		name: "StateCheckpoint",
+		can_be_submitted: false,
+		description:
+			"Appended by the block executor to checkpoint the state at the end of a block.",
+	},
+];
+
+impl Executor {
+	/// Lists the kinds of transactions the executor handles.
+	pub fn list_supported_transaction_types() -> &'static [TransactionTypeDescriptor] {
+		SUPPORTED_TRANSACTION_TYPES
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_only_user_transactions_can_be_submitted() {
+		let submittable: Vec<_> = Executor::list_supported_transaction_types()
+			.iter()
+			.filter(|descriptor| descriptor.can_be_submitted)
+			.map(|descriptor| descriptor.name)
+			.collect();
+		assert_eq!(submittable, vec!["UserTransaction"]);
+	}
+}
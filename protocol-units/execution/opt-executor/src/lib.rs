@@ -1,12 +1,21 @@
 pub mod bootstrap;
 pub mod context;
+pub mod event_stream;
 #[warn(unused_imports)]
 pub mod executor;
 pub mod indexer;
+pub mod metrics;
+pub mod registry;
 pub mod service;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod transaction_pipe;
 
 pub use context::Context;
-pub use executor::Executor;
+pub use executor::{Executor, ExecutorError};
+pub use metrics::ExecutorMetrics;
+pub use registry::ExecutorRegistry;
 pub use service::Service;
+#[cfg(any(test, feature = "testing"))]
+pub use testing::TestExecutor;
 pub use transaction_pipe::TransactionPipe;
@@ -0,0 +1,117 @@
+//! Prometheus metrics of the executor, served by the [`Service`](crate::Service) at `/metrics`.
+
+use poem::{handler, web::Data, Response};
+use prometheus::{
+	exponential_buckets, Encoder, Histogram, HistogramOpts, IntCounter, IntGauge, Registry,
+	TextEncoder,
+};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The metrics of an [`Executor`](crate::Executor).
+/// Each executor has a registry of its own, so that several executors can run in a process.
+pub struct ExecutorMetrics {
+	registry: Registry,
+	/// The number of blocks executed and committed.
+	pub blocks_executed: IntCounter,
+	/// The number of transactions in each executed block, including the block metadata.
+	pub transactions_per_block: Histogram,
+	/// The time taken to execute and commit a block.
+	pub block_execution_latency: Histogram,
+	// The number of transactions admitted to the mempool and not yet executed.
+	mempool_depth: IntGauge,
+	/// The number of state values read from the db.
+	pub db_reads: IntCounter,
+	/// The number of block commits written to the db.
+	pub db_writes: IntCounter,
+	// Sampled into `mempool_depth` when the metrics are gathered.
+	transactions_in_flight: Arc<AtomicU64>,
+}
+
+impl ExecutorMetrics {
+	pub fn new(transactions_in_flight: Arc<AtomicU64>) -> Result<Self, anyhow::Error> {
+		let registry = Registry::new();
+		let blocks_executed = IntCounter::new(
+			"executor_blocks_executed_total",
+			"Number of blocks executed and committed",
+		)?;
+		let transactions_per_block = Histogram::with_opts(
+			HistogramOpts::new(
+				"executor_transactions_per_block",
+				"Number of transactions in each executed block",
+			)
+			.buckets(exponential_buckets(1.0, 2.0, 16)?),
+		)?;
+		let block_execution_latency = Histogram::with_opts(HistogramOpts::new(
+			"executor_block_execution_latency_seconds",
+			"Time taken to execute and commit a block",
+		))?;
+		let mempool_depth = IntGauge::new(
+			"executor_mempool_depth",
+			"Number of transactions admitted to the mempool and not yet executed",
+		)?;
+		let db_reads =
+			IntCounter::new("executor_db_reads_total", "Number of state values read from the db")?;
+		let db_writes = IntCounter::new(
+			"executor_db_writes_total",
+			"Number of block commits written to the db",
+		)?;
+
+		registry.register(Box::new(blocks_executed.clone()))?;
+		registry.register(Box::new(transactions_per_block.clone()))?;
+		registry.register(Box::new(block_execution_latency.clone()))?;
+		registry.register(Box::new(mempool_depth.clone()))?;
+		registry.register(Box::new(db_reads.clone()))?;
+		registry.register(Box::new(db_writes.clone()))?;
+
+		Ok(Self {
+			registry,
+			blocks_executed,
+			transactions_per_block,
+			block_execution_latency,
+			mempool_depth,
+			db_reads,
+			db_writes,
+			transactions_in_flight,
+		})
+	}
+
+	/// Renders all metrics in the Prometheus text exposition format.
+	pub fn encode(&self) -> Result<String, anyhow::Error> {
+		self.mempool_depth
+			.set(self.transactions_in_flight.load(Ordering::Relaxed) as i64);
+		let mut buffer = Vec::new();
+		TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+		Ok(String::from_utf8(buffer)?)
+	}
+}
+
+/// Serves the executor metrics to Prometheus scrapers.
+#[handler]
+pub fn metrics(Data(metrics): Data<&Arc<ExecutorMetrics>>) -> poem::Result<Response> {
+	Ok(Response::builder()
+		.content_type(prometheus::TEXT_FORMAT)
+		.body(metrics.encode()?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_encode_metrics() -> Result<(), anyhow::Error> {
+		let transactions_in_flight = Arc::new(AtomicU64::new(7));
+		let metrics = ExecutorMetrics::new(Arc::clone(&transactions_in_flight))?;
+		metrics.blocks_executed.inc();
+		metrics.transactions_per_block.observe(3.0);
+
+		let text = metrics.encode()?;
+		assert!(text.contains("executor_blocks_executed_total 1"));
+		assert!(text.contains("executor_transactions_per_block_count 1"));
+		assert!(text.contains("executor_mempool_depth 7"));
+		assert!(text.contains("executor_db_writes_total 0"));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,102 @@
+//! Registry of executors for running several chains in a single process.
+
+use crate::Executor;
+
+use aptos_types::chain_id::ChainId;
+use maptos_execution_util::config::Config;
+
+use anyhow::Context as _;
+use dashmap::{mapref::entry::Entry, DashMap};
+use tracing::info;
+
+use std::sync::Arc;
+
+/// Keeps track of the executors running in this process, one per chain.
+#[derive(Default)]
+pub struct ExecutorRegistry {
+	executors: DashMap<ChainId, Arc<Executor>>,
+}
+
+impl ExecutorRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Bootstraps an executor for the chain configured in `config` and registers it.
+	/// The database of each chain lives in a subdirectory of the configured DB path named after its chain ID.
+	pub fn spawn(&self, config: &Config) -> Result<Arc<Executor>, anyhow::Error> {
+		let chain_id = config.chain.maptos_chain_id;
+		// the entry stays locked while the executor is bootstrapped, so that concurrent spawns
+		// for the same chain do not both open its DB
+		let entry = match self.executors.entry(chain_id) {
+			Entry::Occupied(_) => {
+				anyhow::bail!("An executor is already registered for chain {}", chain_id)
+			}
+			Entry::Vacant(entry) => entry,
+		};
+
+		let mut config = config.clone();
+		let db_path = config
+			.chain
+			.maptos_db_path
+			.as_ref()
+			.context("No db path provided.")?
+			.join(chain_id.id().to_string());
+		config.chain.maptos_db_path.replace(db_path);
+
+		let executor = Arc::new(Executor::try_from_config(&config)?);
+		entry.insert(Arc::clone(&executor));
+		info!("Registered executor for chain {}", chain_id);
+		Ok(executor)
+	}
+
+	/// Gets the executor registered for the chain.
+	pub fn get(&self, chain_id: ChainId) -> Option<Arc<Executor>> {
+		self.executors.get(&chain_id).map(|executor| Arc::clone(&executor))
+	}
+
+	/// Unregisters the executor of the chain.
+	/// The executor is dropped once all other references to it are released.
+	pub fn stop(&self, chain_id: ChainId) -> Result<(), anyhow::Error> {
+		self.executors
+			.remove(&chain_id)
+			.with_context(|| format!("No executor registered for chain {}", chain_id))?;
+		info!("Unregistered executor for chain {}", chain_id);
+		Ok(())
+	}
+
+	/// Lists the chains with a registered executor.
+	pub fn list(&self) -> Vec<ChainId> {
+		self.executors.iter().map(|entry| *entry.key()).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_spawn_get_stop() -> Result<(), anyhow::Error> {
+		let (mut config, db_path) = Config::for_testing()?;
+		let registry = ExecutorRegistry::new();
+
+		let chain_ids = [ChainId::new(27), ChainId::new(28)];
+		for chain_id in chain_ids {
+			config.chain.maptos_chain_id = chain_id;
+			registry.spawn(&config)?;
+			assert!(db_path.path().join(chain_id.id().to_string()).exists());
+		}
+		assert!(registry.spawn(&config).is_err());
+
+		let mut listed = registry.list();
+		listed.sort_by_key(|chain_id| chain_id.id());
+		assert_eq!(listed, chain_ids);
+		assert!(registry.get(chain_ids[0]).is_some());
+
+		registry.stop(chain_ids[0])?;
+		assert!(registry.get(chain_ids[0]).is_none());
+		assert!(registry.stop(chain_ids[0]).is_err());
+
+		Ok(())
+	}
+}
@@ -1,11 +1,11 @@
-use crate::Context;
+use crate::{event_stream, metrics, Context, ExecutorMetrics};
 
 use aptos_api::{
 	get_api_service,
 	runtime::{get_apis, root_handler, Apis},
 	set_failpoints,
 };
-use aptos_storage_interface::DbReaderWriter;
+use aptos_storage_interface::{DbReader, DbReaderWriter};
 
 use futures::prelude::*;
 use poem::{http::Method, listener::TcpListener, middleware::Cors, EndpointExt, Route, Server};
@@ -20,6 +20,10 @@ pub struct Service {
 	context: Arc<aptos_api::Context>,
 	// URL for the API endpoint
 	listen_url: String,
+	// Reader of the committed ledger, for streaming events
+	db_reader: Arc<dyn DbReader>,
+	// Metrics served to Prometheus scrapers
+	metrics: Arc<ExecutorMetrics>,
 }
 
 impl Service {
@@ -29,6 +33,7 @@ impl Service {
 			mempool_client_sender,
 			maptos_config,
 			node_config,
+			metrics,
 		} = cx;
 		let context = Arc::new(aptos_api::Context::new(
 			maptos_config.chain.maptos_chain_id.clone(),
@@ -42,7 +47,7 @@ impl Service {
 			maptos_config.chain.maptos_rest_listen_hostname,
 			maptos_config.chain.maptos_rest_listen_port
 		);
-		Service { context, listen_url }
+		Service { context, listen_url, db_reader: reader.clone(), metrics: Arc::clone(metrics) }
 	}
 
 	pub fn api_context(&self) -> Arc<aptos_api::Context> {
@@ -70,6 +75,11 @@ impl Service {
 		let listener = TcpListener::bind(self.listen_url.clone());
 		let app = Route::new()
 			.at("/", poem::get(root_handler))
+			.at(
+				"/v1/events/stream",
+				poem::get(event_stream::stream_events).data(Arc::clone(&self.db_reader)),
+			)
+			.at("/metrics", poem::get(metrics::metrics).data(Arc::clone(&self.metrics)))
 			.nest("/v1", api_service)
 			.nest("/spec", ui)
 			.at("/spec.json", poem::get(spec_json))
@@ -0,0 +1,123 @@
+//! Test harness bootstrapping an [`Executor`] over a temporary database.
+
+use crate::{bootstrap, Executor};
+
+use aptos_crypto::{HashValue, PrivateKey};
+use aptos_sdk::types::LocalAccount;
+use aptos_temppath::TempPath;
+use aptos_types::{
+	block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+	block_metadata::BlockMetadata,
+	transaction::{
+		signature_verified_transaction::into_signature_verified_block, SignedTransaction,
+		Transaction, TransactionOutput,
+	},
+};
+use aptos_vm_genesis::GenesisConfiguration;
+use maptos_execution_util::config::Config;
+
+use anyhow::Context as _;
+
+/// An [`Executor`] with test-appropriate defaults, owning its temporary database.
+pub struct TestExecutor {
+	pub executor: Executor,
+	pub config: Config,
+	// The database directory is removed when this is dropped.
+	_db_path: TempPath,
+}
+
+impl TestExecutor {
+	/// Bootstraps a fresh executor with a genesis database in a temporary directory.
+	pub fn new() -> Result<Self, anyhow::Error> {
+		let (config, db_path) = Config::for_testing()?;
+		let executor = Executor::try_from_config(&config)?;
+
+		Ok(Self { executor, config, _db_path: db_path })
+	}
+
+	/// Bootstraps a fresh executor with a genesis database built from `genesis_config`,
+	/// rather than from [`bootstrap::genesis_configuration`].
+	pub fn with_genesis_config(
+		genesis_config: &GenesisConfiguration,
+	) -> Result<Self, anyhow::Error> {
+		let (config, db_path) = Config::for_testing()?;
+		// the executor keeps the database bootstrapped here, as it is not empty
+		bootstrap::maybe_bootstrap_empty_db(
+			db_path.path(),
+			config.chain.maptos_chain_id.clone(),
+			&config.chain.maptos_private_key.public_key(),
+			&config.rocksdb,
+			genesis_config,
+		)?;
+		let executor = Executor::try_from_config(&config)?;
+
+		Ok(Self { executor, config, _db_path: db_path })
+	}
+
+	/// Gets the root account of the executor, see [`Executor::root_account`].
+	pub fn root_account(&self) -> LocalAccount {
+		self.executor.root_account()
+	}
+
+	/// Executes the transaction in a block of its own and returns its output.
+	pub async fn submit_and_execute(
+		&self,
+		transaction: SignedTransaction,
+	) -> Result<TransactionOutput, anyhow::Error> {
+		let transaction_hash = transaction.committed_hash();
+		let (epoch, round) = self.executor.get_next_epoch_and_round()?;
+		let block_id = HashValue::random();
+		let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+			block_id,
+			epoch,
+			round,
+			self.executor.signer.author(),
+			vec![],
+			vec![],
+			chrono::Utc::now().timestamp_micros() as u64,
+		));
+		let transactions = ExecutableTransactions::Unsharded(into_signature_verified_block(vec![
+			block_metadata,
+			Transaction::UserTransaction(transaction),
+		]));
+		self.executor
+			.execute_block(ExecutableBlock::new(block_id, transactions))
+			.await?;
+
+		let reader = self.executor.db_reader();
+		let ledger_version = reader.get_latest_ledger_info_version()?;
+		let version = reader
+			.get_transaction_by_hash(transaction_hash, ledger_version, false)?
+			.context("Transaction was not committed")?
+			.version;
+		let (_, output) = reader
+			.get_transaction_outputs(version, 1, ledger_version)?
+			.transactions_and_outputs
+			.pop()
+			.context("No output found for the committed transaction")?;
+		Ok(output)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+	use aptos_types::transaction::ExecutionStatus;
+
+	#[tokio::test]
+	async fn test_submit_and_execute() -> Result<(), anyhow::Error> {
+		let test_executor = TestExecutor::new()?;
+		let root_account = test_executor.root_account();
+		let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+
+		let new_account = LocalAccount::generate(&mut rand::thread_rng());
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.create_user_account(new_account.public_key()),
+		);
+		let output = test_executor.submit_and_execute(transaction).await?;
+
+		assert_eq!(output.status().status(), Ok(ExecutionStatus::Success));
+		Ok(())
+	}
+}
@@ -1,6 +1,7 @@
 //! Task processing incoming transactions for the opt API.
 
 use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
 use aptos_mempool::core_mempool::CoreMempool;
 use aptos_mempool::SubmissionStatus;
 use aptos_mempool::{core_mempool::TimelineState, MempoolClientRequest};
@@ -16,10 +17,11 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{debug, info, info_span, warn, Instrument};
 
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(any(test, feature = "testing"))]
+use std::sync::atomic::Ordering;
 use std::sync::{atomic::AtomicU64, Arc};
-use std::time::{Duration, Instant};
-
-const GC_INTERVAL: Duration = Duration::from_secs(30);
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Domain error for the transaction pipe task
 #[derive(Debug, Clone, Error)]
@@ -53,6 +55,16 @@ pub struct TransactionPipe {
 	in_flight_limit: u64,
 	// Timestamp of the last garbage collection
 	last_gc: Instant,
+	// The interval between garbage collections
+	gc_interval: Duration,
+	// Hashes of the transactions added to the mempool, by expiration timestamp in seconds
+	expirations: BTreeMap<u64, Vec<HashValue>>,
+	// The node config the mempool was created with, to recreate it on reset
+	#[cfg(any(test, feature = "testing"))]
+	node_config: NodeConfig,
+	// The number of mempool resets requested through the executor, and applied by the pipe
+	#[cfg(any(test, feature = "testing"))]
+	mempool_resets: (Arc<AtomicU64>, u64),
 }
 
 impl TransactionPipe {
@@ -63,6 +75,7 @@ impl TransactionPipe {
 		node_config: &NodeConfig,
 		transactions_in_flight: Arc<AtomicU64>,
 		transactions_in_flight_limit: u64,
+		gc_interval: Duration,
 	) -> Self {
 		TransactionPipe {
 			mempool_client_receiver,
@@ -72,9 +85,23 @@ impl TransactionPipe {
 			transactions_in_flight,
 			in_flight_limit: transactions_in_flight_limit,
 			last_gc: Instant::now(),
+			gc_interval,
+			expirations: BTreeMap::new(),
+			#[cfg(any(test, feature = "testing"))]
+			node_config: node_config.clone(),
+			#[cfg(any(test, feature = "testing"))]
+			mempool_resets: (Arc::new(AtomicU64::new(0)), 0),
 		}
 	}
 
+	/// Shares the counter of the mempool resets requested through the executor.
+	#[cfg(any(test, feature = "testing"))]
+	pub(crate) fn with_mempool_resets(mut self, mempool_resets: Arc<AtomicU64>) -> Self {
+		let applied = mempool_resets.load(Ordering::Acquire);
+		self.mempool_resets = (mempool_resets, applied);
+		self
+	}
+
 	pub async fn run(mut self) -> Result<(), Error> {
 		loop {
 			self.tick().await?;
@@ -84,7 +111,22 @@ impl TransactionPipe {
 	/// Pipes a batch of transactions from the mempool to the transaction channel.
 	/// todo: it may be wise to move the batching logic up a level to the consuming structs.
 	pub(crate) async fn tick(&mut self) -> Result<(), Error> {
-		let next = self.mempool_client_receiver.next().await;
+		// wake up for garbage collection even when no requests are coming in
+		let next = tokio::select! {
+			next = self.mempool_client_receiver.next() => next,
+			_ = tokio::time::sleep_until((self.last_gc + self.gc_interval).into()) => None,
+		};
+
+		// apply the resets requested while waiting, before serving any further request
+		#[cfg(any(test, feature = "testing"))]
+		{
+			let requested = self.mempool_resets.0.load(Ordering::Acquire);
+			if requested != self.mempool_resets.1 {
+				self.reset_mempool();
+				self.mempool_resets.1 = requested;
+			}
+		}
+
 		if let Some(request) = next {
 			match request {
 				MempoolClientRequest::SubmitTransaction(transaction, callback) => {
@@ -109,14 +151,56 @@ impl TransactionPipe {
 			}
 		}
 
-		if self.last_gc.elapsed() >= GC_INTERVAL {
+		if self.last_gc.elapsed() >= self.gc_interval {
 			self.core_mempool.gc();
+			let now_usecs = SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map_err(|e| anyhow::anyhow!("System time is before the UNIX epoch: {e}"))?
+				.as_micros() as u64;
+			let evicted = self.evict_expired_transactions(now_usecs);
+			if evicted > 0 {
+				info!(evicted, "evicted_expired_transactions");
+			}
 			self.last_gc = Instant::now();
 		}
 
 		Ok(())
 	}
 
+	/// Evicts the transactions that expired before `current_time_usecs` from the mempool,
+	/// returning the number of transactions evicted.
+	pub fn evict_expired_transactions(&mut self, current_time_usecs: u64) -> usize {
+		let current_time = Duration::from_micros(current_time_usecs);
+		// the mempool drops transactions expiring strictly before the current time
+		let live = self.expirations.split_off(&current_time_usecs.div_ceil(1_000_000));
+		let expired = std::mem::replace(&mut self.expirations, live);
+		let evicted = self.count_held_transactions(expired);
+		self.core_mempool.gc_by_expiration_time(current_time);
+		evicted
+	}
+
+	// Counts the distinct transactions still held in the mempool among the given ones.
+	// A transaction submitted again is recorded once per submission.
+	fn count_held_transactions(&self, expirations: BTreeMap<u64, Vec<HashValue>>) -> usize {
+		expirations
+			.into_values()
+			.flatten()
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.filter(|hash| self.core_mempool.get_by_hash(*hash).is_some())
+			.count()
+	}
+
+	/// Discards all transactions held in the mempool, for teardown between test cases.
+	/// It is requested through [`crate::Executor::reset_mempool`].
+	#[cfg(any(test, feature = "testing"))]
+	fn reset_mempool(&mut self) {
+		let expirations = std::mem::take(&mut self.expirations);
+		let discarded = self.count_held_transactions(expirations);
+		self.core_mempool = CoreMempool::new(&self.node_config);
+		debug!(discarded, "reset_mempool");
+	}
+
 	async fn submit_transaction(
 		&mut self,
 		transaction: SignedTransaction,
@@ -173,6 +257,10 @@ impl TransactionPipe {
 		match status.code {
 			MempoolStatusCode::Accepted => {
 				debug!("Transaction accepted: {:?}", transaction);
+				self.expirations
+					.entry(transaction.expiration_timestamp_secs())
+					.or_default()
+					.push(transaction.committed_hash());
 				let sender = transaction.sender();
 				self.transaction_sender
 					.send(transaction)
@@ -195,8 +283,6 @@ impl TransactionPipe {
 #[cfg(test)]
 mod tests {
 
-	use std::collections::BTreeSet;
-
 	use super::*;
 	use crate::{Executor, Service};
 	use aptos_api::{accept_type::AcceptType, transactions::SubmitTransactionPost};
@@ -326,6 +412,41 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_reset_mempool() -> Result<(), anyhow::Error> {
+		let (tx_sender, _tx_receiver) = mpsc::channel(16);
+		let (executor, config, _tempdir) = Executor::try_test_default(GENESIS_KEYPAIR.0.clone())?;
+		let (context, mut transaction_pipe) = executor.background(tx_sender, &config)?;
+		let mut mempool_client_sender = context.mempool_client_sender();
+
+		// the sequence number is ahead of the account's, so the transaction stays in the mempool
+		let user_transaction = create_signed_transaction(1, &config.chain);
+		let (req_sender, callback) = oneshot::channel();
+		mempool_client_sender
+			.send(MempoolClientRequest::SubmitTransaction(user_transaction.clone(), req_sender))
+			.await?;
+		transaction_pipe.tick().await?;
+		let (status, _vm_status_code) = callback.await??;
+		assert_eq!(status.code, MempoolStatusCode::Accepted);
+		assert!(executor.metrics().encode()?.contains("executor_mempool_depth 1"));
+
+		executor.reset_mempool();
+		assert!(executor.metrics().encode()?.contains("executor_mempool_depth 0"));
+
+		// the reset is applied before the next request is served
+		let (req_sender, callback) = oneshot::channel();
+		mempool_client_sender
+			.send(MempoolClientRequest::GetTransactionByHash(
+				user_transaction.committed_hash(),
+				req_sender,
+			))
+			.await?;
+		transaction_pipe.tick().await?;
+		assert!(callback.await?.is_none());
+
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_pipe_mempool_from_api() -> Result<(), anyhow::Error> {
 		let (tx_sender, mut tx_receiver) = mpsc::channel(16);
@@ -462,4 +583,38 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_evict_expired_transactions() -> Result<(), anyhow::Error> {
+		let maptos_config = Config::default();
+		let (mut transaction_pipe, _mempool_client_sender, _tx_receiver) = setup();
+		let user_transaction = create_signed_transaction(1, &maptos_config);
+		let hash = user_transaction.committed_hash();
+		let expiration_secs = user_transaction.expiration_timestamp_secs();
+
+		// park the transaction in the mempool without committing it
+		let status = transaction_pipe.core_mempool.add_txn(
+			user_transaction,
+			0,
+			0,
+			TimelineState::NonQualified,
+			true,
+		);
+		assert_eq!(status.code, MempoolStatusCode::Accepted);
+		// a transaction submitted twice is recorded twice, and still evicted once
+		transaction_pipe.expirations.entry(expiration_secs).or_default().push(hash);
+		transaction_pipe.expirations.entry(expiration_secs).or_default().push(hash);
+
+		// nothing is evicted up to the expiration time
+		let at_expiration = expiration_secs * 1_000_000;
+		assert_eq!(transaction_pipe.evict_expired_transactions(at_expiration), 0);
+		assert!(transaction_pipe.core_mempool.get_by_hash(hash).is_some());
+
+		let after_expiration = expiration_secs * 1_000_000 + 1;
+		assert_eq!(transaction_pipe.evict_expired_transactions(after_expiration), 1);
+		assert!(transaction_pipe.core_mempool.get_by_hash(hash).is_none());
+		assert!(transaction_pipe.expirations.is_empty());
+
+		Ok(())
+	}
 }
@@ -0,0 +1,67 @@
+//! Executes a full block of coin transfers from genesis and checks the resulting balances.
+
+use maptos_opt_executor::TestExecutor;
+
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+use aptos_types::{
+	block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+	block_metadata::BlockMetadata,
+	transaction::{signature_verified_transaction::into_signature_verified_block, Transaction},
+};
+
+const TRANSFER_COUNT: u64 = 10;
+
+#[tokio::test]
+async fn test_execute_block_of_transfers() -> Result<(), anyhow::Error> {
+	let test_executor = TestExecutor::new()?;
+	let executor = &test_executor.executor;
+	let root_account = test_executor.root_account();
+	let tx_factory = TransactionFactory::new(test_executor.config.chain.maptos_chain_id);
+
+	// the genesis transaction is the only one committed
+	assert_eq!(executor.db_reader().get_latest_ledger_info()?.ledger_info().version(), 0);
+
+	let recipients: Vec<LocalAccount> = (0..TRANSFER_COUNT)
+		.map(|_| LocalAccount::generate(&mut rand::thread_rng()))
+		.collect();
+	let mut transactions = Vec::with_capacity(TRANSFER_COUNT as usize + 1);
+	let block_id = HashValue::random();
+	let (epoch, round) = executor.get_next_epoch_and_round()?;
+	transactions.push(Transaction::BlockMetadata(BlockMetadata::new(
+		block_id,
+		epoch,
+		round,
+		executor.signer.author(),
+		vec![],
+		vec![],
+		chrono::Utc::now().timestamp_micros() as u64,
+	)));
+	for (i, recipient) in recipients.iter().enumerate() {
+		let transaction = root_account.sign_with_transaction_builder(
+			tx_factory.transfer(recipient.address(), 100 * (i as u64 + 1)),
+		);
+		transactions.push(Transaction::UserTransaction(transaction));
+	}
+	let last_transfer_hash = transactions.last().expect("block is not empty").hash();
+
+	let transactions =
+		ExecutableTransactions::Unsharded(into_signature_verified_block(transactions));
+	executor.execute_block(ExecutableBlock::new(block_id, transactions)).await?;
+
+	for (i, recipient) in recipients.iter().enumerate() {
+		assert_eq!(executor.get_account_balance(recipient.address(), None)?, 100 * (i as u64 + 1));
+	}
+
+	// the block metadata is at version 1, followed by the transfers and the block's state checkpoint
+	let ledger_version = executor.db_reader().get_latest_ledger_info()?.ledger_info().version();
+	let last_transfer_version = executor
+		.db_reader()
+		.get_transaction_by_hash(last_transfer_hash, ledger_version, false)?
+		.expect("last transfer was committed")
+		.version;
+	assert_eq!(last_transfer_version, TRANSFER_COUNT + 1);
+	assert_eq!(ledger_version, TRANSFER_COUNT + 2);
+
+	Ok(())
+}
@@ -1,11 +1,13 @@
 use super::common::{
-	default_maptos_chain_id, default_maptos_private_key, default_maptos_rest_listen_hostname,
+	default_maptos_block_commit_channel_capacity, default_maptos_chain_id,
+	default_maptos_private_key, default_maptos_rest_listen_hostname,
 	default_maptos_rest_listen_port,
 };
-use aptos_crypto::ed25519::Ed25519PrivateKey;
+use anyhow::Context;
+use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
@@ -27,6 +29,23 @@ pub struct Config {
 
 	/// The path to the Aptos database
 	pub maptos_db_path: Option<PathBuf>,
+
+	/// The path to a TOML file setting the feature flags enabled at genesis
+	#[serde(default)]
+	pub maptos_genesis_features_path: Option<PathBuf>,
+
+	/// Whether the node accepts custom telemetry events, committed to the ledger as their own
+	/// transactions
+	#[serde(default)]
+	pub maptos_custom_events_enabled: bool,
+
+	/// The number of block commits buffered for each subscriber before the oldest are dropped
+	#[serde(default = "default_maptos_block_commit_channel_capacity")]
+	pub maptos_block_commit_channel_capacity: usize,
+
+	/// The public key of the offline signer of block proposals, which are rejected if unset
+	#[serde(default)]
+	pub maptos_proposal_signer_public_key: Option<Ed25519PublicKey>,
 }
 
 impl Default for Config {
@@ -37,6 +56,167 @@ impl Default for Config {
 			maptos_rest_listen_port: default_maptos_rest_listen_port(),
 			maptos_private_key: default_maptos_private_key(),
 			maptos_db_path: None,
+			maptos_genesis_features_path: None,
+			maptos_custom_events_enabled: false,
+			maptos_block_commit_channel_capacity: default_maptos_block_commit_channel_capacity(),
+			maptos_proposal_signer_public_key: None,
+		}
+	}
+}
+
+/// Builds a [`Config`], validating each field as it is set.
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+	config: Config,
+	public_key: Option<Ed25519PublicKey>,
+}
+
+impl ConfigBuilder {
+	/// Starts from the default configuration, meant for development.
+	pub fn from_defaults() -> Self {
+		Self { config: Config::default(), public_key: None }
+	}
+
+	pub fn chain_id(mut self, chain_id: ChainId) -> Self {
+		self.config.maptos_chain_id = chain_id;
+		self
+	}
+
+	pub fn rest_listen_hostname(mut self, hostname: String) -> Result<Self, anyhow::Error> {
+		if hostname.trim().is_empty() {
+			anyhow::bail!("REST listen hostname must not be empty");
+		}
+		self.config.maptos_rest_listen_hostname = hostname;
+		Ok(self)
+	}
+
+	pub fn rest_listen_port(mut self, port: u16) -> Result<Self, anyhow::Error> {
+		if port == 0 {
+			anyhow::bail!("REST listen port must not be 0");
+		}
+		self.config.maptos_rest_listen_port = port;
+		Ok(self)
+	}
+
+	pub fn private_key(mut self, private_key: Ed25519PrivateKey) -> Self {
+		self.config.maptos_private_key = private_key;
+		self
+	}
+
+	/// Sets the expected public key of the signing key, checked by [`ConfigBuilder::build`].
+	/// The key is not stored in the configuration, the node signs with the private key.
+	/// Its length is already enforced by decoding it as an Ed25519 public key.
+	pub fn public_key(mut self, public_key: Ed25519PublicKey) -> Self {
+		self.public_key = Some(public_key);
+		self
+	}
+
+	/// Sets the database path, whose parent directory must exist and be writable.
+	pub fn db_path(mut self, path: PathBuf) -> Result<Self, anyhow::Error> {
+		let parent = match path.parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent,
+			_ => Path::new("."),
+		};
+		let metadata = std::fs::metadata(parent).with_context(|| {
+			format!("Database parent directory {} is missing", parent.display())
+		})?;
+		if !metadata.is_dir() {
+			anyhow::bail!("Database parent {} is not a directory", parent.display());
+		}
+		if metadata.permissions().readonly() {
+			anyhow::bail!("Database parent directory {} is not writable", parent.display());
+		}
+		self.config.maptos_db_path = Some(path);
+		Ok(self)
+	}
+
+	/// Sets the genesis feature flags file, which must exist.
+	pub fn genesis_features_path(mut self, path: PathBuf) -> Result<Self, anyhow::Error> {
+		if !path.is_file() {
+			anyhow::bail!("Genesis features file {} does not exist", path.display());
+		}
+		self.config.maptos_genesis_features_path = Some(path);
+		Ok(self)
+	}
+
+	pub fn custom_events_enabled(mut self, enabled: bool) -> Self {
+		self.config.maptos_custom_events_enabled = enabled;
+		self
+	}
+
+	pub fn block_commit_channel_capacity(mut self, capacity: usize) -> Result<Self, anyhow::Error> {
+		if capacity == 0 {
+			anyhow::bail!("Block commit channel capacity must not be 0");
 		}
+		self.config.maptos_block_commit_channel_capacity = capacity;
+		Ok(self)
+	}
+
+	/// Sets the public key of the offline signer of block proposals.
+	pub fn proposal_signer_public_key(mut self, public_key: Ed25519PublicKey) -> Self {
+		self.config.maptos_proposal_signer_public_key = Some(public_key);
+		self
+	}
+
+	/// Checks the fields against each other and builds the configuration.
+	pub fn build(self) -> Result<Config, anyhow::Error> {
+		if let Some(public_key) = &self.public_key {
+			if public_key != &Ed25519PublicKey::from(&self.config.maptos_private_key) {
+				anyhow::bail!("Public key does not match the configured private key");
+			}
+		}
+		if let (Some(db_path), Some(features_path)) =
+			(&self.config.maptos_db_path, &self.config.maptos_genesis_features_path)
+		{
+			if features_path.starts_with(db_path) {
+				anyhow::bail!(
+					"Genesis features file {} is inside the database directory",
+					features_path.display()
+				);
+			}
+		}
+		Ok(self.config)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aptos_crypto::Uniform;
+
+	#[test]
+	fn test_build_validates_fields() -> Result<(), anyhow::Error> {
+		let dir = tempfile::tempdir()?;
+		let private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+		let config = ConfigBuilder::from_defaults()
+			.chain_id(ChainId::test())
+			.rest_listen_port(30800)?
+			.private_key(private_key.clone())
+			.public_key(Ed25519PublicKey::from(&private_key))
+			.db_path(dir.path().join("db"))?
+			.build()?;
+		assert_eq!(config.maptos_chain_id, ChainId::test());
+		assert_eq!(config.maptos_rest_listen_port, 30800);
+		assert_eq!(config.maptos_db_path, Some(dir.path().join("db")));
+
+		assert!(ConfigBuilder::from_defaults().rest_listen_port(0).is_err());
+		assert!(ConfigBuilder::from_defaults().block_commit_channel_capacity(0).is_err());
+		assert!(ConfigBuilder::from_defaults().rest_listen_hostname(" ".to_string()).is_err());
+		assert!(ConfigBuilder::from_defaults().db_path(dir.path().join("missing/db")).is_err());
+		assert!(ConfigBuilder::from_defaults()
+			.genesis_features_path(dir.path().join("features.toml"))
+			.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_build_rejects_mismatched_public_key() -> Result<(), anyhow::Error> {
+		let other_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+		let error = ConfigBuilder::from_defaults()
+			.public_key(Ed25519PublicKey::from(&other_key))
+			.build()
+			.unwrap_err();
+		assert_eq!(error.to_string(), "Public key does not match the configured private key");
+		Ok(())
 	}
 }
@@ -26,6 +26,14 @@ env_default!(
 // The default Maptos API connection port
 env_default!(default_maptos_rest_connection_port, "MAPTOS_API_CONNECTION_PORT", u16, 30731);
 
+// The default number of block commits buffered for each subscriber
+env_default!(
+	default_maptos_block_commit_channel_capacity,
+	"MAPTOS_BLOCK_COMMIT_CHANNEL_CAPACITY",
+	usize,
+	1024
+);
+
 // The default faucet API listen hostname
 env_default!(
 	default_maptos_faucet_rest_listen_hostname,
@@ -135,3 +143,25 @@ env_default!(
 );
 
 env_default!(default_max_transactions_in_flight, "MAPTOS_MAX_TRANSACTIONS_IN_FLIGHT", u64, 12000);
+
+env_default!(default_mempool_capacity, "MAPTOS_MEMPOOL_CAPACITY", usize, 2_000_000);
+
+env_default!(default_mempool_gc_interval_ms, "MAPTOS_MEMPOOL_GC_INTERVAL_MS", u64, 30_000);
+
+env_default!(default_rocksdb_is_test, "MAPTOS_ROCKSDB_IS_TEST", bool, true);
+
+env_default!(
+	default_rocksdb_block_cache_bytes,
+	"MAPTOS_ROCKSDB_BLOCK_CACHE_BYTES",
+	u64,
+	8 * (1 << 20)
+);
+
+env_default!(
+	default_rocksdb_max_total_wal_size_bytes,
+	"MAPTOS_ROCKSDB_MAX_TOTAL_WAL_SIZE_BYTES",
+	u64,
+	1 << 30
+);
+
+env_default!(default_rocksdb_max_open_files, "MAPTOS_ROCKSDB_MAX_OPEN_FILES", i32, 5000);
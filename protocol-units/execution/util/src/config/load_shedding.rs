@@ -1,8 +1,10 @@
 //! Configuration for load-sheding limits.
 
-use super::common::default_max_transactions_in_flight;
+use super::common::{
+	default_max_transactions_in_flight, default_mempool_capacity, default_mempool_gc_interval_ms,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
@@ -10,10 +12,53 @@ pub struct Config {
 	/// before new transactions are rejected.
 	#[serde(default = "default_max_transactions_in_flight")]
 	pub max_transactions_in_flight: u64,
+
+	/// The maximum number of transactions held in the mempool.
+	#[serde(default = "default_mempool_capacity")]
+	pub mempool_capacity: usize,
+
+	/// The interval in milliseconds between evictions of expired transactions
+	/// from the mempool. It must not be 0.
+	#[serde(
+		default = "default_mempool_gc_interval_ms",
+		deserialize_with = "deserialize_mempool_gc_interval_ms"
+	)]
+	pub mempool_gc_interval_ms: u64,
 }
 
 impl Default for Config {
 	fn default() -> Self {
-		Self { max_transactions_in_flight: default_max_transactions_in_flight() }
+		Self {
+			max_transactions_in_flight: default_max_transactions_in_flight(),
+			mempool_capacity: default_mempool_capacity(),
+			mempool_gc_interval_ms: default_mempool_gc_interval_ms(),
+		}
+	}
+}
+
+// Rejects a zero interval, with which the mempool GC would run in a busy loop.
+fn deserialize_mempool_gc_interval_ms<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let interval = u64::deserialize(deserializer)?;
+	if interval == 0 {
+		return Err(serde::de::Error::custom("mempool_gc_interval_ms must not be 0"));
+	}
+	Ok(interval)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rejects_zero_mempool_gc_interval() -> Result<(), anyhow::Error> {
+		let config: Config = serde_json::from_str(r#"{"mempool_gc_interval_ms": 500}"#)?;
+		assert_eq!(config.mempool_gc_interval_ms, 500);
+
+		let error = serde_json::from_str::<Config>(r#"{"mempool_gc_interval_ms": 0}"#).unwrap_err();
+		assert!(error.to_string().contains("mempool_gc_interval_ms must not be 0"));
+		Ok(())
 	}
 }
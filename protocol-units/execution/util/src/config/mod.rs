@@ -6,8 +6,17 @@ pub mod fin;
 pub mod indexer;
 pub mod indexer_processor;
 pub mod load_shedding;
+pub mod rocksdb;
 
+use anyhow::Context;
+use aptos_crypto::{
+	ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+	Uniform, ValidCryptoMaterialStringExt,
+};
+use aptos_temppath::TempPath;
+use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
@@ -38,6 +47,10 @@ pub struct Config {
 	/// The load shedding parameters
 	#[serde(default)]
 	pub load_shedding: load_shedding::Config,
+
+	/// The RocksDB tuning parameters
+	#[serde(default)]
+	pub rocksdb: rocksdb::Config,
 }
 
 impl Default for Config {
@@ -50,10 +63,103 @@ impl Default for Config {
 			faucet: faucet::Config::default(),
 			fin: fin::Config::default(),
 			load_shedding: load_shedding::Config::default(),
+			rocksdb: rocksdb::Config::default(),
 		}
 	}
 }
 
+impl Config {
+	/// Builds a configuration for tests, with a fresh signing key, the test chain id,
+	/// and the database in a new temporary directory.
+	/// The returned [`TempPath`] removes the directory when dropped,
+	/// so it must be kept alive for as long as the database is in use.
+	pub fn for_testing() -> Result<(Self, TempPath), anyhow::Error> {
+		let db_path = TempPath::new();
+		db_path.create_as_dir()?;
+
+		let mut config = Config::default();
+		config.chain.maptos_chain_id = ChainId::test();
+		config.chain.maptos_private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+		config.chain.maptos_db_path = Some(db_path.path().to_path_buf());
+		Ok((config, db_path))
+	}
+
+	/// Builds a configuration from `MOVEMENT_*` environment variables.
+	///
+	/// See [`Config::from_env_with_prefix`] for the variables that are read.
+	pub fn from_env() -> Result<Self, anyhow::Error> {
+		Self::from_env_with_prefix("MOVEMENT")
+	}
+
+	/// Builds a configuration from environment variables named `{prefix}_*`.
+	///
+	/// The following variables are read, and unset ones keep their defaults:
+	/// - `{prefix}_DB_PATH`: path to the Aptos database.
+	/// - `{prefix}_CHAIN_ID`: the chain id, either numeric or a named chain.
+	/// - `{prefix}_PRIVATE_KEY`: hex-encoded Ed25519 signing key.
+	/// - `{prefix}_PUBLIC_KEY`: hex-encoded Ed25519 public key; the node signs with
+	///   a private key, so this is checked against the configured signing key.
+	/// - `{prefix}_OPT_LISTEN_URL`: REST listen address as `[scheme://]host:port`.
+	/// - `{prefix}_MEMPOOL_CAPACITY`: maximum number of transactions in the mempool.
+	pub fn from_env_with_prefix(prefix: &str) -> Result<Self, anyhow::Error> {
+		let var = |name: &str| -> Result<Option<(String, String)>, anyhow::Error> {
+			let key = format!("{prefix}_{name}");
+			match std::env::var(&key) {
+				Ok(value) => Ok(Some((key, value))),
+				Err(std::env::VarError::NotPresent) => Ok(None),
+				Err(e) => Err(anyhow::anyhow!("failed to read {key}: {e}")),
+			}
+		};
+
+		let mut config = Config::default();
+
+		if let Some((_, value)) = var("DB_PATH")? {
+			config.chain.maptos_db_path = Some(PathBuf::from(value));
+		}
+
+		if let Some((key, value)) = var("CHAIN_ID")? {
+			config.chain.maptos_chain_id = ChainId::from_str(&value)
+				.with_context(|| format!("{key} is not a valid chain id: {value:?}"))?;
+		}
+
+		if let Some((key, value)) = var("PRIVATE_KEY")? {
+			config.chain.maptos_private_key = Ed25519PrivateKey::from_encoded_string(&value)
+				.with_context(|| format!("{key} is not a valid Ed25519 private key"))?;
+		}
+
+		if let Some((key, value)) = var("PUBLIC_KEY")? {
+			let public_key = Ed25519PublicKey::from_encoded_string(&value)
+				.with_context(|| format!("{key} is not a valid Ed25519 public key: {value:?}"))?;
+			let expected = Ed25519PublicKey::from(&config.chain.maptos_private_key);
+			if public_key != expected {
+				anyhow::bail!("{key} does not match the public key of the configured private key");
+			}
+		}
+
+		if let Some((key, value)) = var("OPT_LISTEN_URL")? {
+			let address = value.split_once("://").map_or(value.as_str(), |(_, rest)| rest);
+			let address = address.trim_end_matches('/');
+			let (hostname, port) = address
+				.rsplit_once(':')
+				.with_context(|| format!("{key} must be of the form host:port: {value:?}"))?;
+			if hostname.is_empty() {
+				anyhow::bail!("{key} is missing a hostname: {value:?}");
+			}
+			config.chain.maptos_rest_listen_hostname = hostname.to_string();
+			config.chain.maptos_rest_listen_port =
+				port.parse().with_context(|| format!("{key} has an invalid port: {value:?}"))?;
+		}
+
+		if let Some((key, value)) = var("MEMPOOL_CAPACITY")? {
+			config.load_shedding.mempool_capacity = value
+				.parse()
+				.with_context(|| format!("{key} is not a valid capacity: {value:?}"))?;
+		}
+
+		Ok(config)
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MaptosConfig {
 	pub maptos_config: Config,
@@ -64,3 +170,63 @@ impl Default for MaptosConfig {
 		Self { maptos_config: Config::default() }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_for_testing_creates_db_dir() -> Result<(), anyhow::Error> {
+		let (config, db_path) = Config::for_testing()?;
+		assert_eq!(config.chain.maptos_chain_id, ChainId::test());
+		assert_eq!(config.chain.maptos_db_path.as_deref(), Some(db_path.path()));
+		assert!(db_path.path().is_dir());
+
+		let (other, _other_db_path) = Config::for_testing()?;
+		assert_ne!(other.chain.maptos_private_key, config.chain.maptos_private_key);
+		assert_ne!(other.chain.maptos_db_path, config.chain.maptos_db_path);
+		Ok(())
+	}
+
+	#[test]
+	fn test_from_env_with_prefix_reads_variables() -> Result<(), anyhow::Error> {
+		let private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+		let public_key = Ed25519PublicKey::from(&private_key);
+		std::env::set_var("FROM_ENV_OK_DB_PATH", "/tmp/movement-db");
+		std::env::set_var("FROM_ENV_OK_CHAIN_ID", "42");
+		std::env::set_var("FROM_ENV_OK_PRIVATE_KEY", private_key.to_encoded_string()?);
+		std::env::set_var("FROM_ENV_OK_PUBLIC_KEY", public_key.to_encoded_string()?);
+		std::env::set_var("FROM_ENV_OK_OPT_LISTEN_URL", "http://127.0.0.1:30800/");
+		std::env::set_var("FROM_ENV_OK_MEMPOOL_CAPACITY", "1000");
+
+		let config = Config::from_env_with_prefix("FROM_ENV_OK")?;
+		assert_eq!(config.chain.maptos_db_path, Some(PathBuf::from("/tmp/movement-db")));
+		assert_eq!(config.chain.maptos_chain_id, ChainId::new(42));
+		assert_eq!(config.chain.maptos_private_key, private_key);
+		assert_eq!(config.chain.maptos_rest_listen_hostname, "127.0.0.1");
+		assert_eq!(config.chain.maptos_rest_listen_port, 30800);
+		assert_eq!(config.load_shedding.mempool_capacity, 1000);
+		Ok(())
+	}
+
+	#[test]
+	fn test_from_env_with_prefix_uses_defaults() -> Result<(), anyhow::Error> {
+		let config = Config::from_env_with_prefix("FROM_ENV_UNSET")?;
+		let default = Config::default();
+		assert_eq!(config.chain.maptos_db_path, default.chain.maptos_db_path);
+		assert_eq!(config.chain.maptos_chain_id, default.chain.maptos_chain_id);
+		assert_eq!(config.load_shedding, default.load_shedding);
+		Ok(())
+	}
+
+	#[test]
+	fn test_from_env_with_prefix_names_invalid_variable() {
+		std::env::set_var("FROM_ENV_BAD_MEMPOOL_CAPACITY", "lots");
+		let error = Config::from_env_with_prefix("FROM_ENV_BAD").unwrap_err();
+		assert!(error.to_string().contains("FROM_ENV_BAD_MEMPOOL_CAPACITY"));
+
+		std::env::set_var("FROM_ENV_BAD_KEY_PUBLIC_KEY", "0x00");
+		let error = Config::from_env_with_prefix("FROM_ENV_BAD_KEY").unwrap_err();
+		assert!(error.to_string().contains("FROM_ENV_BAD_KEY_PUBLIC_KEY"));
+	}
+}
@@ -0,0 +1,39 @@
+//! Configuration for tuning the RocksDB instances backing the Aptos DB.
+
+use super::common::{
+	default_rocksdb_block_cache_bytes, default_rocksdb_is_test, default_rocksdb_max_open_files,
+	default_rocksdb_max_total_wal_size_bytes,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+	/// Whether to open the DB with the test defaults, ignoring the tuning parameters below.
+	#[serde(default = "default_rocksdb_is_test")]
+	pub is_test: bool,
+
+	/// The size of the block cache of each DB, in bytes.
+	#[serde(default = "default_rocksdb_block_cache_bytes")]
+	pub block_cache_bytes: u64,
+
+	/// The total size of the write-ahead logs above which the memtables they back are flushed,
+	/// in bytes. RocksDB's `max_total_wal_size`, the Aptos DB does not expose the write buffer size.
+	#[serde(default = "default_rocksdb_max_total_wal_size_bytes")]
+	pub max_total_wal_size_bytes: u64,
+
+	/// The maximum number of files each DB keeps open, -1 for unlimited.
+	#[serde(default = "default_rocksdb_max_open_files")]
+	pub max_open_files: i32,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			is_test: default_rocksdb_is_test(),
+			block_cache_bytes: default_rocksdb_block_cache_bytes(),
+			max_total_wal_size_bytes: default_rocksdb_max_total_wal_size_bytes(),
+			max_open_files: default_rocksdb_max_open_files(),
+		}
+	}
+}